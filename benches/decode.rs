@@ -0,0 +1,224 @@
+//! Benchmarks the decoding of the responses of frequently polled endpoints.
+//!
+//! Run with `cargo bench --bench decode`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use roboat::bedev2::NonTradableLimitedDetails;
+use roboat::catalog::ItemDetails;
+use roboat::economy::{Listing, Reseller, ResellersPageRef};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::hint::black_box;
+
+/// The sizes of the pages/batches benchmarked.
+const SIZES: [usize; 3] = [10, 30, 100];
+
+fn resellers_body(listings: usize) -> Vec<u8> {
+    let data: Vec<Value> = (0..listings)
+        .map(|i| {
+            json!({
+                "userAssetId": 1_000_000_000 + i,
+                "seller": {
+                    "hasVerifiedBadge": false,
+                    "id": 10_000 + i,
+                    "type": "User",
+                    "name": format!("reseller_{}", i),
+                },
+                "price": 5_000 + i,
+                "serialNumber": if i % 2 == 0 { json!(i) } else { Value::Null },
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&json!({
+        "previousPageCursor": null,
+        "nextPageCursor": "1_2_3_4_5_6_7_8_9",
+        "data": data,
+    }))
+    .unwrap()
+}
+
+fn item_details_body(items: usize) -> Vec<u8> {
+    let data: Vec<Value> = (0..items)
+        .map(|i| {
+            json!({
+                "id": 1_000 + i,
+                "itemType": "Asset",
+                "assetType": 8,
+                "name": format!("Item {}", i),
+                "description": "A description that is long enough to be representative of a real item.",
+                "productId": 2_000 + i,
+                "genres": ["All"],
+                "bundledItems": [{"owned": false, "id": 1, "name": "Part", "type": "Asset"}],
+                "itemStatus": [],
+                "itemRestrictions": ["Limited"],
+                "creatorHasVerifiedBadge": true,
+                "creatorType": "User",
+                "creatorTargetId": 1,
+                "creatorName": "Roblox",
+                "lowestPrice": 10_000 + i,
+                "lowestResalePrice": 10_000 + i,
+                "purchaseCount": 0,
+                "favoriteCount": 12_345,
+                "offSaleDeadline": null,
+                "hasResellers": true,
+                "saleLocationType": "NotApplicable",
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&json!({ "data": data })).unwrap()
+}
+
+fn non_tradable_limited_details_body(items: usize) -> Vec<u8> {
+    let data: Vec<Value> = (0..items)
+        .map(|i| {
+            json!({
+                "collectibleItemId": format!("a4b5cb79-5218-4ca1-93fa-{:012}", i),
+                "name": format!("Item {}", i),
+                "description": "A description that is long enough to be representative of a real item.",
+                "collectibleProductId": format!("61f2e366-9fe6-4562-8ce3-{:012}", i),
+                "creatorHasVerifiedBadge": false,
+                "creatorType": "User",
+                "itemTargetId": 1_000 + i,
+                "creatorId": 1,
+                "creatorName": "creator",
+                "price": 100,
+                "lowestPrice": 100,
+                "unitsAvailableForConsumption": 500,
+                "offSaleDeadline": "2024-01-01T00:00:00Z",
+                "assetStock": 1000,
+                "errorCode": null,
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&data).unwrap()
+}
+
+/// The way resellers were decoded before borrowed parsing, kept as a baseline.
+mod baseline {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ResellersResponse {
+        #[allow(dead_code)]
+        pub previous_page_cursor: Option<String>,
+        pub next_page_cursor: Option<String>,
+        pub data: Vec<ListingRaw>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ListingRaw {
+        pub user_asset_id: u64,
+        pub seller: ResellerRaw,
+        pub price: u64,
+        pub serial_number: Option<u64>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    pub struct ResellerRaw {
+        pub has_verified_badge: bool,
+        pub id: u64,
+        #[serde(rename = "type")]
+        pub seller_type: Option<Value>,
+        pub name: String,
+    }
+
+    pub fn resellers(bytes: &[u8]) -> (Vec<Listing>, Option<String>) {
+        let raw: ResellersResponse = serde_json::from_slice(bytes).unwrap();
+
+        let mut listings = Vec::new();
+
+        for listing in raw.data {
+            listings.push(Listing {
+                uaid: listing.user_asset_id,
+                price: listing.price,
+                reseller: Reseller {
+                    user_id: listing.seller.id,
+                    name: listing.seller.name,
+                },
+                serial_number: listing.serial_number,
+            });
+        }
+
+        (listings, raw.next_page_cursor)
+    }
+}
+
+fn bench_resellers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resellers");
+
+    for size in SIZES {
+        let body = resellers_body(size);
+        group.throughput(Throughput::Bytes(body.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("baseline", size), &body, |b, body| {
+            b.iter(|| baseline::resellers(black_box(body)))
+        });
+
+        group.bench_with_input(BenchmarkId::new("owned", size), &body, |b, body| {
+            b.iter(|| {
+                ResellersPageRef::parse(black_box(body))
+                    .unwrap()
+                    .into_owned()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("borrowed", size), &body, |b, body| {
+            b.iter(|| ResellersPageRef::parse(black_box(body)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_item_details(c: &mut Criterion) {
+    let mut group = c.benchmark_group("item_details");
+
+    for size in SIZES {
+        let body = item_details_body(size);
+        group.throughput(Throughput::Bytes(body.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("value", size), &body, |b, body| {
+            b.iter(|| serde_json::from_slice::<Value>(black_box(body)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("parse_bulk", size), &body, |b, body| {
+            b.iter(|| ItemDetails::parse_bulk(black_box(body)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_non_tradable_limited_details(c: &mut Criterion) {
+    let mut group = c.benchmark_group("non_tradable_limited_details");
+
+    for size in SIZES {
+        let body = non_tradable_limited_details_body(size);
+        group.throughput(Throughput::Bytes(body.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("value", size), &body, |b, body| {
+            b.iter(|| serde_json::from_slice::<Value>(black_box(body)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("parse_bulk", size), &body, |b, body| {
+            b.iter(|| NonTradableLimitedDetails::parse_bulk(black_box(body)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_resellers,
+    bench_item_details,
+    bench_non_tradable_limited_details
+);
+criterion_main!(benches);
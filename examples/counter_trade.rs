@@ -0,0 +1,40 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    #[arg(long)]
+    trade_id: u64,
+    #[arg(long, num_args = 1.., value_delimiter = ',', required = true)]
+    your_uaids: Vec<u64>,
+    #[arg(long)]
+    your_robux: u64,
+    #[arg(long, num_args = 1.., value_delimiter = ',', required = true)]
+    partner_uaids: Vec<u64>,
+    #[arg(long)]
+    partner_robux: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity.clone())
+        .build();
+
+    let new_trade_id = client
+        .counter_trade(
+            args.trade_id,
+            args.your_uaids,
+            args.your_robux,
+            args.partner_uaids,
+            args.partner_robux,
+        )
+        .await?;
+
+    println!("Countered Trade! New Trade ID: {}", new_trade_id);
+
+    Ok(())
+}
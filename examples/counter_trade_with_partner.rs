@@ -0,0 +1,37 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    #[arg(long)]
+    trade_id: u64,
+    #[arg(long)]
+    robux_decrease: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity.clone())
+        .build();
+
+    let details = client.trade_details(args.trade_id).await?;
+
+    let new_trade_id = client
+        .counter_trade_with_partner(
+            details.trade_id,
+            details.partner.user_id,
+            details.your_uaids(),
+            details.your_robux.saturating_sub(args.robux_decrease),
+            details.partner_uaids(),
+            details.partner_robux,
+        )
+        .await?;
+
+    println!("Countered Trade! New Trade ID: {}", new_trade_id);
+
+    Ok(())
+}
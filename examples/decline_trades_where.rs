@@ -0,0 +1,48 @@
+use clap::Parser;
+use roboat::trades::{TradeFilter, TradeType};
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    /// Declines (cancels) outbound trades instead of inbound trades.
+    #[arg(long)]
+    outbound: bool,
+    /// Only declines trades older than this many hours.
+    #[arg(long, default_value_t = 24)]
+    older_than_hours: u64,
+    /// The maximum amount of trades declined at the same time.
+    #[arg(long, default_value_t = 4)]
+    max_concurrency: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let trade_type = match args.outbound {
+        true => TradeType::Outbound,
+        false => TradeType::Inbound,
+    };
+
+    let filter =
+        TradeFilter::new().older_than(Duration::from_secs(args.older_than_hours * 60 * 60));
+    let report = client
+        .decline_trades_where(trade_type, filter, args.max_concurrency)
+        .await?;
+
+    println!("Checked {} trades", report.checked);
+    println!("Declined: {:?}", report.declined);
+
+    for (trade_id, error) in report.failed {
+        println!("Failed to decline {}: {}", trade_id, error);
+    }
+
+    Ok(())
+}
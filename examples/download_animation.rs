@@ -28,7 +28,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Save to file
     let filename = format!("asset_{}.bin", asset_id);
     let path = Path::new(&filename);
-    let mut file = File::create(&path)?;
+    let mut file = File::create(path)?;
     file.write_all(&data_blob)?;
 
     println!("Saved asset to {}", filename);
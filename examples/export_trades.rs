@@ -0,0 +1,45 @@
+use clap::Parser;
+use roboat::trades::export::{ExportFormat, TradeExporter};
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+use std::sync::Arc;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    /// The file the trades are written to. Ending it with `.csv` writes csv instead of json lines.
+    #[arg(long, short, default_value = "trades.jsonl")]
+    output: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = Arc::new(
+        ClientBuilder::new()
+            .roblosecurity(args.roblosecurity)
+            .build(),
+    );
+
+    let format = match args.output.ends_with(".csv") {
+        true => ExportFormat::Csv,
+        false => ExportFormat::JsonLines,
+    };
+
+    let report = TradeExporter::new(client)
+        .format(format)
+        .export(&args.output)
+        .await?;
+
+    println!(
+        "Exported {} trades ({} this run) to {}",
+        report.exported, report.exported_this_run, args.output
+    );
+
+    for (trade_id, error) in &report.failed {
+        println!("Skipped trade {}: {}", trade_id, error);
+    }
+
+    Ok(())
+}
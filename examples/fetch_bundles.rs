@@ -0,0 +1,35 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+use roboat::{Limit, RoboatError};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    user_id: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new().build();
+
+    let mut cursor = None;
+
+    loop {
+        let (bundles, next_cursor) = client.bundles(args.user_id, Limit::Hundred, cursor).await?;
+
+        for bundle in bundles {
+            println!(
+                "{} - bundle id: {}, type: {:?}, creator: {}",
+                bundle.name, bundle.bundle_id, bundle.bundle_type, bundle.creator_name
+            );
+        }
+
+        match next_cursor {
+            Some(x) => cursor = Some(x),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
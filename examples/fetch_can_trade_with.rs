@@ -0,0 +1,26 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    #[arg(long, short)]
+    user_id: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let can_trade_with = client.can_trade_with(args.user_id).await?;
+
+    println!("Can Trade: {}", can_trade_with.can_trade);
+    println!("Status: {}", can_trade_with.status);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    user_id: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new().build();
+
+    let can_view = client.can_view_inventory(args.user_id).await?;
+
+    println!("Inventory Visible: {}", can_view);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+use roboat::{Limit, RoboatError};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    user_id: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new().build();
+
+    let mut cursor = None;
+
+    loop {
+        let (collectibles, next_cursor) = client
+            .collectibles(args.user_id, Limit::Hundred, cursor)
+            .await?;
+
+        for collectible in collectibles {
+            println!(
+                "{} - uaid: {}, serial: {:?}, rap: {}, on hold: {}",
+                collectible.name,
+                collectible.uaid,
+                collectible.serial_number,
+                collectible.rap,
+                collectible.is_on_hold
+            );
+        }
+
+        match next_cursor {
+            Some(x) => cursor = Some(x),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
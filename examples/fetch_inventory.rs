@@ -0,0 +1,42 @@
+use clap::Parser;
+use roboat::catalog::AssetType;
+use roboat::ClientBuilder;
+use roboat::{Limit, RoboatError};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    user_id: u64,
+    /// The id of the asset type, such as 8 for hats.
+    #[arg(long, short, default_value_t = 8)]
+    asset_type_id: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new().build();
+
+    let asset_type = AssetType::from(args.asset_type_id);
+    let mut cursor = None;
+
+    loop {
+        let (items, next_cursor) = client
+            .inventory(args.user_id, asset_type, Limit::Hundred, cursor)
+            .await?;
+
+        for item in items {
+            println!(
+                "{} - asset id: {}, uaid: {}, obtained: {}",
+                item.name, item.asset_id, item.uaid, item.created
+            );
+        }
+
+        match next_cursor {
+            Some(x) => cursor = Some(x),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use clap::Parser;
+use roboat::catalog::ItemType;
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    user_id: u64,
+    #[arg(long, short)]
+    item_id: u64,
+    /// The type of the item, such as "Asset", "Bundle", "GamePass" or "Badge".
+    #[arg(long, short = 't', default_value = "Asset")]
+    item_type: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new().build();
+
+    let item_type = ItemType::from(args.item_type.as_str());
+    let owned = client
+        .is_owned(args.user_id, item_type, args.item_id)
+        .await?;
+
+    println!("Owned: {}", owned);
+
+    Ok(())
+}
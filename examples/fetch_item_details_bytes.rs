@@ -0,0 +1,19 @@
+use roboat::catalog::{Item, ItemDetails, ItemType};
+use roboat::ClientBuilder;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let item = Item {
+        item_type: ItemType::Asset,
+        id: 1365767,
+    };
+
+    let client = ClientBuilder::new().build();
+
+    let bytes = client.item_details_bytes(vec![item]).await?;
+    let details = ItemDetails::parse_bulk(&bytes)?;
+
+    dbg!(details);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use roboat::bedev2::NonTradableLimitedDetails;
+use roboat::ClientBuilder;
+
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let collectible_item_id = "a4b5cb79-5218-4ca1-93fa-1e3436f595ef".to_owned();
+
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let bytes = client
+        .non_tradable_limited_details_bytes(vec![collectible_item_id])
+        .await?;
+    let details = NonTradableLimitedDetails::parse_bulk(&bytes)?;
+
+    dbg!(details);
+
+    Ok(())
+}
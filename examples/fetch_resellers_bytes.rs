@@ -0,0 +1,35 @@
+use clap::Parser;
+use roboat::economy::ResellersPageRef;
+use roboat::ClientBuilder;
+use roboat::Limit;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let item_id = 1365767;
+    let limit = Limit::Ten;
+    let cursor = None;
+
+    let bytes = client.resellers_bytes(item_id, limit, cursor).await?;
+    let page = ResellersPageRef::parse(&bytes)?;
+
+    for listing in page.listings {
+        println!(
+            "Uaid {} for {} by {}",
+            listing.uaid, listing.price, listing.reseller.name
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use clap::Parser;
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let metadata = client.trade_metadata().await?;
+
+    println!("Max Items Per Side: {}", metadata.max_items_per_side);
+    println!("Min Value Ratio: {}", metadata.min_value_ratio);
+    println!("Max Robux Share: {}", metadata.max_robux_share);
+    println!("Robux Tax Rate: {}", metadata.robux_tax_rate);
+
+    Ok(())
+}
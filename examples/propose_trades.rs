@@ -0,0 +1,66 @@
+use clap::Parser;
+use roboat::trades::proposals::{ProposalConstraints, TradeProposer};
+use roboat::{ClientBuilder, Limit, RoboatError};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    #[arg(long, short)]
+    partner_id: u64,
+    /// The minimum rap gained for a trade to be proposed.
+    #[arg(long, short, default_value_t = 1)]
+    min_gain: i64,
+    /// Asset ids that are never offered.
+    #[arg(long, short)]
+    keep: Vec<u64>,
+    /// Sends this many of the best proposals instead of only printing them.
+    #[arg(long, default_value_t = 0)]
+    send: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let user_id = client.user_id().await?;
+    let (ours, _) = client.collectibles(user_id, Limit::Hundred, None).await?;
+    let (theirs, _) = client
+        .collectibles(args.partner_id, Limit::Hundred, None)
+        .await?;
+
+    let constraints = args.keep.iter().fold(
+        ProposalConstraints::new().min_gain(args.min_gain),
+        |x, y| x.keep_asset(*y),
+    );
+
+    let proposer = TradeProposer::new()
+        .metadata(client.trade_metadata().await?)
+        .constraints(constraints);
+
+    let proposals = proposer.propose(&ours, &theirs);
+
+    for proposal in &proposals {
+        println!(
+            "Give {:?} for {:?} (net gain {})",
+            proposal.your_uaids(),
+            proposal.partner_uaids(),
+            proposal.net_gain
+        );
+    }
+
+    for result in proposer
+        .send(&client, args.partner_id, &proposals, args.send)
+        .await
+    {
+        match result {
+            Ok(trade_id) => println!("Sent Trade! Trade ID: {}", trade_id),
+            Err(e) => println!("Error: {}", e),
+        }
+    }
+
+    Ok(())
+}
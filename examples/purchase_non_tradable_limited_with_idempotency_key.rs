@@ -0,0 +1,73 @@
+use clap::Parser;
+use roboat::{ClientBuilder, RoboatError};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    #[arg(long, short)]
+    item_id: u64,
+    #[arg(long, short)]
+    price: u64,
+    /// The key of the purchase. Reuse the key printed by a failed run to retry it
+    /// without buying the item twice.
+    #[arg(long, short = 'k')]
+    idempotency_key: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let item_id = args.item_id;
+    let price = args.price;
+
+    let idempotency_key = args
+        .idempotency_key
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    println!("Idempotency Key: {}", idempotency_key);
+
+    let collectible_item_id = client.collectible_item_id(item_id).await?;
+
+    let collectible_product_id = client
+        .collectible_product_id(collectible_item_id.clone())
+        .await?;
+
+    let collectible_creator_id = client
+        .collectible_creator_id(collectible_item_id.clone())
+        .await?;
+
+    // Retrying with the same key cannot buy the item twice.
+    for _ in 0..3 {
+        match client
+            .purchase_non_tradable_limited_with_idempotency_key(
+                collectible_item_id.clone(),
+                collectible_product_id.clone(),
+                collectible_creator_id,
+                price,
+                idempotency_key.clone(),
+            )
+            .await
+        {
+            Ok(receipt) => {
+                println!(
+                    "Purchased item {} for {} robux. Result: {}",
+                    item_id, price, receipt.purchase_result
+                );
+                return Ok(());
+            }
+            Err(RoboatError::ReqwestError(e)) => {
+                println!("Request failed, retrying with the same key: {}", e)
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    println!("Purchase not confirmed, rerun with the same idempotency key to retry it");
+
+    Ok(())
+}
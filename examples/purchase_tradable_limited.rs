@@ -45,7 +45,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await;
 
     match result {
-        Ok(()) => println!("Purchased item for {} robux.", price),
+        Ok(receipt) => println!("Purchased item for {} robux.", receipt.price),
         Err(e) => println!("Failed to purchase item for {} robux. Reason: {}", price, e),
     }
 
@@ -0,0 +1,63 @@
+use clap::Parser;
+use reqwest::Method;
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+use serde::Deserialize;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    #[arg(long, short)]
+    user_id: u64,
+}
+
+#[derive(Deserialize)]
+struct CountResponse {
+    count: u64,
+}
+
+#[derive(Deserialize)]
+struct UsersResponse {
+    data: Vec<UserResponse>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserResponse {
+    id: u64,
+    name: String,
+    display_name: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let response: CountResponse = client
+        .request(
+            Method::GET,
+            "https://friends.roblox.com/v1/user/friend-requests/count",
+        )
+        .send_json()
+        .await?;
+
+    println!("Pending friend requests: {}", response.count);
+
+    // This endpoint only reads data, even though it uses POST.
+    let response: UsersResponse = client
+        .request(Method::POST, "https://users.roblox.com/v1/users")
+        .json(&serde_json::json!({ "userIds": [args.user_id] }))
+        .mutating(false)
+        .send_json()
+        .await?;
+
+    for user in response.data {
+        println!("{} ({}) - id: {}", user.name, user.display_name, user.id);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use clap::Parser;
+use roboat::trades::responder::{ResponderEvent, ResponderRules, TradeResponder};
+use roboat::ClientBuilder;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    /// The minimum rap gained for a trade to be accepted.
+    #[arg(long, short, default_value_t = 0)]
+    min_net_gain: i64,
+    /// Accepts and declines trades instead of only printing the decisions.
+    #[arg(long)]
+    live: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let client = Arc::new(
+        ClientBuilder::new()
+            .roblosecurity(args.roblosecurity)
+            .build(),
+    );
+
+    let rules = ResponderRules::new().min_net_gain(args.min_net_gain);
+
+    let (_handle, mut events) = TradeResponder::new(client, rules)
+        .poll_interval(Duration::from_secs(30))
+        .dry_run(!args.live)
+        .spawn();
+
+    while let Some(event) = events.recv().await {
+        match event {
+            Ok(ResponderEvent::Handled(trade)) => println!(
+                "Trade {} from {}: {:?} ({:?}, net gain {})",
+                trade.trade_id,
+                trade.partner.username,
+                trade.decision,
+                trade.outcome,
+                trade.analysis.net_gain
+            ),
+            Ok(ResponderEvent::Skipped { trade_id, error }) => {
+                eprintln!("Trade {} skipped: {}", trade_id, error)
+            }
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    }
+}
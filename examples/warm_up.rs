@@ -0,0 +1,42 @@
+use clap::Parser;
+use roboat::latency::PURCHASE_DOMAINS;
+use roboat::ClientBuilder;
+use roboat::RoboatError;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// If set, the user id and x-csrf-token are fetched as part of the warm up.
+    #[arg(long, short)]
+    roblosecurity: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+
+    let mut builder = ClientBuilder::new().low_latency();
+
+    if let Some(roblosecurity) = args.roblosecurity {
+        builder = builder.roblosecurity(roblosecurity);
+    }
+
+    let client = builder.build();
+
+    let report = client.warm_up(&PURCHASE_DOMAINS).await?;
+
+    for domain in &report.domains {
+        println!(
+            "{}: {:?} cold, {:?} warm",
+            domain.domain, domain.first_request, domain.round_trip
+        );
+    }
+
+    println!("Session Fetched: {}", report.session_fetched);
+
+    for domain in PURCHASE_DOMAINS {
+        let round_trip = client.round_trip_time(domain).await?;
+        println!("{}: {:?}", domain, round_trip);
+    }
+
+    Ok(())
+}
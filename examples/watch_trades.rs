@@ -0,0 +1,50 @@
+use clap::Parser;
+use roboat::trades::watcher::{TradeEvent, TradeWatcher};
+use roboat::ClientBuilder;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+    /// The file the seen trades are saved to, so that a restart does not report them again.
+    #[arg(long, short, default_value = "trade_watcher.json")]
+    state_file: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let client = Arc::new(
+        ClientBuilder::new()
+            .roblosecurity(args.roblosecurity)
+            .build(),
+    );
+
+    let (_handle, mut events) = TradeWatcher::new(client)
+        .poll_interval(Duration::from_secs(30))
+        .state_file(args.state_file)
+        .spawn();
+
+    while let Some(event) = events.recv().await {
+        match event {
+            Ok(TradeEvent::NewInbound(x)) => println!(
+                "New trade {} from {}",
+                x.trade.trade_id, x.trade.partner.username
+            ),
+            Ok(event) => {
+                let watched = event.trade();
+
+                println!(
+                    "{:?} trade {} with {}: {}",
+                    watched.trade_type,
+                    watched.trade.trade_id,
+                    watched.trade.partner.username,
+                    watched.trade.status
+                );
+            }
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use clap::Parser;
+use roboat::{Client, ClientBuilder, RoboatError};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, short)]
+    roblosecurity: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RoboatError> {
+    let args = Args::parse();
+    let client = ClientBuilder::new()
+        .roblosecurity(args.roblosecurity)
+        .build();
+
+    let response = Client::with_meta(client.robux()).await;
+
+    if let Some(meta) = &response.meta {
+        println!("Url: {}", meta.url);
+        println!("Status: {}", meta.status);
+        println!("Elapsed: {:?}", meta.elapsed);
+        println!("Machine Id: {:?}", meta.machine_id());
+        println!("Correlation Id: {:?}", meta.correlation_id());
+        println!("Rate Limit Remaining: {:?}", meta.rate_limit_remaining());
+        println!("Rate Limit Reset: {:?}", meta.rate_limit_reset());
+    }
+
+    match response.data {
+        Ok(robux) => println!("Robux: {}", robux),
+        Err(RoboatError::TooManyRequests) => {
+            let retry_after = response.meta.and_then(|x| x.retry_after());
+            println!("Rate limited, retry after {:?}", retry_after);
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
@@ -98,7 +98,7 @@ impl Client {
             Err(e) => match e {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
-                    self.fetch_asset_metadata_internal(asset_id).await
+                    Self::retry(self.fetch_asset_metadata_internal(asset_id)).await
                 }
                 _ => Err(e),
             },
@@ -132,7 +132,7 @@ impl Client {
     ///
     /// ```no_run
     /// use clap::Parser;
-    /// use roboat::assetdelivery::request_types::AssetBatchPayload;
+    /// use roboat::assetdelivery::AssetBatchPayload;
     ///
     /// #[derive(Parser, Debug)]
     /// struct Args {
@@ -177,7 +177,7 @@ impl Client {
             Err(e) => match e {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
-                    self.post_asset_metadata_batch_internal(asset_batch).await
+                    Self::retry(self.post_asset_metadata_batch_internal(asset_batch)).await
                 }
                 _ => Err(e),
             },
@@ -215,7 +215,7 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-
+    //
     // WARNING: Theres a V2 API https://assetdelivery.roblox.com/v2/assetid/119472671657225 that
     // has location of the file. Migrate to it if they ever ratelimit/remove the v1 API
     /// If this API hangs, use a timeout and retry.
@@ -225,15 +225,15 @@ impl Client {
 
         let xcsrf = self.xcsrf().await;
 
-        let response_result = self
+        let request_builder = self
             .reqwest_client
             .get(&formatted_url)
             .header(header::COOKIE, cookie_string)
-            .header(XCSRF_HEADER, xcsrf)
-            .send()
-            .await;
+            .header(XCSRF_HEADER, xcsrf);
 
-        let response = Self::validate_request_result(response_result).await?;
+        let response = self
+            .send_request(request_builder, "assetdelivery.fetch_asset_data")
+            .await?;
 
         let bytes = response.bytes().await.map_err(RoboatError::ReqwestError)?;
         Ok(bytes)
@@ -257,25 +257,24 @@ mod internal {
             let cookie = self.cookie_string()?;
             let formatted_url = format!("{ASSETDELIVERY_V2_API}/assets/batch");
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
-                .json(&asset_payload)
-                .send()
-                .await;
+                .json(&asset_payload);
 
-            let response = Self::validate_request_result(request_result).await?;
-            let mut meta_data = Self::parse_to_raw::<Vec<AssetBatchResponse>>(response).await?;
+            let response = self
+                .send_request(request_builder, "assetdelivery.post_asset_metadata_batch")
+                .await?;
+            let mut meta_data = self
+                .parse_to_raw::<Vec<AssetBatchResponse>>(response)
+                .await?;
 
             // Scan response for roblox errors, if its 401 just return Invalid Cookie (Can't be
             // CSRF on this API)
             for batch_resp in &mut meta_data {
                 if let Some(id) = batch_resp.asset_type_id {
-                    match catalog_types::AssetType::try_from(id as u64) {
-                        Ok(e) => batch_resp.asset_type = Some(e),
-                        Err(..) => {}
-                    }
+                    batch_resp.asset_type = Some(catalog_types::AssetType::from(id as u64));
                 }
                 if let Some(roblox_error_raw) = &batch_resp.errors {
                     for error in roblox_error_raw {
@@ -297,15 +296,15 @@ mod internal {
             // let xcsrf = self.xcsrf().await;
             let formatted_url = format!("{}/assetid/{}", ASSETDELIVERY_V2_API, asset_id);
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .get(formatted_url)
-                .header(header::COOKIE, cookie)
-                .send()
-                .await;
+                .header(header::COOKIE, cookie);
 
-            let response = Self::validate_request_result(request_result).await?;
-            let meta_data = Self::parse_to_raw::<AssetIdResponse>(response).await?;
+            let response = self
+                .send_request(request_builder, "assetdelivery.fetch_asset_metadata")
+                .await?;
+            let meta_data = self.parse_to_raw::<AssetIdResponse>(response).await?;
 
             // Scan response for roblox errors, if its 401 just return Invalid Cookie (Can't be
             // CSRF on this API)
@@ -0,0 +1,139 @@
+//! An audit journal of the state-changing actions performed by a [`Client`](crate::Client).
+//!
+//! An [`AuditSink`] can be attached using [`ClientBuilder::audit_sink`](crate::ClientBuilder::audit_sink).
+//! The client then writes one [`AuditEntry`] for every call of a mutating method (sending or accepting trades,
+//! purchases, listing changes, role changes, uploads, etc.), including calls that failed, calls that were not sent
+//! because of [`ClientBuilder::dry_run`](crate::ClientBuilder::dry_run), and calls rejected by the client itself
+//! (such as purchases blocked by the [`SpendingPolicy`](crate::spending::SpendingPolicy)).
+//!
+//! [`JsonlAuditSink`] is provided to append the entries to a file, one json object per line.
+
+use crate::{files, RoboatError};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// The result of a mutating call recorded in an [`AuditEntry`].
+#[allow(missing_docs)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
+)]
+pub enum AuditOutcome {
+    #[default]
+    Success,
+    Failure,
+    /// The request was built but not sent, as [`ClientBuilder::dry_run`](crate::ClientBuilder::dry_run) is enabled.
+    DryRun,
+    /// The call was rejected by the client before a request was built, such as a purchase blocked by the
+    /// [`SpendingPolicy`](crate::spending::SpendingPolicy) or an invalid trade offer.
+    Rejected,
+}
+
+/// A single state-changing action performed by a [`Client`](crate::Client).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// The time the response was received (or the request was dropped), in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// The user id of the account. This is fetched (and cached) when the first entry is written if the client
+    /// has not cached it yet, and is `None` if it could not be fetched.
+    pub account_id: Option<u64>,
+    /// The action performed, in the form of `module.method` (e.g. `trades.send_trade`).
+    pub action: String,
+    /// The http method of the request. Empty if the call was [rejected](AuditOutcome::Rejected).
+    pub method: String,
+    /// The url of the request, which contains the parameters of most actions (e.g. the trade id).
+    /// Empty if the call was [rejected](AuditOutcome::Rejected).
+    pub url: String,
+    /// The body of the request, which contains the remaining parameters of the action. If the call was
+    /// [rejected](AuditOutcome::Rejected), this contains the parameters of the call as json instead.
+    pub body: Option<String>,
+    /// Whether the action succeeded.
+    pub outcome: AuditOutcome,
+    /// The status code returned by Roblox, if a response was received.
+    pub status: Option<u16>,
+    /// The error returned by the method, if any.
+    pub error: Option<String>,
+}
+
+/// A receiver for the audit entries written by a [`Client`](crate::Client).
+///
+/// # Notes
+/// * Attempts rejected because of an invalid x-csrf-token are not recorded, as the method repeats
+///   them with a new token. Only the repeated attempt is recorded.
+/// * Cookies and x-csrf-tokens are never part of an entry.
+///
+/// # Example
+/// ```
+/// use roboat::audit::{AuditEntry, AuditSink};
+/// use roboat::ClientBuilder;
+///
+/// #[derive(Debug)]
+/// struct PrintSink;
+///
+/// impl AuditSink for PrintSink {
+///     fn record(&self, entry: &AuditEntry) {
+///         println!("{} {:?} {:?}", entry.action, entry.outcome, entry.error);
+///     }
+/// }
+///
+/// let client = ClientBuilder::new().audit_sink(PrintSink).build();
+/// ```
+pub trait AuditSink: std::fmt::Debug + Send + Sync {
+    /// Records a single entry.
+    fn record(&self, entry: &AuditEntry);
+}
+
+/// An [`AuditSink`] that appends every entry to a file as a line of json.
+///
+/// The file is opened in append mode and is never truncated. Every entry is flushed as it is written.
+///
+/// # Example
+/// ```no_run
+/// use roboat::audit::JsonlAuditSink;
+/// use roboat::ClientBuilder;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let audit_sink = JsonlAuditSink::open("audit.jsonl")?;
+/// let client = ClientBuilder::new().audit_sink(audit_sink).build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JsonlAuditSink {
+    file: Mutex<File>,
+}
+
+impl JsonlAuditSink {
+    /// Opens (or creates) the file at `path` for appending.
+    ///
+    /// # Errors
+    /// * [`RoboatError::IoError`] - If the file cannot be opened.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RoboatError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for JsonlAuditSink {
+    fn record(&self, entry: &AuditEntry) {
+        let write_result = files::json_line(entry).and_then(|line| {
+            let mut file = self.file.lock().unwrap();
+            file.write_all(line.as_bytes())?;
+            file.flush()?;
+            Ok(())
+        });
+
+        #[cfg(feature = "tracing")]
+        if let Err(e) = &write_result {
+            tracing::warn!(error = %e, action = %entry.action, "failed to write audit entry");
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = write_result;
+    }
+}
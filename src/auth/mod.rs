@@ -44,10 +44,8 @@ impl Client {
             Err(_) => builder,
         };
 
-        let request_result = builder.send().await;
-
         // We want to take the xcsrf from here.
-        match Self::validate_request_result(request_result).await {
+        match self.send_request(builder, "auth.force_refresh_xcsrf").await {
             // This just means the xcsrf is valid.
             Ok(_) => Ok(()),
             Err(e) => match e {
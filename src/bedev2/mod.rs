@@ -1,6 +1,8 @@
 use crate::bedev2::request_types::AssetInfo;
 use crate::catalog::CreatorType;
-use crate::{Client, RoboatError};
+use crate::spending::SpendingItemId;
+use crate::{Client, PurchaseReceipt, RoboatError};
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
 mod request_types;
@@ -107,6 +109,23 @@ pub struct NonTradableLimitedDetails {
     pub error_code: Option<u64>,
 }
 
+impl NonTradableLimitedDetails {
+    /// Parses the body of a response from <https://apis.roblox.com/marketplace-items/v1/items/details>,
+    /// usually returned by [`Client::non_tradable_limited_details_bytes`].
+    ///
+    /// The items are returned in the order they appear in the body. Fields that are not part of
+    /// [`NonTradableLimitedDetails`] are skipped without being decoded.
+    ///
+    /// # Errors
+    /// * [`RoboatError::MalformedResponse`] - If the body is not a valid list of item details.
+    pub fn parse_bulk(bytes: &[u8]) -> Result<Vec<Self>, RoboatError> {
+        let raw = serde_json::from_slice::<Vec<request_types::NonTradableLimitedDetailsRaw>>(bytes)
+            .map_err(|_| RoboatError::MalformedResponse)?;
+
+        raw.into_iter().map(Self::try_from).collect()
+    }
+}
+
 impl TryFrom<request_types::NonTradableLimitedDetailsRaw> for NonTradableLimitedDetails {
     type Error = RoboatError;
 
@@ -155,7 +174,7 @@ impl Client {
     /// # Notes
     /// * Requires a valid roblosecurity.
     /// * The amount of items that can be requested at once is unknown as not enough non-tradable limiteds exist, and the
-    /// endpoint doesn't accept duplicates.
+    ///   endpoint doesn't accept duplicates.
     /// * Will repeat once if the x-csrf-token is invalid.
     ///
     /// # Errors
@@ -197,8 +216,63 @@ impl Client {
         &self,
         collectible_item_ids: Vec<String>,
     ) -> Result<Vec<NonTradableLimitedDetails>, RoboatError> {
+        let bytes = self
+            .non_tradable_limited_details_bytes(collectible_item_ids.clone())
+            .await?;
+
+        let mut collectible_item_details = NonTradableLimitedDetails::parse_bulk(&bytes)?;
+        sort_items_by_argument_order(&mut collectible_item_details, &collectible_item_ids);
+
+        Ok(collectible_item_details)
+    }
+
+    /// Grabs details of one or more non-tradable limiteds from <https://apis.roblox.com/marketplace-items/v1/items/details>,
+    /// returning the raw body of the response.
+    ///
+    /// This is meant for high-frequency polling, where the body is kept (or only parsed when it changes)
+    /// and parsed using [`NonTradableLimitedDetails::parse_bulk`].
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * Will repeat once if the x-csrf-token is invalid.
+    /// * Unlike [`Client::non_tradable_limited_details`], the items in the body are not sorted in the order of the arguments.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use roboat::bedev2::NonTradableLimitedDetails;
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new()
+    ///     .roblosecurity(ROBLOSECURITY.to_string())
+    ///     .build();
+    ///
+    /// let collectible_item_id = "a4b5cb79-5218-4ca1-93fa-1e3436f595ef".to_owned();
+    ///
+    /// let bytes = client
+    ///     .non_tradable_limited_details_bytes(vec![collectible_item_id])
+    ///     .await?;
+    /// let details = NonTradableLimitedDetails::parse_bulk(&bytes)?;
+    ///
+    /// println!("Remaining Stock: {}", details[0].remaining_stock);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn non_tradable_limited_details_bytes(
+        &self,
+        collectible_item_ids: Vec<String>,
+    ) -> Result<Bytes, RoboatError> {
         match self
-            .non_tradable_limited_details_internal(collectible_item_ids.clone())
+            .non_tradable_limited_details_bytes_internal(collectible_item_ids.clone())
             .await
         {
             Ok(x) => Ok(x),
@@ -206,8 +280,10 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.non_tradable_limited_details_internal(collectible_item_ids)
-                        .await
+                    Self::retry(
+                        self.non_tradable_limited_details_bytes_internal(collectible_item_ids),
+                    )
+                    .await
                 }
                 _ => Err(e),
             },
@@ -269,7 +345,7 @@ impl Client {
     /// # Notes
     /// * Requires a valid roblosecurity.
     /// * The amount of items that can be requested at once is unknown as not enough non-tradable limiteds exist, and the
-    /// endpoint doesn't accept duplicates.
+    ///   endpoint doesn't accept duplicates.
     /// * Will repeat once if the x-csrf-token is invalid.
     ///
     /// # Errors
@@ -383,28 +459,33 @@ impl Client {
     ///
     /// # Notes
     /// * Requires a valid roblosecurity.
-    /// * Will repeat once if the x-csrf-token is invalid.
+    /// * Will repeat once if the x-csrf-token is invalid. The repeat uses the same idempotency key,
+    ///   so the item cannot be bought twice.
+    /// * A new idempotency key is generated for every call. Use [`Client::purchase_non_tradable_limited_with_idempotency_key`]
+    ///   to supply your own key, which makes it safe to retry the whole call yourself.
     /// * Currently only tested to work when buying from users (as opposed to groups), and only tested
-    /// when buying the items from the original seller (with original stock). This is because
-    /// these are the only conditions that currently exist as of 4/14/2023.
+    ///   when buying the items from the original seller (with original stock). This is because
+    ///   these are the only conditions that currently exist as of 4/14/2023.
     ///
     /// # Return Value Notes
-    /// * Will return `Ok(())` if the limited was successfully purchased.
+    /// * Will return `Ok(PurchaseReceipt)` if the limited was successfully purchased.
     ///
     /// # Argument Notes
     /// * `collectible_item_id` is the string id of a non-tradable limited. It can be
-    /// fetched using [`Client::collectible_item_id`].
+    ///   fetched using [`Client::collectible_item_id`].
     /// * `collectible_product_id` is the string product id of a non-tradable limited. It can be
-    /// fetched using [`Client::collectible_product_id`].
+    ///   fetched using [`Client::collectible_product_id`].
     /// * `collectible_seller_id` is the user id of the seller of a non-tradable limited. It can be
-    /// fetched using [`Client::collectible_creator_id`] (currently it is unknown how to buy from a reseller
-    /// instead of the original creator as they do not exist yet).
+    ///   fetched using [`Client::collectible_creator_id`] (currently it is unknown how to buy from a reseller
+    ///   instead of the original creator as they do not exist yet).
     ///
     /// # Errors
     /// * All errors under [Standard Errors](#standard-errors).
     /// * All errors under [Auth Required Errors](#auth-required-errors).
     /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
     /// * [`RoboatError::PurchaseNonTradableLimitedError`] - Nested inside this error, all variants of [`PurchaseNonTradableLimitedError`] may be thrown.
+    /// * [`RoboatError::SpendingPolicyViolation`] - If the purchase is blocked by the [`SpendingPolicy`](crate::spending::SpendingPolicy)
+    ///   of the client, checked using [`SpendingItemId::CollectibleItemId`].
     ///
     /// # Examples
     /// ```no_run
@@ -421,8 +502,8 @@ impl Client {
     /// let collectible_seller_id = 123456789;
     /// let price = 0;
     ///
-    /// let _ = client.purchase_non_tradable_limited(collectible_item_id, collectible_product_id, collectible_seller_id, price).await?;
-    /// println!("Successfully Purchased!");
+    /// let receipt = client.purchase_non_tradable_limited(collectible_item_id, collectible_product_id, collectible_seller_id, price).await?;
+    /// println!("Successfully Purchased! Result: {}", receipt.purchase_result);
     /// # Ok(())
     /// # }
     /// ```
@@ -432,13 +513,110 @@ impl Client {
         collectible_product_id: String,
         collectible_seller_id: u64,
         price: u64,
-    ) -> Result<(), RoboatError> {
-        match self
+    ) -> Result<PurchaseReceipt, RoboatError> {
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+
+        self.purchase_non_tradable_limited_with_idempotency_key(
+            collectible_item_id,
+            collectible_product_id,
+            collectible_seller_id,
+            price,
+            idempotency_key,
+        )
+        .await
+    }
+
+    /// Purchases a non-tradable limited (includes ugc limiteds) with a caller-supplied idempotency key using endpoint
+    /// <https://apis.roblox.com/marketplace-sales/v1/item/{collectible_item_id}/purchase-item>.
+    ///
+    /// This is the same as [`Client::purchase_non_tradable_limited`], except that Roblox will not
+    /// complete more than one purchase with the same idempotency key. Reusing the key when retrying a call
+    /// that failed (for example, with a network error) makes sure the item is only bought once.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * Will repeat once if the x-csrf-token is invalid, using the same idempotency key.
+    /// * Calls with the same idempotency key share a single reservation in the
+    ///   [`SpendingPolicy`](crate::spending::SpendingPolicy) of the client, so retrying does not count the price twice.
+    ///
+    /// # Return Value Notes
+    /// * Will return `Ok(PurchaseReceipt)` if the limited was successfully purchased.
+    ///
+    /// # Argument Notes
+    /// * `idempotency_key` should be unique per intended purchase. Roblox uses uuids (v4) for this.
+    /// * See [`Client::purchase_non_tradable_limited`] for the other arguments.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    /// * [`RoboatError::PurchaseNonTradableLimitedError`] - Nested inside this error, all variants of [`PurchaseNonTradableLimitedError`] may be thrown.
+    /// * [`RoboatError::SpendingPolicyViolation`] - If the purchase is blocked by the [`SpendingPolicy`](crate::spending::SpendingPolicy)
+    ///   of the client, checked using [`SpendingItemId::CollectibleItemId`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use roboat::{ClientBuilder, RoboatError};
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let collectible_item_id = "abc".to_string();
+    /// let collectible_product_id = "xyz".to_string();
+    /// let collectible_seller_id = 123456789;
+    /// let price = 0;
+    /// let idempotency_key = "3f2b7a52-5a4e-4d1b-9a39-1c4f0c7e9d10".to_string();
+    ///
+    /// // Retrying with the same key cannot buy the item twice.
+    /// for _ in 0..3 {
+    ///     match client
+    ///         .purchase_non_tradable_limited_with_idempotency_key(
+    ///             collectible_item_id.clone(),
+    ///             collectible_product_id.clone(),
+    ///             collectible_seller_id,
+    ///             price,
+    ///             idempotency_key.clone(),
+    ///         )
+    ///         .await
+    ///     {
+    ///         Ok(receipt) => {
+    ///             println!("Successfully Purchased! Result: {}", receipt.purchase_result);
+    ///             break;
+    ///         }
+    ///         Err(RoboatError::ReqwestError(_)) => continue,
+    ///         Err(e) => return Err(e.into()),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn purchase_non_tradable_limited_with_idempotency_key(
+        &self,
+        collectible_item_id: String,
+        collectible_product_id: String,
+        collectible_seller_id: u64,
+        price: u64,
+        idempotency_key: String,
+    ) -> Result<PurchaseReceipt, RoboatError> {
+        let reservation = self
+            .reserve_spending(
+                "bedev2.purchase_non_tradable_limited",
+                SpendingItemId::CollectibleItemId(collectible_item_id.clone()),
+                price,
+                Some(&idempotency_key),
+            )
+            .await?;
+
+        let result = match self
             .purchase_non_tradable_limited_internal(
                 collectible_item_id.clone(),
                 collectible_product_id.clone(),
                 collectible_seller_id,
                 price,
+                idempotency_key.clone(),
             )
             .await
         {
@@ -447,17 +625,22 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.purchase_non_tradable_limited_internal(
+                    Self::retry(self.purchase_non_tradable_limited_internal(
                         collectible_item_id,
                         collectible_product_id,
                         collectible_seller_id,
                         price,
-                    )
+                        idempotency_key,
+                    ))
                     .await
                 }
                 _ => Err(e),
             },
-        }
+        };
+
+        self.settle_spending(reservation, &result);
+
+        result
     }
 
     /// Fetches detailed information about a specific asset using its asset ID.
@@ -474,7 +657,7 @@ impl Client {
     /// # Notes
     /// * requires .ROBLOSECURITY cookie
     /// * Will repeat once if the x-csrf-token is invalid.
-
+    ///
     /// # Errors
     ///
     /// * All errors under [Standard Errors](#standard-errors).
@@ -502,12 +685,12 @@ impl Client {
     /// # }
     /// ```
     pub async fn get_asset_info(&self, asset_id: u64) -> Result<AssetInfo, RoboatError> {
-        match self.get_asset_info_internal(asset_id.clone()).await {
+        match self.get_asset_info_internal(asset_id).await {
             Ok(x) => Ok(x),
             Err(e) => match e {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
-                    self.get_asset_info_internal(asset_id).await
+                    Self::retry(self.get_asset_info_internal(asset_id)).await
                 }
                 _ => Err(e),
             },
@@ -549,13 +732,13 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.upload_classic_clothing_to_group_internal(
+                    Self::retry(self.upload_classic_clothing_to_group_internal(
                         group_id,
                         name,
                         description,
                         image_path,
                         classic_clothing_type,
-                    )
+                    ))
                     .await
                 }
                 _ => Err(e),
@@ -567,51 +750,52 @@ impl Client {
 mod internal {
     use std::path::Path;
 
+    use bytes::Bytes;
     use reqwest::header;
 
     use super::{
-        request_types, sort_items_by_argument_order, ClassicClothingType,
-        NonTradableLimitedDetails, PurchaseNonTradableLimitedError, COLLECTIBLE_ITEM_DETAILS_API,
-        PURCHASE_NON_TRADEABLE_LIMITED_API_PART_1, PURCHASE_NON_TRADEABLE_LIMITED_API_PART_2,
+        request_types, ClassicClothingType, PurchaseNonTradableLimitedError,
+        COLLECTIBLE_ITEM_DETAILS_API, PURCHASE_NON_TRADEABLE_LIMITED_API_PART_1,
+        PURCHASE_NON_TRADEABLE_LIMITED_API_PART_2,
     };
+    use crate::transport::DescribedForm;
     use crate::{
         bedev2::{request_types::AssetInfo, ASSET_API},
-        Client, RoboatError, XCSRF_HEADER,
+        Client, PurchaseReceipt, PurchasedItem, RoboatError, XCSRF_HEADER,
     };
 
     impl Client {
-        pub(super) async fn non_tradable_limited_details_internal(
+        pub(super) async fn non_tradable_limited_details_bytes_internal(
             &self,
             collectible_item_ids: Vec<String>,
-        ) -> Result<Vec<NonTradableLimitedDetails>, RoboatError> {
+        ) -> Result<Bytes, RoboatError> {
             let request_body = serde_json::json!({
                 "itemIds": collectible_item_ids,
             });
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(COLLECTIBLE_ITEM_DETAILS_API)
                 .header(XCSRF_HEADER, self.xcsrf().await)
                 .header(header::COOKIE, self.cookie_string()?)
-                .json(&request_body)
-                .send()
-                .await;
-
-            let response = Self::validate_request_result(request_result).await?;
-            let raw =
-                Self::parse_to_raw::<Vec<request_types::NonTradableLimitedDetailsRaw>>(response)
-                    .await?;
+                .json(&request_body);
 
-            let mut collectible_item_details = Vec::new();
+            let response = self
+                .send_request(request_builder, "bedev2.non_tradable_limited_details")
+                .await?;
 
-            for raw_details in raw {
-                let details = NonTradableLimitedDetails::try_from(raw_details)?;
-                collectible_item_details.push(details);
-            }
+            let url = self
+                .detects_schema_drift()
+                .then(|| response.url().to_string());
+            let bytes = response.bytes().await.map_err(RoboatError::ReqwestError)?;
 
-            sort_items_by_argument_order(&mut collectible_item_details, &collectible_item_ids);
+            self.check_schema_drift::<Vec<request_types::NonTradableLimitedDetailsRaw>>(
+                &bytes,
+                url,
+                |_, report| report.ignore_unused_fields(&["[].offSaleDeadline"]),
+            )?;
 
-            Ok(collectible_item_details)
+            Ok(bytes)
         }
 
         pub(super) async fn purchase_non_tradable_limited_internal(
@@ -620,8 +804,8 @@ mod internal {
             collectible_product_id: String,
             seller_id: u64,
             price: u64,
-        ) -> Result<(), RoboatError> {
-            let idempotency_key = uuid::Uuid::new_v4().to_string();
+            idempotency_key: String,
+        ) -> Result<PurchaseReceipt, RoboatError> {
             let client_user_id = self.user_id().await?;
 
             let request_body = serde_json::json!({
@@ -643,21 +827,33 @@ mod internal {
                 PURCHASE_NON_TRADEABLE_LIMITED_API_PART_2
             );
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(XCSRF_HEADER, self.xcsrf().await)
                 .header(header::COOKIE, self.cookie_string()?)
-                .json(&request_body)
-                .send()
-                .await;
+                .json(&request_body);
 
-            let response = Self::validate_request_result(request_result).await?;
-            let raw = Self::parse_to_raw::<request_types::PurchaseNonTradeableLimitedRaw>(response)
+            let response = self
+                .send_mutating_request(request_builder, "bedev2.purchase_non_tradable_limited")
+                .await?;
+            let raw = self
+                .parse_to_raw::<request_types::PurchaseNonTradeableLimitedRaw>(response)
                 .await?;
 
             if raw.purchased {
-                return Ok(());
+                return Ok(PurchaseReceipt {
+                    item: PurchasedItem::NonTradableLimited {
+                        collectible_item_id,
+                        collectible_product_id,
+                    },
+                    price,
+                    seller_id,
+                    uaid: None,
+                    collectible_item_instance_id: raw.collectible_item_instance_id,
+                    idempotency_key: Some(idempotency_key),
+                    purchase_result: raw.purchase_result,
+                });
             }
 
             let err_msg = raw.error_message.ok_or(RoboatError::MalformedResponse)?;
@@ -683,17 +879,19 @@ mod internal {
             let xcsrf = self.xcsrf().await;
             let formatted_url = format!("{}/{}", ASSET_API, asset_id);
 
-            let response_result = self
+            let request_builder = self
                 .reqwest_client
                 .get(formatted_url)
                 .header(header::COOKIE, cookie_string)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            let response = Self::validate_request_result(response_result).await?;
+            let response = self
+                .send_request(request_builder, "bedev2.get_asset_info")
+                .await?;
 
-            let asset_info = Self::parse_to_raw::<request_types::AssetInfo>(response).await?;
+            let asset_info = self
+                .parse_to_raw::<request_types::AssetInfo>(response)
+                .await?;
 
             Ok(asset_info)
         }
@@ -722,24 +920,31 @@ mod internal {
                 ClassicClothingType::TShirt => 0,
             };
 
-            let form = reqwest::multipart::Form::new()
-                .part("fileContent", reqwest::multipart::Part::bytes(tokio::fs::read(image_path).await?).file_name(filename))
-                .text("request", format!("{{\"displayName\":\"{name}\",\"description\":\"{description}\",\"assetType\":\"{asset_name_patch}\",\"creationContext\":{{\"creator\":{{\"groupId\":{group_id}}},\"expectedPrice\":{expected_price}}}}}" ));
+            let (form, parts) = DescribedForm::new()
+                .file("fileContent", tokio::fs::read(image_path).await?, filename)
+                .text("request", format!("{{\"displayName\":\"{name}\",\"description\":\"{description}\",\"assetType\":\"{asset_name_patch}\",\"creationContext\":{{\"creator\":{{\"groupId\":{group_id}}},\"expectedPrice\":{expected_price}}}}}" ))
+                .into_parts();
 
             let cookie_string = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
-            let response_result = self
+            let request_builder = self
                 .reqwest_client
                 .request(reqwest::Method::POST, ASSET_API)
                 .header(header::COOKIE, cookie_string)
                 .header(XCSRF_HEADER, xcsrf)
-                .multipart(form)
-                .send()
-                .await;
-
-            let response = Self::validate_request_result(response_result).await?;
-            let _ = Self::parse_to_raw::<request_types::UploadClassicClothingRaw>(response).await?;
+                .multipart(form);
+
+            let response = self
+                .send_multipart_request(
+                    request_builder,
+                    "bedev2.upload_classic_clothing_to_group",
+                    parts,
+                )
+                .await?;
+            let _ = self
+                .parse_to_raw::<request_types::UploadClassicClothingRaw>(response)
+                .await?;
 
             Ok(())
         }
@@ -1,6 +1,8 @@
 use crate::catalog::CreatorType;
 use serde::{Deserialize, Serialize};
 
+/// Fields that are not used by [`NonTradableLimitedDetails`](super::NonTradableLimitedDetails)
+/// (such as `offSaleDeadline`) are left out so that they are skipped without being decoded.
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct NonTradableLimitedDetailsRaw {
@@ -18,7 +20,6 @@ pub(super) struct NonTradableLimitedDetailsRaw {
     /// It's unclear which one of these to use
     pub lowest_price: u64,
     pub units_available_for_consumption: u64,
-    pub off_sale_deadline: String,
     pub asset_stock: u64,
     pub error_code: Option<u64>,
 }
@@ -30,6 +31,8 @@ pub(super) struct PurchaseNonTradeableLimitedRaw {
     pub purchased: bool,
     /// Error variants: null, "PriceMismatch"
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub collectible_item_instance_id: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
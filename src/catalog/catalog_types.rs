@@ -1,4 +1,5 @@
 use super::request_types;
+use crate::enums::{id_enum, string_enum};
 
 // Allow unused imports so they can be linked to in the docs.
 #[allow(unused_imports)]
@@ -8,23 +9,27 @@ use serde::{Deserialize, Serialize};
 const AVATAR_CATALOG_SEARCH_BASE_URL: &str = "https://catalog.roblox.com/v1/search/items?";
 
 /// An enum representing the overall high level type of the item (Asset or Bundle)
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum ItemType {
     /// An individual asset.
     #[default]
     Asset,
     /// A bundle (such as an animation package).
     Bundle,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(ItemType {
+    Asset => "Asset",
+    Bundle => "Bundle",
+});
+
 /// An enum representing the type of the asset (hat, shirt, gear).
+///
+/// Deserialized from either the asset type id Roblox uses or the name of the variant.
 #[allow(missing_docs)]
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
-
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Copy)]
 pub enum AssetType {
     #[default]
     Image,
@@ -88,24 +93,26 @@ pub enum AssetType {
     EyelashAccessory,
     MoodAnimation,
     DynamicHead,
+    /// An asset type id not known by this crate.
+    Unknown(u64),
 }
 
 /// An enum representing the type of bundle (BodyParts or AvatarAnimations).
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+///
+/// Deserialized from either the bundle type id Roblox uses or the name of the variant.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Copy)]
 #[allow(missing_docs)]
 pub enum BundleType {
     #[default]
     BodyParts,
     AvatarAnimations,
+    /// A bundle type id not known by this crate.
+    Unknown(u64),
 }
 
 /// An enum representing the genre of an item (war, funny). Only used when returning
 /// info from item_details.
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[allow(missing_docs)]
 pub enum Genre {
     #[default]
@@ -124,11 +131,31 @@ pub enum Genre {
     Sports,
     Ninja,
     WildWest,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(Genre {
+    All => "All",
+    Tutorial => "Tutorial",
+    Scary => "Scary",
+    TownAndCity => "TownAndCity",
+    War => "War",
+    Funny => "Funny",
+    Fantasy => "Fantasy",
+    Adventure => "Adventure",
+    SciFi => "SciFi",
+    Pirate => "Pirate",
+    FPS => "FPS",
+    RPG => "RPG",
+    Sports => "Sports",
+    Ninja => "Ninja",
+    WildWest => "WildWest",
+});
+
 /// An enum representing the sale location type of an asset. Only used when returning
 /// info from item_details.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Copy)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(missing_docs)]
 #[non_exhaustive]
 pub enum SaleLocationType {
@@ -136,36 +163,20 @@ pub enum SaleLocationType {
     Game,
     ExperiencesDevApiOnly,
     ShopAndAllExperiences,
-    // there might be more...
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
-impl SaleLocationType {
-    pub(crate) fn from_str(data: &str) -> Option<Self> {
-        match data {
-            "NotApplicable" => Some(SaleLocationType::NotApplicable),
-            "Game" => Some(SaleLocationType::Game),
-            "ExperiencesDevApiOnly" => Some(SaleLocationType::ExperiencesDevApiOnly),
-            "ShopAndAllExperiences" => Some(SaleLocationType::ShopAndAllExperiences),
-            _ => None,
-        }
-    }
-}
-impl std::fmt::Display for SaleLocationType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::NotApplicable => write!(f, "NotApplicable"),
-            Self::Game => write!(f, "Game"),
-            Self::ExperiencesDevApiOnly => write!(f, "ExperiencesDevApiOnly"),
-            Self::ShopAndAllExperiences => write!(f, "ShopAndAllExperiences"),
-        }
-    }
-}
+string_enum!(SaleLocationType {
+    NotApplicable => "NotApplicable",
+    Game => "Game",
+    ExperiencesDevApiOnly => "ExperiencesDevApiOnly",
+    ShopAndAllExperiences => "ShopAndAllExperiences",
+});
 
 /// The status of an item (Sale, Exclusive).
 #[allow(missing_docs)]
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum ItemStatus {
     #[default]
     New,
@@ -175,14 +186,24 @@ pub enum ItemStatus {
     GooglePlayExclusive,
     IosExclusive,
     SaleTimer,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(ItemStatus {
+    New => "New",
+    Sale => "Sale",
+    XboxExclusive => "XboxExclusive",
+    AmazonExclusive => "AmazonExclusive",
+    GooglePlayExclusive => "GooglePlayExclusive",
+    IosExclusive => "IosExclusive",
+    SaleTimer => "SaleTimer",
+});
+
 /// Restriction on an item (ThirteenPlus, Limited). Not sure why limited
 /// is here but I guess that's how they do it.
 #[allow(missing_docs)]
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum ItemRestriction {
     #[default]
     ThirteenPlus,
@@ -191,22 +212,36 @@ pub enum ItemRestriction {
     Rthro,
     /// Appears to be used only for "new" limiteds (including ugc limiteds).
     Collectible,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(ItemRestriction {
+    ThirteenPlus => "ThirteenPlus",
+    LimitedUnique => "LimitedUnique",
+    Limited => "Limited",
+    Rthro => "Rthro",
+    Collectible => "Collectible",
+});
+
 /// The price status of an item. Only applies to items not on sale (Free, Offsale).
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[allow(missing_docs)]
 pub enum PriceStatus {
     #[default]
     Free,
-    #[serde(alias = "Off Sale")]
     Offsale,
-    #[serde(alias = "No Resellers")]
     NoResellers,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(PriceStatus {
+    Free => "Free",
+    Offsale => "Offsale" | "Off Sale",
+    NoResellers => "NoResellers" | "No Resellers",
+});
+
 /// The broad category of an item for use in search (Clothing, Collectables).
 #[derive(
     Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
@@ -543,9 +578,7 @@ pub struct ItemDetails {
 
 /// Contains an item id and its type. Used as part of a parameter in [`Client::item_details`], and used as
 /// part of a response in [`Client::avatar_catalog_search`].
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 pub struct Item {
     /// The type of the item (Asset or Bundle).
     #[serde(alias = "itemType")]
@@ -555,109 +588,99 @@ pub struct Item {
     pub id: u64,
 }
 
-impl TryFrom<u64> for AssetType {
-    type Error = RoboatError;
+id_enum!(AssetType {
+    Image => 1,
+    TShirt => 2,
+    Audio => 3,
+    Mesh => 4,
+    Lua => 5,
+    Hat => 8,
+    Place => 9,
+    Model => 10,
+    Shirt => 11,
+    Pants => 12,
+    Decal => 13,
+    Head => 17,
+    Face => 18,
+    Gear => 19,
+    Badge => 21,
+    Animation => 24,
+    Arms => 25,
+    Legs => 26,
+    Torso => 27,
+    RightArm => 28,
+    LeftArm => 29,
+    LeftLeg => 30,
+    RightLeg => 31,
+    Package => 32,
+    GamePass => 34,
+    Plugin => 38,
+    MeshPart => 40,
+    HairAccessory => 41,
+    FaceAccessory => 42,
+    NeckAccessory => 43,
+    ShoulderAccessory => 44,
+    FrontAccessory => 45,
+    BackAccessory => 46,
+    WaistAccessory => 47,
+    ClimbAnimation => 48,
+    DeathAnimation => 49,
+    FallAnimation => 50,
+    IdleAnimation => 51,
+    JumpAnimation => 52,
+    RunAnimation => 53,
+    SwimAnimation => 54,
+    WalkAnimation => 55,
+    PoseAnimation => 56,
+    EarAccessory => 57,
+    EyeAccessory => 58,
+    EmoteAnimation => 61,
+    Video => 62,
+    TShirtAccessory => 64,
+    ShirtAccessory => 65,
+    PantsAccessory => 66,
+    JacketAccessory => 67,
+    SweaterAccessory => 68,
+    ShortsAccessory => 69,
+    LeftShoeAccessory => 70,
+    RightShoeAccessory => 71,
+    DressSkirtAccessory => 72,
+    FontFamily => 73,
+    EyebrowAccessory => 76,
+    EyelashAccessory => 77,
+    MoodAnimation => 78,
+    DynamicHead => 79,
+});
+
+id_enum!(BundleType {
+    BodyParts => 1,
+    AvatarAnimations => 2,
+});
+
+impl ItemDetails {
+    /// Parses the body of a response from <https://catalog.roblox.com/v1/catalog/items/details>,
+    /// usually returned by [`Client::item_details_bytes`].
+    ///
+    /// The items are returned in the order they appear in the body. Fields that are not part of
+    /// [`ItemDetails`] are skipped without being decoded.
+    ///
+    /// # Errors
+    /// * [`RoboatError::MalformedResponse`] - If the body is not a valid list of item details.
+    pub fn parse_bulk(bytes: &[u8]) -> Result<Vec<Self>, RoboatError> {
+        let raw = serde_json::from_slice::<request_types::ItemDetailsResponse>(bytes)
+            .map_err(|_| RoboatError::MalformedResponse)?;
 
-    fn try_from(value: u64) -> Result<Self, Self::Error> {
-        match value {
-            1 => Ok(AssetType::Image),
-            2 => Ok(AssetType::TShirt),
-            3 => Ok(AssetType::Audio),
-            4 => Ok(AssetType::Mesh),
-            5 => Ok(AssetType::Lua),
-            8 => Ok(AssetType::Hat),
-            9 => Ok(AssetType::Place),
-            10 => Ok(AssetType::Model),
-            11 => Ok(AssetType::Shirt),
-            12 => Ok(AssetType::Pants),
-            13 => Ok(AssetType::Decal),
-            17 => Ok(AssetType::Head),
-            18 => Ok(AssetType::Face),
-            19 => Ok(AssetType::Gear),
-            21 => Ok(AssetType::Badge),
-            24 => Ok(AssetType::Animation),
-            25 => Ok(AssetType::Arms),
-            26 => Ok(AssetType::Legs),
-            27 => Ok(AssetType::Torso),
-            28 => Ok(AssetType::RightArm),
-            29 => Ok(AssetType::LeftArm),
-            30 => Ok(AssetType::LeftLeg),
-            31 => Ok(AssetType::RightLeg),
-            32 => Ok(AssetType::Package),
-            34 => Ok(AssetType::GamePass),
-            38 => Ok(AssetType::Plugin),
-            40 => Ok(AssetType::MeshPart),
-            41 => Ok(AssetType::HairAccessory),
-            42 => Ok(AssetType::FaceAccessory),
-            43 => Ok(AssetType::NeckAccessory),
-            44 => Ok(AssetType::ShoulderAccessory),
-            45 => Ok(AssetType::FrontAccessory),
-            46 => Ok(AssetType::BackAccessory),
-            47 => Ok(AssetType::WaistAccessory),
-            48 => Ok(AssetType::ClimbAnimation),
-            49 => Ok(AssetType::DeathAnimation),
-            50 => Ok(AssetType::FallAnimation),
-            51 => Ok(AssetType::IdleAnimation),
-            52 => Ok(AssetType::JumpAnimation),
-            53 => Ok(AssetType::RunAnimation),
-            54 => Ok(AssetType::SwimAnimation),
-            55 => Ok(AssetType::WalkAnimation),
-            56 => Ok(AssetType::PoseAnimation),
-            57 => Ok(AssetType::EarAccessory),
-            58 => Ok(AssetType::EyeAccessory),
-            61 => Ok(AssetType::EmoteAnimation),
-            62 => Ok(AssetType::Video),
-            64 => Ok(AssetType::TShirtAccessory),
-            65 => Ok(AssetType::ShirtAccessory),
-            66 => Ok(AssetType::PantsAccessory),
-            67 => Ok(AssetType::JacketAccessory),
-            68 => Ok(AssetType::SweaterAccessory),
-            69 => Ok(AssetType::ShortsAccessory),
-            70 => Ok(AssetType::LeftShoeAccessory),
-            71 => Ok(AssetType::RightShoeAccessory),
-            72 => Ok(AssetType::DressSkirtAccessory),
-            73 => Ok(AssetType::FontFamily),
-            76 => Ok(AssetType::EyebrowAccessory),
-            77 => Ok(AssetType::EyelashAccessory),
-            78 => Ok(AssetType::MoodAnimation),
-            79 => Ok(AssetType::DynamicHead),
-            _ => Err(RoboatError::MalformedResponse),
-        }
+        raw.data.into_iter().map(Self::try_from).collect()
     }
 }
 
-impl TryFrom<u64> for BundleType {
+impl TryFrom<request_types::ItemDetailsRaw<'_>> for ItemDetails {
     type Error = RoboatError;
 
-    fn try_from(value: u64) -> Result<Self, Self::Error> {
-        match value {
-            1 => Ok(BundleType::BodyParts),
-            2 => Ok(BundleType::AvatarAnimations),
-            _ => Err(RoboatError::MalformedResponse),
-        }
-    }
-}
+    fn try_from(value: request_types::ItemDetailsRaw<'_>) -> Result<Self, Self::Error> {
+        let asset_type = value.asset_type.map(AssetType::from);
 
-impl TryFrom<request_types::ItemDetailsRaw> for ItemDetails {
-    type Error = RoboatError;
-
-    fn try_from(value: request_types::ItemDetailsRaw) -> Result<Self, Self::Error> {
-        let asset_type = match value.asset_type {
-            Some(asset_type_id) => {
-                let asset_type = AssetType::try_from(asset_type_id)?;
-                Some(asset_type)
-            }
-            None => None,
-        };
-
-        let _bundled_items = value.bundled_items;
-        let bundle_type = match value.bundle_type {
-            Some(bundle_type_id) => {
-                let bundle_type = BundleType::try_from(bundle_type_id)?;
-                Some(bundle_type)
-            }
-            None => None,
-        };
+        let bundle_type = value.bundle_type.map(BundleType::from);
 
         let id = value.id.ok_or(RoboatError::MalformedResponse)?;
         let item_type = value.item_type.ok_or(RoboatError::MalformedResponse)?;
@@ -670,8 +693,7 @@ impl TryFrom<request_types::ItemDetailsRaw> for ItemDetails {
         let is_off_sale = value.is_off_sale;
         let sale_location_type = value
             .sale_location_type
-            .map(|x| SaleLocationType::from_str(&x))
-            .unwrap();
+            .map(|x| SaleLocationType::from(x.as_ref()));
 
         let item_restrictions = value.item_restrictions;
 
@@ -683,10 +705,7 @@ impl TryFrom<request_types::ItemDetailsRaw> for ItemDetails {
             .creator_target_id
             .ok_or(RoboatError::MalformedResponse)?;
 
-        let creator_name = value
-            .creator_name
-            .clone()
-            .ok_or(RoboatError::MalformedResponse)?;
+        let creator_name = value.creator_name.ok_or(RoboatError::MalformedResponse)?;
 
         let genres = value.genres;
         let favorite_count = value.favorite_count;
@@ -739,21 +758,28 @@ impl TryFrom<request_types::ItemDetailsRaw> for ItemDetails {
 }
 
 /// The type of a creator (User, Group).
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[allow(missing_docs)]
 pub enum CreatorType {
     #[default]
     User,
     Group,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(CreatorType {
+    User => "User",
+    Group => "Group",
+});
+
 impl CreatorType {
-    pub(crate) fn as_u8(&self) -> u8 {
+    /// Returns the id of the creator type, or `None` if it is not known by this crate.
+    pub(crate) fn as_u8(&self) -> Option<u8> {
         match self {
-            Self::User => 1,
-            Self::Group => 2,
+            Self::User => Some(1),
+            Self::Group => Some(2),
+            Self::Unknown(_) => None,
         }
     }
 }
@@ -883,8 +909,8 @@ impl AvatarSearchQuery {
             url.push_str(&format!("creatorTargetId={}&", creator_id));
         }
 
-        if let Some(creator_type) = self.creator_type {
-            url.push_str(&format!("creatorType={}&", creator_type.as_u8()));
+        if let Some(creator_type) = self.creator_type.as_ref().and_then(|x| x.as_u8()) {
+            url.push_str(&format!("creatorType={}&", creator_type));
         }
 
         if !self.query_genres.is_empty() {
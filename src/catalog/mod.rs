@@ -1,4 +1,5 @@
 use crate::{Client, RoboatError};
+use bytes::Bytes;
 use request_types::AvatarSearchQueryResponse;
 
 use catalog_types::QueryLimit;
@@ -37,8 +38,8 @@ impl Client {
     ///
     /// # Argument Notes
     /// * The `id` parameter is that acts differently for this endpoint than others.
-    /// If the `item_type` is [`ItemType::Asset`], then `id` is the item ID.
-    /// Otherwise, if the `item_type` is [`ItemType::Bundle`], then `id` is the bundle ID.
+    ///   If the `item_type` is [`ItemType::Asset`], then `id` is the item ID.
+    ///   Otherwise, if the `item_type` is [`ItemType::Bundle`], then `id` is the bundle ID.
     ///
     /// # Errors
     /// * All errors under [Standard Errors](#standard-errors).
@@ -81,13 +82,60 @@ impl Client {
     /// # }
     /// ```
     pub async fn item_details(&self, items: Vec<Item>) -> Result<Vec<ItemDetails>, RoboatError> {
-        match self.item_details_internal(items.clone()).await {
+        let bytes = self.item_details_bytes(items.clone()).await?;
+
+        let mut item_details = ItemDetails::parse_bulk(&bytes)?;
+        sort_items_by_argument_order(&mut item_details, &items);
+
+        Ok(item_details)
+    }
+
+    /// Grabs details of one or more items from <https://catalog.roblox.com/v1/catalog/items/details>,
+    /// returning the raw body of the response.
+    ///
+    /// This is meant for high-frequency polling, where the body is kept (or only parsed when it changes)
+    /// and parsed using [`ItemDetails::parse_bulk`].
+    ///
+    /// # Notes
+    /// * Does not require a valid roblosecurity.
+    /// * This endpoint will accept up to 120 items at a time.
+    /// * Will repeat once if the x-csrf-token is invalid.
+    /// * Unlike [`Client::item_details`], the items in the body are not sorted in the order of the arguments.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use roboat::catalog::{ItemDetails, ItemType, Item};
+    /// use roboat::ClientBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// let asset = Item {
+    ///     item_type: ItemType::Asset,
+    ///     id: 1365767,
+    /// };
+    ///
+    /// let bytes = client.item_details_bytes(vec![asset]).await?;
+    /// let details = ItemDetails::parse_bulk(&bytes)?;
+    ///
+    /// println!("Item Name: {}", details[0].name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn item_details_bytes(&self, items: Vec<Item>) -> Result<Bytes, RoboatError> {
+        match self.item_details_bytes_internal(items.clone()).await {
             Ok(x) => Ok(x),
             Err(e) => match e {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.item_details_internal(items).await
+                    Self::retry(self.item_details_bytes_internal(items)).await
                 }
                 _ => Err(e),
             },
@@ -364,10 +412,14 @@ impl Client {
             cursor.unwrap_or_default()
         );
 
-        let request_result = self.reqwest_client.get(formatted_url).send().await;
+        let request_builder = self.reqwest_client.get(formatted_url);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<AvatarSearchQueryResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "catalog.avatar_catalog_search")
+            .await?;
+        let raw = self
+            .parse_to_raw::<AvatarSearchQueryResponse>(response)
+            .await?;
 
         let items = raw.items;
         let next_cursor = raw.next_page_cursor;
@@ -377,45 +429,74 @@ impl Client {
 }
 
 mod internal {
-    use super::{request_types, sort_items_by_argument_order, Item, ItemDetails, ITEM_DETAILS_API};
+    use super::{request_types, AssetType, BundleType, Item, ITEM_DETAILS_API};
     use crate::XCSRF_HEADER;
     use crate::{Client, RoboatError};
+    use bytes::Bytes;
 
     impl Client {
         /// Used internally to fetch the details of one or more items from <https://catalog.roblox.com/v1/catalog/items/details>.
-        pub(super) async fn item_details_internal(
+        pub(super) async fn item_details_bytes_internal(
             &self,
             items: Vec<Item>,
-        ) -> Result<Vec<ItemDetails>, RoboatError> {
+        ) -> Result<Bytes, RoboatError> {
             let request_body = request_types::ItemDetailsReqBody {
                 // Convert the ItemParameters to te reqwest ItemParametersReq
                 items: items
                     .iter()
-                    .map(|x| request_types::ItemReq::from(*x))
+                    .map(|x| request_types::ItemReq::from(x.clone()))
                     .collect(),
             };
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(ITEM_DETAILS_API)
                 .header(XCSRF_HEADER, self.xcsrf().await)
-                .json(&request_body)
-                .send()
-                .await;
-
-            let response = Self::validate_request_result(request_result).await?;
-            let raw = Self::parse_to_raw::<request_types::ItemDetailsResponse>(response).await?;
-
-            let mut item_details = Vec::new();
-
-            for raw_details in raw.data {
-                let details = ItemDetails::try_from(raw_details)?;
-                item_details.push(details);
-            }
-
-            sort_items_by_argument_order(&mut item_details, &items);
-
-            Ok(item_details)
+                .json(&request_body);
+
+            let response = self
+                .send_request(request_builder, "catalog.item_details")
+                .await?;
+
+            let url = self
+                .detects_schema_drift()
+                .then(|| response.url().to_string());
+            let bytes = response.bytes().await.map_err(RoboatError::ReqwestError)?;
+
+            // Asset and bundle types are sent as numbers, so they are checked after parsing.
+            self.check_schema_drift::<request_types::ItemDetailsResponse<'static>>(
+                &bytes,
+                url,
+                |raw, report| {
+                    report.ignore_unused_fields(&["data[].bundledItems", "data[].offSaleDeadline"]);
+
+                    for (i, item) in raw.data.iter().enumerate() {
+                        if let Some(x) = item
+                            .asset_type
+                            .filter(|x| matches!(AssetType::from(*x), AssetType::Unknown(_)))
+                        {
+                            report.unexpected_enum_value(
+                                format!("data[{}].assetType", i),
+                                "AssetType",
+                                x.to_string(),
+                            );
+                        }
+
+                        if let Some(x) = item
+                            .bundle_type
+                            .filter(|x| matches!(BundleType::from(*x), BundleType::Unknown(_)))
+                        {
+                            report.unexpected_enum_value(
+                                format!("data[{}].bundleType", i),
+                                "BundleType",
+                                x.to_string(),
+                            );
+                        }
+                    }
+                },
+            )?;
+
+            Ok(bytes)
         }
     }
 }
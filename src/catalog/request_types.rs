@@ -2,15 +2,19 @@ use super::catalog_types::{
     CreatorType, Genre, Item, ItemRestriction, ItemStatus, ItemType, PremiumPricing, PriceStatus,
 };
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub(super) struct ItemDetailsResponse {
-    pub data: Vec<ItemDetailsRaw>,
+pub(super) struct ItemDetailsResponse<'a> {
+    #[serde(borrow)]
+    pub data: Vec<ItemDetailsRaw<'a>>,
 }
 
+/// Fields that are not used by [`ItemDetails`](super::ItemDetails) (such as `bundledItems` and `offSaleDeadline`)
+/// are left out so that they are skipped without being decoded.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(super) struct ItemDetailsRaw {
+pub(super) struct ItemDetailsRaw<'a> {
     pub id: Option<u64>,
     pub item_type: Option<ItemType>,
     pub bundle_type: Option<u64>,
@@ -19,7 +23,6 @@ pub(super) struct ItemDetailsRaw {
     pub description: Option<String>,
     pub product_id: Option<u64>,
     pub genres: Option<Vec<Genre>>,
-    pub bundled_items: Option<serde_json::Value>,
     pub item_status: Option<Vec<ItemStatus>>,
     pub item_restrictions: Option<Vec<ItemRestriction>>,
     pub creator_has_verified_badge: Option<bool>,
@@ -37,13 +40,6 @@ pub(super) struct ItemDetailsRaw {
     pub purchase_count: Option<u64>,
     pub premium_pricing: Option<PremiumPricing>,
     pub price_status: Option<PriceStatus>,
-    /// It is unknown as to what type this value is.
-    /// The farthest it can be tracked by reverse engineering is that the value
-    /// is fed into a `new Date()` constructor in js.
-    ///
-    /// Because of this, it is not included in the public struct until
-    /// we know what it is.
-    pub off_sale_deadline: Option<serde_json::Value>,
     /// Only exists for "new" limiteds.
     pub collectible_item_id: Option<String>,
     /// Only exists for "new" limiteds.
@@ -53,7 +49,9 @@ pub(super) struct ItemDetailsRaw {
     pub remaining_stock: Option<u64>,
     pub total_stock: Option<u64>,
     pub quantity_limit_per_user: Option<u64>,
-    pub sale_location_type: Option<String>,
+    /// Only used to pick an enum variant, so it is borrowed from the body.
+    #[serde(borrow)]
+    pub sale_location_type: Option<Cow<'a, str>>,
     /// Only exists for "new" limiteds.
     pub units_available_for_consumption: Option<u64>,
 }
@@ -36,15 +36,17 @@ impl Client {
     pub async fn unread_conversation_count(&self) -> Result<u64, RoboatError> {
         let cookie_string = self.cookie_string()?;
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(UNREAD_CONVERSATION_COUNT_API)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::UnreadMessageCountResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "chat.unread_conversation_count")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::UnreadMessageCountResponse>(response)
+            .await?;
 
         Ok(raw.count)
     }
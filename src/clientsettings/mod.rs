@@ -43,14 +43,13 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn client_version(
-        &self,
-        binary_type: String,
-    ) -> Result<ClientVersion, RoboatError> {
+    pub async fn client_version(&self, binary_type: String) -> Result<ClientVersion, RoboatError> {
         let formatted_url = format!("{}/client-version/{}", CLIENT_SETTINGS_V2_API, binary_type);
-        let request_result = self.reqwest_client.get(&formatted_url).send().await;
-        let response = Self::validate_request_result(request_result).await?;
-        Self::parse_to_raw::<ClientVersion>(response).await
+        let request_builder = self.reqwest_client.get(&formatted_url);
+        let response = self
+            .send_request(request_builder, "clientsettings.client_version")
+            .await?;
+        self.parse_to_raw::<ClientVersion>(response).await
     }
 
     /// Gets the client version information for a specific binary type and channel.
@@ -80,9 +79,11 @@ impl Client {
             "{}/client-version/{}/channel/{}",
             CLIENT_SETTINGS_V2_API, binary_type, channel_name
         );
-        let request_result = self.reqwest_client.get(&formatted_url).send().await;
-        let response = Self::validate_request_result(request_result).await?;
-        Self::parse_to_raw::<ClientVersion>(response).await
+        let request_builder = self.reqwest_client.get(&formatted_url);
+        let response = self
+            .send_request(request_builder, "clientsettings.client_version_for_channel")
+            .await?;
+        self.parse_to_raw::<ClientVersion>(response).await
     }
 
     /// Gets the channel name for the currently logged in user.
@@ -100,7 +101,7 @@ impl Client {
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = ClientBuilder::new().build();
     /// let user_channel = client.user_channel(Some("WindowsPlayer".to_string())).await?;
-    /// println!("User channel: {}", user_channel.channel_name);
+    /// println!("User channel: {}", user_channel.name);
     /// # Ok(())
     /// # }
     /// ```
@@ -115,14 +116,14 @@ impl Client {
             formatted_url.push_str(&format!("?binaryType={}", bt));
         }
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(&formatted_url)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(request_result).await?;
-        Self::parse_to_raw::<UserChannel>(response).await
+        let response = self
+            .send_request(request_builder, "clientsettings.user_channel")
+            .await?;
+        self.parse_to_raw::<UserChannel>(response).await
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,9 @@
-use crate::{Client, Limit, RoboatError};
+use crate::spending::SpendingItemId;
+use crate::{Client, Limit, PurchaseReceipt, RoboatError};
+use bytes::Bytes;
 use reqwest::header;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 mod request_types;
 
@@ -86,6 +89,98 @@ pub struct Listing {
     pub serial_number: Option<u64>,
 }
 
+/// A page of resale listings borrowed from the body of a response from
+/// <https://economy.roblox.com/v1/assets/{item_id}/resellers>.
+///
+/// Parsed using [`ResellersPageRef::parse`], usually from the body returned by [`Client::resellers_bytes`].
+/// Strings borrow from the body unless they contain escape sequences, so parsing a page only allocates the
+/// vector of listings. Fields not used by [`Listing`] are skipped without being decoded.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+pub struct ResellersPageRef<'a> {
+    /// The listings of the page.
+    #[serde(rename = "data", borrow)]
+    pub listings: Vec<ListingRef<'a>>,
+    /// The cursor for the next page of results, or `None` if there are no more pages.
+    #[serde(rename = "nextPageCursor", borrow)]
+    pub next_page_cursor: Option<Cow<'a, str>>,
+}
+
+/// A resale listing borrowed from the body of a response. The borrowed version of [`Listing`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingRef<'a> {
+    #[serde(rename = "userAssetId")]
+    pub uaid: u64,
+    pub price: u64,
+    #[serde(rename = "seller", borrow)]
+    pub reseller: ResellerRef<'a>,
+    pub serial_number: Option<u64>,
+}
+
+/// A reseller borrowed from the body of a response. The borrowed version of [`Reseller`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+pub struct ResellerRef<'a> {
+    #[serde(rename = "id")]
+    pub user_id: u64,
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+}
+
+impl<'a> ResellersPageRef<'a> {
+    /// Parses the body of a response from <https://economy.roblox.com/v1/assets/{item_id}/resellers>.
+    ///
+    /// # Errors
+    /// * [`RoboatError::MalformedResponse`] - If the body is not a valid page of listings.
+    ///
+    /// # Example
+    /// ```
+    /// use roboat::economy::ResellersPageRef;
+    ///
+    /// let body = br#"{"previousPageCursor":null,"nextPageCursor":"abc","data":[
+    ///     {"userAssetId":1,"seller":{"hasVerifiedBadge":false,"id":2,"type":"User","name":"bob"},"price":100,"serialNumber":null}
+    /// ]}"#;
+    ///
+    /// let page = ResellersPageRef::parse(body).unwrap();
+    /// assert_eq!(page.listings[0].reseller.name, "bob");
+    /// assert_eq!(page.next_page_cursor.as_deref(), Some("abc"));
+    ///
+    /// let (listings, next_page_cursor) = page.into_owned();
+    /// assert_eq!(listings[0].price, 100);
+    /// ```
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, RoboatError> {
+        serde_json::from_slice(bytes).map_err(|_| RoboatError::MalformedResponse)
+    }
+
+    /// Converts the page into owned listings and the cursor for the next page, which is
+    /// what [`Client::resellers`] returns.
+    pub fn into_owned(self) -> (Vec<Listing>, Option<String>) {
+        let listings = self
+            .listings
+            .into_iter()
+            .map(ListingRef::into_owned)
+            .collect();
+
+        (listings, self.next_page_cursor.map(Cow::into_owned))
+    }
+}
+
+impl ListingRef<'_> {
+    /// Converts the listing into an owned [`Listing`].
+    pub fn into_owned(self) -> Listing {
+        Listing {
+            uaid: self.uaid,
+            price: self.price,
+            reseller: Reseller {
+                user_id: self.reseller.user_id,
+                name: self.reseller.name.into_owned(),
+            },
+            serial_number: self.serial_number,
+        }
+    }
+}
+
 /// A sale of an asset from the user's transaction history. Retrieved from <https://economy.roblox.com/v2/users/{user_id}/transactions?transactionType=Sale>.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 pub struct UserSale {
@@ -138,15 +233,15 @@ impl Client {
         let formatted_url = format!("{}{}{}", ROBUX_API_PART_1, user_id, ROBUX_API_PART_2);
         let cookie = self.cookie_string()?;
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(formatted_url)
-            .header(header::COOKIE, cookie)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::CurrencyResponse>(response).await?;
+        let response = self.send_request(request_builder, "economy.robux").await?;
+        let raw = self
+            .parse_to_raw::<request_types::CurrencyResponse>(response)
+            .await?;
 
         let robux = raw.robux;
 
@@ -195,6 +290,57 @@ impl Client {
         limit: Limit,
         cursor: Option<String>,
     ) -> Result<(Vec<Listing>, Option<String>), RoboatError> {
+        let bytes = self.resellers_bytes(item_id, limit, cursor).await?;
+        let page = ResellersPageRef::parse(&bytes)?;
+
+        Ok(page.into_owned())
+    }
+
+    /// Grabs resellers of an item from <https://economy.roblox.com/v1/assets/{item_id}/resellers?cursor={cursor}&limit={limit}>,
+    /// returning the raw body of the response.
+    ///
+    /// This is meant for high-frequency polling, where the body can be parsed into borrowed listings using
+    /// [`ResellersPageRef::parse`] instead of being copied into owned ones like [`Client::resellers`] does.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    ///
+    /// # Argument Notes
+    /// * The cursor is used to get the a certain page of results. If you want the starting page, use `None`.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::economy::ResellersPageRef;
+    /// use roboat::Limit;
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let item_id = 1365767;
+    ///
+    /// let bytes = client.resellers_bytes(item_id, Limit::Ten, None).await?;
+    /// let page = ResellersPageRef::parse(&bytes)?;
+    ///
+    /// if let Some(listing) = page.listings.first() {
+    ///     println!("Lowest Price for Item {}: {} (by {})", item_id, listing.price, listing.reseller.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resellers_bytes(
+        &self,
+        item_id: u64,
+        limit: Limit,
+        cursor: Option<String>,
+    ) -> Result<Bytes, RoboatError> {
         let limit = limit.to_u64();
         let cursor = cursor.unwrap_or_default();
         let cookie = self.cookie_string()?;
@@ -204,37 +350,29 @@ impl Client {
             RESELLERS_API_PART_1, item_id, RESELLERS_API_PART_2, cursor, limit
         );
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(formatted_url)
-            .header(header::COOKIE, cookie)
-            .send()
-            .await;
-
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::ResellersResponse>(response).await?;
-
-        let next_page_cursor = raw.next_page_cursor;
-
-        let mut listings = Vec::new();
-
-        for listing in raw.data {
-            let reseller = Reseller {
-                user_id: listing.seller.id,
-                name: listing.seller.name,
-            };
-
-            let listing = Listing {
-                uaid: listing.user_asset_id,
-                price: listing.price,
-                reseller,
-                serial_number: listing.serial_number,
-            };
-
-            listings.push(listing);
-        }
-
-        Ok((listings, next_page_cursor))
+            .header(header::COOKIE, cookie);
+
+        let response = self
+            .send_request(request_builder, "economy.resellers")
+            .await?;
+
+        let url = self
+            .detects_schema_drift()
+            .then(|| response.url().to_string());
+        let bytes = response.bytes().await.map_err(RoboatError::ReqwestError)?;
+
+        self.check_schema_drift::<ResellersPageRef<'static>>(&bytes, url, |_, report| {
+            report.ignore_unused_fields(&[
+                "previousPageCursor",
+                "data[].seller.hasVerifiedBadge",
+                "data[].seller.type",
+            ])
+        })?;
+
+        Ok(bytes)
     }
 
     /// Grabs user sales from <https://economy.roblox.com/v2/users/{user_id}/transactions?transactionType=Sale&cursor={cursor}&limit={limit}>.
@@ -301,15 +439,17 @@ impl Client {
 
         let cookie = self.cookie_string()?;
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(formatted_url)
-            .header(header::COOKIE, cookie)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::UserSalesResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "economy.user_sales")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::UserSalesResponse>(response)
+            .await?;
 
         let next_page_cursor = raw.next_page_cursor;
 
@@ -390,8 +530,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.put_limited_on_sale_internal(item_id, uaid, price)
-                        .await
+                    Self::retry(self.put_limited_on_sale_internal(item_id, uaid, price)).await
                 }
                 _ => Err(e),
             },
@@ -439,7 +578,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.take_limited_off_sale_internal(item_id, uaid).await
+                    Self::retry(self.take_limited_off_sale_internal(item_id, uaid)).await
                 }
                 _ => Err(e),
             },
@@ -454,9 +593,10 @@ impl Client {
     /// * Will repeat once if the x-csrf-token is invalid.
     ///
     /// # Return Value Notes
-    /// * Will return `Ok(())` if the limited was successfully purchased.
+    /// * Will return `Ok(PurchaseReceipt)` if the limited was successfully purchased.
+    ///   The receipt contains the uaid bought, and the `reason` returned by Roblox as the purchase result.
     /// * As it will repeat once if the x-csrf-token is invalid, you may want to manually refresh the x-csrf-token
-    /// on another thread by using [`Client::force_refresh_xcsrf`].
+    ///   on another thread by using [`Client::force_refresh_xcsrf`].
     ///
     /// # Argument Notes
     /// * `product_id` is the product id of the limited, NOT the item id.
@@ -466,6 +606,8 @@ impl Client {
     /// * All errors under [Auth Required Errors](#auth-required-errors).
     /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
     /// * [`RoboatError::PurchaseTradableLimitedError`] - Nested inside this error, all variants of [`PurchaseTradableLimitedError`] may be thrown.
+    /// * [`RoboatError::SpendingPolicyViolation`] - If the purchase is blocked by the [`SpendingPolicy`](crate::spending::SpendingPolicy)
+    ///   of the client, checked using [`SpendingItemId::ProductId`].
     ///
     /// # Example
     /// ```no_run
@@ -482,8 +624,8 @@ impl Client {
     /// let uaid = 987654321;
     /// let price = 5000;
     ///
-    /// let _ = client.purchase_tradable_limited(product_id, seller_id, uaid, price).await?;
-    /// println!("Successfully Purchased!");
+    /// let receipt = client.purchase_tradable_limited(product_id, seller_id, uaid, price).await?;
+    /// println!("Successfully Purchased {:?} for {} robux!", receipt.uaid, receipt.price);
     /// # Ok(())
     /// # }
     /// ```
@@ -493,8 +635,17 @@ impl Client {
         seller_id: u64,
         uaid: u64,
         price: u64,
-    ) -> Result<(), RoboatError> {
-        match self
+    ) -> Result<PurchaseReceipt, RoboatError> {
+        let reservation = self
+            .reserve_spending(
+                "economy.purchase_limited",
+                SpendingItemId::ProductId(product_id),
+                price,
+                None,
+            )
+            .await?;
+
+        let result = match self
             .purchase_limited_internal(product_id, price, seller_id, uaid)
             .await
         {
@@ -503,12 +654,16 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.purchase_limited_internal(product_id, price, seller_id, uaid)
+                    Self::retry(self.purchase_limited_internal(product_id, price, seller_id, uaid))
                         .await
                 }
                 _ => Err(e),
             },
-        }
+        };
+
+        self.settle_spending(reservation, &result);
+
+        result
     }
 }
 
@@ -516,7 +671,9 @@ mod internal {
     use super::{
         request_types, PurchaseTradableLimitedError, TOGGLE_SALE_API_PART_1, TOGGLE_SALE_API_PART_2,
     };
-    use crate::{Client, RoboatError, CONTENT_TYPE, USER_AGENT, XCSRF_HEADER};
+    use crate::{
+        Client, PurchaseReceipt, PurchasedItem, RoboatError, CONTENT_TYPE, USER_AGENT, XCSRF_HEADER,
+    };
     use reqwest::header;
 
     impl Client {
@@ -537,16 +694,16 @@ mod internal {
                 "price": price,
             });
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .patch(formatted_url)
                 .header(header::COOKIE, cookie)
                 .header(XCSRF_HEADER, self.xcsrf().await)
-                .json(&json)
-                .send()
-                .await;
+                .json(&json);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "economy.put_limited_on_sale")
+                .await?;
 
             // We don't need to do anything, we just need a 200 status code.
 
@@ -567,16 +724,16 @@ mod internal {
 
             let json = serde_json::json!({});
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .patch(formatted_url)
                 .header(header::COOKIE, cookie)
                 .header(XCSRF_HEADER, self.xcsrf().await)
-                .json(&json)
-                .send()
-                .await;
+                .json(&json);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "economy.take_limited_off_sale")
+                .await?;
 
             // We don't need to do anything, we just need a 200 status code.
 
@@ -589,7 +746,7 @@ mod internal {
             price: u64,
             seller_id: u64,
             uaid: u64,
-        ) -> Result<(), RoboatError> {
+        ) -> Result<PurchaseReceipt, RoboatError> {
             let formatted_url = format!(
                 "https://economy.roblox.com/v1/purchases/products/{}",
                 product_id
@@ -604,24 +761,33 @@ mod internal {
                 "userAssetId": uaid,
             });
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
                 .header(XCSRF_HEADER, self.xcsrf().await)
                 .header(header::USER_AGENT, USER_AGENT)
                 .header(header::CONTENT_TYPE, CONTENT_TYPE)
-                .json(&json)
-                .send()
-                .await;
+                .json(&json);
 
-            let response = Self::validate_request_result(request_result).await?;
+            let response = self
+                .send_mutating_request(request_builder, "economy.purchase_limited")
+                .await?;
 
-            let raw =
-                Self::parse_to_raw::<request_types::PurchaseLimitedResponse>(response).await?;
+            let raw = self
+                .parse_to_raw::<request_types::PurchaseLimitedResponse>(response)
+                .await?;
 
             match raw.purchased {
-                true => Ok(()),
+                true => Ok(PurchaseReceipt {
+                    item: PurchasedItem::TradableLimited { product_id },
+                    price,
+                    seller_id,
+                    uaid: Some(uaid),
+                    collectible_item_instance_id: None,
+                    idempotency_key: None,
+                    purchase_result: raw.reason,
+                }),
                 false => match raw.error_msg.as_str() {
                     "You have a pending transaction. Please wait 1 minute and try again." => {
                         Err(RoboatError::PurchaseTradableLimitedError(
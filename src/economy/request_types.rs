@@ -5,33 +5,6 @@ pub(super) struct CurrencyResponse {
     pub robux: u64,
 }
 
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(super) struct ResellersResponse {
-    pub previous_page_cursor: Option<String>,
-    pub next_page_cursor: Option<String>,
-    pub data: Vec<ListingRaw>,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(super) struct ListingRaw {
-    pub user_asset_id: u64,
-    pub seller: ResellerRaw,
-    pub price: u64,
-    pub serial_number: Option<u64>,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(super) struct ResellerRaw {
-    pub has_verified_badge: bool,
-    pub id: u64,
-    #[serde(rename = "type")]
-    pub seller_type: Option<serde_json::Value>,
-    pub name: String,
-}
-
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct UserSalesResponse {
@@ -77,6 +50,7 @@ pub(super) enum CurrencyTypeRaw {
     Robux,
 }
 
+#[allow(dead_code)]
 #[derive(Serialize, Deserialize)]
 pub(super) enum TransactionTypeRaw {
     Sale,
@@ -87,4 +61,6 @@ pub(super) enum TransactionTypeRaw {
 pub struct PurchaseLimitedResponse {
     pub purchased: bool,
     pub error_msg: String,
+    #[serde(default)]
+    pub reason: String,
 }
@@ -0,0 +1,221 @@
+//! Support for enums that keep values not known by this crate in an `Unknown` variant,
+//! so that a new value sent by Roblox does not make a whole response fail to parse.
+
+use serde::de::{EnumAccess, VariantAccess, Visitor};
+use std::marker::PhantomData;
+
+/// Implements `as_str`, `From<&str>`, `Display`, `Serialize`, and `Deserialize` for an enum made of
+/// unit variants and an `Unknown(String)` variant, which holds any value not listed.
+///
+/// Each variant is given the value Roblox sends for it, followed by any other accepted values
+/// (`Variant => "Value" | "Alias"`). The first value is the one serialized.
+macro_rules! string_enum {
+    ($name:ident { $($variant:ident => $value:literal $(| $alias:literal)*,)* }) => {
+        impl $name {
+            /// Returns the value Roblox uses for the variant.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(x) => x,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value $(| $alias)* => Self::$variant,)*
+                    _ => Self::Unknown(value.to_string()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                const VARIANTS: &[&str] = &[$($value, $($alias,)*)*];
+
+                deserializer.deserialize_enum(
+                    stringify!($name),
+                    VARIANTS,
+                    $crate::enums::StringEnumVisitor::new(),
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use string_enum;
+
+/// Implements `From<u64>`, `From<Self> for u64`, `Serialize`, and `Deserialize` for an enum made of
+/// unit variants and an `Unknown(u64)` variant, which holds any id not listed.
+///
+/// Each variant is given the id Roblox uses for it (`Variant => 1`). Known variants are serialized
+/// as their name and unknown ones as their id, and either form is accepted when deserializing.
+macro_rules! id_enum {
+    ($name:ident { $($variant:ident => $id:literal,)* }) => {
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                match value {
+                    $($id => Self::$variant,)*
+                    x => Self::Unknown(x),
+                }
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $id,)*
+                    $name::Unknown(x) => x,
+                }
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    $(Self::$variant => serializer.serialize_str(stringify!($variant)),)*
+                    Self::Unknown(x) => serializer.serialize_u64(*x),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                const VARIANTS: &[(&str, u64)] = &[$((stringify!($variant), $id),)*];
+
+                deserializer.deserialize_any($crate::enums::IdEnumVisitor::new(VARIANTS))
+            }
+        }
+    };
+}
+
+pub(crate) use id_enum;
+
+/// Deserializes an enum declared with [`string_enum`] from a string.
+///
+/// The enum is deserialized through `deserialize_enum` so that the known values are visible to
+/// the deserializer (and are reported by [schema drift detection](crate::schema) when missing).
+pub(crate) struct StringEnumVisitor<T>(PhantomData<T>);
+
+impl<T> StringEnumVisitor<T> {
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'de, T: for<'a> From<&'a str>> Visitor<'de> for StringEnumVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+        Ok(T::from(value))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<T, A::Error> {
+        let (value, variant): (String, _) = data.variant()?;
+        variant.unit_variant()?;
+
+        Ok(T::from(value.as_str()))
+    }
+}
+
+/// Deserializes an enum declared with [`id_enum`] from its id, or from the name of a variant.
+pub(crate) struct IdEnumVisitor<T> {
+    variants: &'static [(&'static str, u64)],
+    enum_type: PhantomData<T>,
+}
+
+impl<T> IdEnumVisitor<T> {
+    pub(crate) fn new(variants: &'static [(&'static str, u64)]) -> Self {
+        Self {
+            variants,
+            enum_type: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: From<u64>> Visitor<'de> for IdEnumVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an id or a variant name")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<T, E> {
+        Ok(T::from(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<T, E> {
+        u64::try_from(value)
+            .map(T::from)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+        match self.variants.iter().find(|(name, _)| *name == value) {
+            Some((_, id)) => Ok(T::from(*id)),
+            None => Err(E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::{AssetType, ItemType};
+
+    #[test]
+    fn keeps_unknown_strings() {
+        let item_type: ItemType = serde_json::from_str(r#""Experience""#).unwrap();
+
+        assert_eq!(item_type, ItemType::Unknown("Experience".to_string()));
+        assert_eq!(
+            serde_json::to_string(&item_type).unwrap(),
+            r#""Experience""#
+        );
+        assert_eq!(
+            serde_json::from_str::<ItemType>(r#""Bundle""#).unwrap(),
+            ItemType::Bundle
+        );
+    }
+
+    #[test]
+    fn serializes_known_ids_by_name() {
+        assert_eq!(serde_json::to_string(&AssetType::Hat).unwrap(), r#""Hat""#);
+        assert_eq!(
+            serde_json::to_string(&AssetType::Unknown(999)).unwrap(),
+            "999"
+        );
+    }
+
+    #[test]
+    fn deserializes_ids_and_names() {
+        for value in [r#""Hat""#, "8"] {
+            assert_eq!(
+                serde_json::from_str::<AssetType>(value).unwrap(),
+                AssetType::Hat
+            );
+        }
+
+        assert_eq!(
+            serde_json::from_str::<AssetType>("999").unwrap(),
+            AssetType::Unknown(999)
+        );
+        assert!(serde_json::from_str::<AssetType>(r#""NotAnAssetType""#).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+//! Helpers for writing the files kept by long running tasks, such as checkpoints and audit logs.
+
+use crate::RoboatError;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+/// Serializes `value` as JSON and writes it to `path`, replacing the file atomically.
+///
+/// The contents are written to `{path}.tmp` and synced before being renamed over `path`, so a crash
+/// never leaves a partially written file behind.
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), RoboatError> {
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(".tmp");
+
+    let contents = serde_json::to_vec(value).map_err(std::io::Error::from)?;
+
+    let mut file = File::create(&temporary_path)?;
+    file.write_all(&contents)?;
+    file.sync_all()?;
+
+    fs::rename(&temporary_path, path)?;
+
+    Ok(())
+}
+
+/// Serializes `value` as a single line of JSON, ending with a newline.
+pub(crate) fn json_line<T: Serialize>(value: &T) -> Result<String, RoboatError> {
+    let mut line = serde_json::to_string(value).map_err(std::io::Error::from)?;
+    line.push('\n');
+
+    Ok(line)
+}
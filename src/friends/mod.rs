@@ -63,7 +63,7 @@ impl Client {
     /// println!("Found {} friends.", friends.len());
     ///
     /// for friend in friends {
-    ///     println!("{}: {}", friend.username, friend.user_id);
+    ///     println!("{}: {}", friend.name, friend.id);
     /// }
     ///
     /// # Ok(())
@@ -75,10 +75,14 @@ impl Client {
     ) -> Result<Vec<request_types::FriendUserInformation>, RoboatError> {
         let formatted_url = FRIENDS_LIST_API.replace("{user_id}", &user_id.to_string());
 
-        let request_result = self.reqwest_client.get(formatted_url).send().await;
+        let request_builder = self.reqwest_client.get(formatted_url);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let friends = Self::parse_to_raw::<request_types::FriendsListResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "friends.friends_list")
+            .await?;
+        let friends = self
+            .parse_to_raw::<request_types::FriendsListResponse>(response)
+            .await?;
 
         Ok(friends.data)
     }
@@ -106,7 +110,7 @@ impl Client {
     /// let (friend_requests, next_cursor) = client.friend_requests(None).await?;
     ///
     /// for user in friend_requests {
-    ///     println!("{} from {}: {}", user.username, user.user_id);
+    ///     println!("{}: {}", user.name, user.id);
     /// }
     ///
     /// # Ok(())
@@ -123,16 +127,18 @@ impl Client {
             formatted_url = format!("{}&cursor={}", formatted_url, cursor)
         }
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(formatted_url)
-            .header(header::COOKIE, cookie)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie);
 
-        let response = Self::validate_request_result(request_result).await?;
+        let response = self
+            .send_request(request_builder, "friends.friend_requests")
+            .await?;
 
-        let raw = Self::parse_to_raw::<FriendRequestsResponse>(response).await?;
+        let raw = self
+            .parse_to_raw::<FriendRequestsResponse>(response)
+            .await?;
         let next_page_cursor = raw.next_page_cursor.clone();
 
         Ok((raw, next_page_cursor))
@@ -169,17 +175,18 @@ impl Client {
         let cookie = self.cookie_string()?;
         let formatted_url = PENDING_FRIEND_REQUESTS_API;
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(formatted_url)
-            .header(header::COOKIE, cookie)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie);
 
-        let response = Self::validate_request_result(request_result).await?;
+        let response = self
+            .send_request(request_builder, "friends.pending_friend_requests")
+            .await?;
 
-        let raw =
-            Self::parse_to_raw::<request_types::PendingFriendRequestsResponse>(response).await?;
+        let raw = self
+            .parse_to_raw::<request_types::PendingFriendRequestsResponse>(response)
+            .await?;
 
         Ok(raw.count)
     }
@@ -220,7 +227,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.accept_friend_request_internal(requester_id).await
+                    Self::retry(self.accept_friend_request_internal(requester_id)).await
                 }
                 _ => Err(e),
             },
@@ -263,7 +270,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.decline_friend_request_internal(requester_id).await
+                    Self::retry(self.decline_friend_request_internal(requester_id)).await
                 }
                 _ => Err(e),
             },
@@ -305,7 +312,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.send_friend_request_internal(target_id).await
+                    Self::retry(self.send_friend_request_internal(target_id)).await
                 }
                 _ => Err(e),
             },
@@ -347,7 +354,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.unfriend_internal(target_id).await
+                    Self::retry(self.unfriend_internal(target_id)).await
                 }
                 _ => Err(e),
             },
@@ -372,15 +379,15 @@ mod internal {
             let cookie = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "friends.accept_friend_request")
+                .await?;
 
             // If we got a status code 200, it was successful.
 
@@ -397,15 +404,15 @@ mod internal {
             let cookie = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "friends.decline_friend_request")
+                .await?;
 
             // If we got a status code 200, it was successful.
 
@@ -427,16 +434,16 @@ mod internal {
                 "friendshipOriginSourceType": 0
             });
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
                 .header(XCSRF_HEADER, xcsrf)
-                .json(&body)
-                .send()
-                .await;
+                .json(&body);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "friends.send_friend_request")
+                .await?;
 
             // If we got a status code 200, it was successful.
 
@@ -449,15 +456,15 @@ mod internal {
             let cookie = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "friends.unfriend")
+                .await?;
 
             // If we got a status code 200, it was successful.
 
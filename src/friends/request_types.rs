@@ -54,13 +54,9 @@ pub struct RequestInfo {
 }
 
 /// Represents the source of a friend request or friend connection
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum OriginSource {
-    /// Source is unknown or not specified
-    #[serde(rename = "Unknown")]
-    Unknown = 0,
-
     /// Friend was found through player search
     #[serde(rename = "PlayerSearch")]
     PlayerSearch = 1,
@@ -96,12 +92,12 @@ pub enum OriginSource {
     /// Friend was suggested through recommendations
     #[serde(rename = "FriendRecommendations")]
     FriendRecommendations = 9,
-}
 
-impl Default for OriginSource {
-    fn default() -> Self {
-        OriginSource::Unknown
-    }
+    /// Source is unknown, not specified, or not known by this crate.
+    /// Kept last as it is used for any unrecognized value.
+    #[default]
+    #[serde(rename = "Unknown", other)]
+    Unknown = 0,
 }
 
 // Optionally, implement From<u8> to convert from numeric values
@@ -174,10 +174,12 @@ mod internal {
         ) -> Result<GamesResponseV2, RoboatError> {
             // Max limit is 50
             let formatted_url = format!("{}/users/{}/games?limit=50", GAMES_V2_API, user_id);
-            let request_result = self.reqwest_client.get(formatted_url).send().await;
+            let request_builder = self.reqwest_client.get(formatted_url);
 
-            let response = Self::validate_request_result(request_result).await?;
-            let users_games_json = Self::parse_to_raw::<GamesResponseV2>(response).await?;
+            let response = self
+                .send_request(request_builder, "games.user_games")
+                .await?;
+            let users_games_json = self.parse_to_raw::<GamesResponseV2>(response).await?;
             Ok(users_games_json)
         }
 
@@ -186,10 +188,12 @@ mod internal {
             group_id: u64,
         ) -> Result<GamesResponseV2, RoboatError> {
             let formatted_url = format!("{}/groups/{}/gamesv2?limit=100", GAMES_V2_API, group_id);
-            let request_result = self.reqwest_client.get(formatted_url).send().await;
+            let request_builder = self.reqwest_client.get(formatted_url);
 
-            let response = Self::validate_request_result(request_result).await?;
-            let group_games_json = Self::parse_to_raw::<GamesResponseV2>(response).await?;
+            let response = self
+                .send_request(request_builder, "games.group_games")
+                .await?;
+            let group_games_json = self.parse_to_raw::<GamesResponseV2>(response).await?;
             Ok(group_games_json)
         }
     }
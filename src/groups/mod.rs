@@ -65,15 +65,19 @@ impl Client {
     pub async fn group_roles(&self, group_id: u64) -> Result<Vec<Role>, RoboatError> {
         let formatted_url = GROUP_ROLES_API.replace("{group_id}", &group_id.to_string());
 
-        let request_result = self.reqwest_client.get(formatted_url).send().await;
+        let request_builder = self.reqwest_client.get(formatted_url);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::RolesResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "groups.group_roles")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::RolesResponse>(response)
+            .await?;
 
         let mut roles = raw.roles;
 
         // Enforce that the roles are in order by rank in ascending order
-        roles.sort_by(|a, b| a.rank.cmp(&b.rank));
+        roles.sort_by_key(|a| a.rank);
 
         Ok(roles)
     }
@@ -128,10 +132,14 @@ impl Client {
             .replace("{limit}", &limit.to_u64().to_string())
             .replace("{sort_order}", GROUP_ROLE_MEMBERS_SORT_ORDER);
 
-        let request_result = self.reqwest_client.get(formatted_url).send().await;
+        let request_builder = self.reqwest_client.get(formatted_url);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::RoleMembersResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "groups.group_role_members")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::RoleMembersResponse>(response)
+            .await?;
 
         let mut users = Vec::new();
 
@@ -198,7 +206,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.set_group_member_role_internal(user_id, group_id, role_id)
+                    Self::retry(self.set_group_member_role_internal(user_id, group_id, role_id))
                         .await
                 }
                 _ => Err(e),
@@ -228,16 +236,16 @@ mod internal {
 
             let json = serde_json::json!({ "roleId": role_id });
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .patch(formatted_url)
                 .json(&json)
                 .header(header::COOKIE, cookie)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "groups.set_group_member_role")
+                .await?;
 
             // If we got a status code 200, it was successful.
 
@@ -42,7 +42,7 @@ impl Client {
     /// # Example
     /// ```no_run
     /// use bytes::Bytes;
-    /// use roboat::{ClientBuilder, ide::request_types::Animation};
+    /// use roboat::{ClientBuilder, ide::ide_types::NewAnimation};
     ///
     /// const ROBLOSECURITY: &str = "your_.ROBLOSECURITY_cookie";
     ///
@@ -52,12 +52,11 @@ impl Client {
     ///     .roblosecurity(ROBLOSECURITY.to_string())
     ///     .build();
     ///
-    /// let animation = Animation {
-    ///     id: None,
-    ///     title: "MyCoolAnimation".to_string(),
+    /// let animation = NewAnimation {
+    ///     name: "MyCoolAnimation".to_string(),
     ///     description: "A test animation created by Roboat.".to_string(),
     ///     group_id: Some(123456),
-    ///     animation_data: Some(Bytes::from_static(b"<KeyframeSequence>...</KeyframeSequence>")),
+    ///     animation_data: Bytes::from_static(b"<KeyframeSequence>...</KeyframeSequence>"),
     /// };
     ///
     /// client.upload_new_animation(animation).await?;
@@ -77,7 +76,7 @@ impl Client {
             Ok(x) => Ok(x),
             Err(RoboatError::InvalidXcsrf(new_xcsrf)) => {
                 self.set_xcsrf(new_xcsrf).await;
-                self.upload_new_animation_internal(animation_info).await
+                Self::retry(self.upload_new_animation_internal(animation_info)).await
             }
             Err(e) => Err(e),
         }
@@ -107,17 +106,17 @@ mod internal {
                 formatted_url = formatted_url.replace("{groupId}", &group_id.to_string());
             }
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(formatted_url)
                 .header(header::COOKIE, cookie)
                 .body(animation_info.animation_data)
                 .header(XCSRF_HEADER, xcsrf)
-                .header(USER_AGENT, "Roblox/WinInet")
-                .send()
-                .await;
+                .header(USER_AGENT, "Roblox/WinInet");
 
-            let response = Self::validate_request_result(request_result).await?;
+            let response = self
+                .send_mutating_request(request_builder, "ide.upload_new_animation")
+                .await?;
             let response_id = response.text().await.map_err(RoboatError::ReqwestError)?;
             Ok(response_id)
         }
@@ -0,0 +1,452 @@
+use crate::catalog::{AssetType, BundleType, ItemType};
+use crate::{Client, Limit, RoboatError};
+use reqwest::header;
+use serde::{Deserialize, Serialize};
+
+mod request_types;
+
+const COLLECTIBLES_API: &str =
+    "https://inventory.roblox.com/v1/users/{user_id}/assets/collectibles";
+
+const INVENTORY_API: &str =
+    "https://inventory.roblox.com/v2/users/{user_id}/inventory/{asset_type}";
+
+const CAN_VIEW_INVENTORY_API: &str =
+    "https://inventory.roblox.com/v1/users/{user_id}/can-view-inventory";
+
+const BUNDLES_API: &str = "https://catalog.roblox.com/v1/users/{user_id}/bundles";
+
+const IS_OWNED_API: &str =
+    "https://inventory.roblox.com/v1/users/{user_id}/items/{item_type}/{item_id}/is-owned";
+
+/// For inventory requests, we use Ascending as the sort order, which is the order
+/// the website uses. Items are ordered by when they were obtained.
+const SORT_ORDER: &str = "Asc";
+
+/// A copy of a limited item owned by a user.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct Collectible {
+    /// The unique asset id of this copy of the item. Used to trade the item or put it on sale.
+    pub uaid: u64,
+    /// The asset id of the item.
+    pub asset_id: u64,
+    /// The name of the item.
+    pub name: String,
+    /// The serial number of this copy. Only exists for limited Us.
+    pub serial_number: Option<u64>,
+    /// The recent average price of the item.
+    pub rap: u64,
+    /// The price the item was originally sold for, if it was sold.
+    pub original_price: Option<u64>,
+    /// The amount of copies of the item that were sold. Only exists for limited Us.
+    pub asset_stock: Option<u64>,
+    /// Whether this copy is on hold. Copies are put on hold for a few days after being
+    /// purchased, and cannot be traded or sold until the hold ends.
+    pub is_on_hold: bool,
+}
+
+impl Collectible {
+    /// Returns whether this copy can be traded or put on sale right now.
+    pub fn is_tradable(&self) -> bool {
+        !self.is_on_hold
+    }
+}
+
+/// A bundle owned by a user.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct InventoryBundle {
+    /// The id of the bundle.
+    pub bundle_id: u64,
+    /// The name of the bundle.
+    pub name: String,
+    /// The type of the bundle.
+    pub bundle_type: BundleType,
+    /// The id of the creator of the bundle.
+    pub creator_id: u64,
+    /// The name of the creator of the bundle.
+    pub creator_name: String,
+}
+
+/// An item in the inventory of a user.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct InventoryItem {
+    /// The unique asset id of this copy of the item.
+    pub uaid: u64,
+    /// The asset id of the item.
+    pub asset_id: u64,
+    /// The name of the item.
+    pub name: String,
+    /// The serial number of this copy. Only exists for limited items.
+    pub serial_number: Option<u64>,
+    /// The collectible item id of the item. Only exists for limiteds sold through the
+    /// collectibles system (Limited 2.0).
+    pub collectible_item_id: Option<String>,
+    /// The time the copy was obtained, in ISO 8601 format.
+    pub created: String,
+}
+
+impl Client {
+    /// Fetches the limited items owned by a user using
+    /// <https://inventory.roblox.com/v1/users/{user_id}/assets/collectibles>.
+    ///
+    /// # Notes
+    /// * Does not require a valid roblosecurity. If the client has one, it is sent so that
+    ///   inventories only visible to the account can be fetched.
+    /// * The inventory of the user must be visible to the client.
+    /// * Items are ordered from the oldest obtained to the newest obtained.
+    ///
+    /// # Return Value Notes
+    /// * The first value is a vector of the items.
+    /// * The second value is the cursor for the next page of results. If there are no more pages, this is `None`.
+    ///
+    /// # Argument Notes
+    /// * `cursor` should be `None` to fetch the first page.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::{ClientBuilder, Limit};
+    ///
+    /// const USER_ID: u64 = 1;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// let (collectibles, next_cursor) = client.collectibles(USER_ID, Limit::Hundred, None).await?;
+    ///
+    /// for collectible in collectibles.iter().filter(|x| x.is_tradable()) {
+    ///     println!("{} ({}): {} rap", collectible.name, collectible.uaid, collectible.rap);
+    /// }
+    ///
+    /// println!("Next Cursor: {:?}", next_cursor);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collectibles(
+        &self,
+        user_id: u64,
+        limit: Limit,
+        cursor: Option<String>,
+    ) -> Result<(Vec<Collectible>, Option<String>), RoboatError> {
+        let formatted_url = format!(
+            "{}?sortOrder={}&cursor={}&limit={}",
+            COLLECTIBLES_API.replace("{user_id}", &user_id.to_string()),
+            SORT_ORDER,
+            cursor.unwrap_or_default(),
+            limit.to_u64()
+        );
+
+        let mut request_builder = self.reqwest_client.get(&formatted_url);
+
+        // The cookie is optional here.
+        if let Ok(cookie_string) = self.cookie_string() {
+            request_builder = request_builder.header(header::COOKIE, cookie_string);
+        }
+
+        let response = self
+            .send_request(request_builder, "inventory.collectibles")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::CollectiblesResponse>(response)
+            .await?;
+
+        let collectibles = raw
+            .data
+            .into_iter()
+            .map(|x| Collectible {
+                uaid: x.user_asset_id,
+                asset_id: x.asset_id,
+                name: x.name,
+                serial_number: x.serial_number,
+                rap: x.recent_average_price.unwrap_or_default(),
+                original_price: x.original_price,
+                asset_stock: x.asset_stock,
+                is_on_hold: x.is_on_hold,
+            })
+            .collect();
+
+        Ok((collectibles, raw.next_page_cursor))
+    }
+
+    /// Fetches the items of a given asset type owned by a user using
+    /// <https://inventory.roblox.com/v2/users/{user_id}/inventory/{asset_type_id}>.
+    ///
+    /// # Notes
+    /// * Does not require a valid roblosecurity. If the client has one, it is sent so that
+    ///   inventories only visible to the account can be fetched.
+    /// * The inventory of the user must be visible to the client. Use [`Client::can_view_inventory`]
+    ///   to check beforehand.
+    /// * Items are ordered from the oldest obtained to the newest obtained.
+    /// * Bundles are not asset types. Use [`Client::bundles`] to list them.
+    ///
+    /// # Return Value Notes
+    /// * The first value is a vector of the items.
+    /// * The second value is the cursor for the next page of results. If there are no more pages, this is `None`.
+    ///
+    /// # Argument Notes
+    /// * `cursor` should be `None` to fetch the first page.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::catalog::AssetType;
+    /// use roboat::{ClientBuilder, Limit};
+    ///
+    /// const USER_ID: u64 = 1;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// let (hats, next_cursor) = client
+    ///     .inventory(USER_ID, AssetType::Hat, Limit::Hundred, None)
+    ///     .await?;
+    ///
+    /// for hat in hats {
+    ///     println!("{} ({})", hat.name, hat.asset_id);
+    /// }
+    ///
+    /// println!("Next Cursor: {:?}", next_cursor);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn inventory(
+        &self,
+        user_id: u64,
+        asset_type: AssetType,
+        limit: Limit,
+        cursor: Option<String>,
+    ) -> Result<(Vec<InventoryItem>, Option<String>), RoboatError> {
+        let formatted_url = format!(
+            "{}?sortOrder={}&cursor={}&limit={}",
+            INVENTORY_API
+                .replace("{user_id}", &user_id.to_string())
+                .replace("{asset_type}", &u64::from(asset_type).to_string()),
+            SORT_ORDER,
+            cursor.unwrap_or_default(),
+            limit.to_u64()
+        );
+
+        let mut request_builder = self.reqwest_client.get(&formatted_url);
+
+        // The cookie is optional here.
+        if let Ok(cookie_string) = self.cookie_string() {
+            request_builder = request_builder.header(header::COOKIE, cookie_string);
+        }
+
+        let response = self
+            .send_request(request_builder, "inventory.inventory")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::InventoryResponse>(response)
+            .await?;
+
+        let items = raw
+            .data
+            .into_iter()
+            .map(|x| InventoryItem {
+                uaid: x.user_asset_id,
+                asset_id: x.asset_id,
+                name: x.asset_name,
+                serial_number: x.serial_number,
+                collectible_item_id: x.collectible_item_id,
+                created: x.created.unwrap_or_default(),
+            })
+            .collect();
+
+        Ok((items, raw.next_page_cursor))
+    }
+
+    /// Fetches the bundles owned by a user using
+    /// <https://catalog.roblox.com/v1/users/{user_id}/bundles>.
+    ///
+    /// # Notes
+    /// * Does not require a valid roblosecurity. If the client has one, it is sent so that
+    ///   inventories only visible to the account can be fetched.
+    /// * The inventory of the user must be visible to the client.
+    /// * Bundles are ordered from the oldest obtained to the newest obtained.
+    ///
+    /// # Return Value Notes
+    /// * The first value is a vector of the bundles.
+    /// * The second value is the cursor for the next page of results. If there are no more pages, this is `None`.
+    ///
+    /// # Argument Notes
+    /// * `cursor` should be `None` to fetch the first page.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::catalog::BundleType;
+    /// use roboat::{ClientBuilder, Limit};
+    ///
+    /// const USER_ID: u64 = 1;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// let (bundles, next_cursor) = client.bundles(USER_ID, Limit::Hundred, None).await?;
+    ///
+    /// for bundle in bundles.iter().filter(|x| x.bundle_type == BundleType::AvatarAnimations) {
+    ///     println!("{} ({})", bundle.name, bundle.bundle_id);
+    /// }
+    ///
+    /// println!("Next Cursor: {:?}", next_cursor);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bundles(
+        &self,
+        user_id: u64,
+        limit: Limit,
+        cursor: Option<String>,
+    ) -> Result<(Vec<InventoryBundle>, Option<String>), RoboatError> {
+        let formatted_url = format!(
+            "{}?sortOrder={}&cursor={}&limit={}",
+            BUNDLES_API.replace("{user_id}", &user_id.to_string()),
+            SORT_ORDER,
+            cursor.unwrap_or_default(),
+            limit.to_u64()
+        );
+
+        let mut request_builder = self.reqwest_client.get(&formatted_url);
+
+        // The cookie is optional here.
+        if let Ok(cookie_string) = self.cookie_string() {
+            request_builder = request_builder.header(header::COOKIE, cookie_string);
+        }
+
+        let response = self
+            .send_request(request_builder, "inventory.bundles")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::BundlesResponse>(response)
+            .await?;
+
+        let bundles = raw
+            .data
+            .into_iter()
+            .map(|x| InventoryBundle {
+                bundle_id: x.id,
+                name: x.name,
+                bundle_type: x.bundle_type,
+                creator_id: x.creator.id,
+                creator_name: x.creator.name,
+            })
+            .collect();
+
+        Ok((bundles, raw.next_page_cursor))
+    }
+
+    /// Returns whether the inventory of a user is visible to the client, using
+    /// <https://inventory.roblox.com/v1/users/{user_id}/can-view-inventory>.
+    ///
+    /// # Notes
+    /// * Does not require a valid roblosecurity. If the client has one, it is sent, as some
+    ///   inventories are only visible to friends or to the user themselves.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// const USER_ID: u64 = 1;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// let can_view = client.can_view_inventory(USER_ID).await?;
+    ///
+    /// println!("Inventory Visible: {}", can_view);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn can_view_inventory(&self, user_id: u64) -> Result<bool, RoboatError> {
+        let formatted_url = CAN_VIEW_INVENTORY_API.replace("{user_id}", &user_id.to_string());
+
+        let mut request_builder = self.reqwest_client.get(&formatted_url);
+
+        // The cookie is optional here.
+        if let Ok(cookie_string) = self.cookie_string() {
+            request_builder = request_builder.header(header::COOKIE, cookie_string);
+        }
+
+        let response = self
+            .send_request(request_builder, "inventory.can_view_inventory")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::CanViewInventoryResponse>(response)
+            .await?;
+
+        Ok(raw.can_view)
+    }
+
+    /// Returns whether a user owns an item, using
+    /// <https://inventory.roblox.com/v1/users/{user_id}/items/{item_type}/{item_id}/is-owned>.
+    ///
+    /// # Notes
+    /// * Does not require a valid roblosecurity. If the client has one, it is sent.
+    /// * Works even if the inventory of the user is not visible to the client.
+    ///
+    /// # Argument Notes
+    /// * `item_id` is the asset id if `item_type` is [`ItemType::Asset`], and the bundle id
+    ///   if it is [`ItemType::Bundle`].
+    /// * [`ItemType::Unknown`] is sent as is, so other types Roblox accepts (such as
+    ///   `"GamePass"` or `"Badge"`) can be checked with the id of that item.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::catalog::ItemType;
+    /// use roboat::ClientBuilder;
+    ///
+    /// const USER_ID: u64 = 1;
+    /// const ASSET_ID: u64 = 1365767;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// let owned = client.is_owned(USER_ID, ItemType::Asset, ASSET_ID).await?;
+    ///
+    /// println!("Owned: {}", owned);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn is_owned(
+        &self,
+        user_id: u64,
+        item_type: ItemType,
+        item_id: u64,
+    ) -> Result<bool, RoboatError> {
+        let formatted_url = IS_OWNED_API
+            .replace("{user_id}", &user_id.to_string())
+            .replace("{item_type}", item_type.as_str())
+            .replace("{item_id}", &item_id.to_string());
+
+        let mut request_builder = self.reqwest_client.get(&formatted_url);
+
+        // The cookie is optional here.
+        if let Ok(cookie_string) = self.cookie_string() {
+            request_builder = request_builder.header(header::COOKIE, cookie_string);
+        }
+
+        let response = self
+            .send_request(request_builder, "inventory.is_owned")
+            .await?;
+
+        self.parse_to_raw::<bool>(response).await
+    }
+}
@@ -0,0 +1,77 @@
+use crate::catalog::BundleType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct CollectiblesResponse {
+    pub previous_page_cursor: Option<String>,
+    pub next_page_cursor: Option<String>,
+    pub data: Vec<CollectibleRaw>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct CollectibleRaw {
+    pub user_asset_id: u64,
+    pub serial_number: Option<u64>,
+    pub asset_id: u64,
+    pub name: String,
+    pub recent_average_price: Option<u64>,
+    pub original_price: Option<u64>,
+    pub asset_stock: Option<u64>,
+    pub builders_club_membership_type: Option<String>,
+    pub is_on_hold: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct InventoryResponse {
+    pub previous_page_cursor: Option<String>,
+    pub next_page_cursor: Option<String>,
+    pub data: Vec<InventoryItemRaw>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct InventoryItemRaw {
+    pub user_asset_id: u64,
+    pub asset_id: u64,
+    pub asset_name: String,
+    pub serial_number: Option<u64>,
+    pub collectible_item_id: Option<String>,
+    pub collectible_item_instance_id: Option<String>,
+    pub created: Option<String>,
+    pub updated: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct CanViewInventoryResponse {
+    pub can_view: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BundlesResponse {
+    pub previous_page_cursor: Option<String>,
+    pub next_page_cursor: Option<String>,
+    pub data: Vec<BundleRaw>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BundleRaw {
+    pub id: u64,
+    pub name: String,
+    pub bundle_type: BundleType,
+    pub creator: BundleCreatorRaw,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BundleCreatorRaw {
+    pub id: u64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub creator_type: String,
+}
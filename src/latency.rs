@@ -0,0 +1,141 @@
+//! Tools for time-critical calls, such as purchasing a limited the moment it drops.
+//!
+//! The first request a [`Client`] sends to a domain pays for DNS resolution, the TCP and TLS handshakes,
+//! and (for most mutating endpoints) fetching an x-csrf-token. [`Client::warm_up`] pays for all of them
+//! ahead of time, and [`ClientBuilder::low_latency`](crate::ClientBuilder::low_latency) keeps the opened
+//! connections alive until they are needed.
+
+use crate::{Client, RoboatError};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The domains used by the purchase methods, and by the requests they depend on
+/// (the user id and the x-csrf-token).
+pub const PURCHASE_DOMAINS: [&str; 4] = [
+    "apis.roblox.com",
+    "economy.roblox.com",
+    "users.roblox.com",
+    "auth.roblox.com",
+];
+
+/// The latency of a single domain measured by [`Client::warm_up`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DomainLatency {
+    /// The domain, such as `economy.roblox.com`.
+    pub domain: String,
+    /// The time taken by the first request, which includes opening the connection
+    /// if none was pooled.
+    pub first_request: Duration,
+    /// The time taken by a request sent over the opened connection.
+    pub round_trip: Duration,
+}
+
+/// The result of [`Client::warm_up`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct WarmUpReport {
+    /// The latency of every domain, in the order they were given.
+    pub domains: Vec<DomainLatency>,
+    /// Whether the user id and a valid x-csrf-token were fetched. This only happens if the
+    /// client has a roblosecurity.
+    pub session_fetched: bool,
+}
+
+impl Client {
+    /// Opens a connection to every domain in `domains` and keeps it pooled, measuring the latency of each.
+    /// If the client has a roblosecurity, the user id and a valid x-csrf-token are fetched as well.
+    ///
+    /// [`PURCHASE_DOMAINS`] contains the domains used by the purchase methods.
+    ///
+    /// # Notes
+    /// * Sends two `HEAD` requests to the root of each domain. Any response, including an error status, counts as a success.
+    /// * Domains are warmed up one at a time, so that the measured latencies do not affect each other.
+    /// * Without [`ClientBuilder::low_latency`](crate::ClientBuilder::low_latency), idle connections are closed by
+    ///   the pool after 90 seconds, so this should be called shortly before the time-critical calls.
+    ///
+    /// # Errors
+    /// * [`RoboatError::ReqwestError`] - If a domain cannot be reached.
+    /// * All errors under [Standard Errors](Client#standard-errors) and [Auth Required Errors](Client#auth-required-errors),
+    ///   if the client has a roblosecurity.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::latency::PURCHASE_DOMAINS;
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new()
+    ///     .roblosecurity(ROBLOSECURITY.to_string())
+    ///     .low_latency()
+    ///     .build();
+    ///
+    /// let report = client.warm_up(&PURCHASE_DOMAINS).await?;
+    ///
+    /// for domain in report.domains {
+    ///     println!(
+    ///         "{}: {:?} cold, {:?} warm",
+    ///         domain.domain, domain.first_request, domain.round_trip
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_up(&self, domains: &[&str]) -> Result<WarmUpReport, RoboatError> {
+        let mut report = WarmUpReport::default();
+
+        for domain in domains {
+            let first_request = self.round_trip_time(domain).await?;
+            let round_trip = self.round_trip_time(domain).await?;
+
+            report.domains.push(DomainLatency {
+                domain: domain.to_string(),
+                first_request,
+                round_trip,
+            });
+        }
+
+        if self.cookie_string().is_ok() {
+            self.user_id().await?;
+            self.force_refresh_xcsrf().await?;
+            report.session_fetched = true;
+        }
+
+        Ok(report)
+    }
+
+    /// Measures the time taken by a `HEAD` request to the root of `domain`, from sending
+    /// it to receiving the response headers.
+    ///
+    /// # Notes
+    /// * Includes the time taken to open a connection if none is pooled. Call [`Client::warm_up`]
+    ///   first to only measure the round trip.
+    /// * Any response, including an error status, counts as a success.
+    ///
+    /// # Errors
+    /// * [`RoboatError::ReqwestError`] - If the domain cannot be reached.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().build();
+    ///
+    /// client.warm_up(&["economy.roblox.com"]).await?;
+    /// let rtt = client.round_trip_time("economy.roblox.com").await?;
+    ///
+    /// println!("Round trip: {:?}", rtt);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn round_trip_time(&self, domain: &str) -> Result<Duration, RoboatError> {
+        let request_builder = self.reqwest_client.head(format!("https://{}/", domain));
+
+        let (_, elapsed) = self.send_request_unvalidated(request_builder).await?;
+
+        Ok(elapsed)
+    }
+}
@@ -13,12 +13,15 @@
 //!   - Force Refresh Xcsrf - [`Client::force_refresh_xcsrf`]
 //! * BEDEV2 API
 //!   - Fetch Non-Tradable Limited Details - [`Client::non_tradable_limited_details`]
+//!   - Fetch Non-Tradable Limited Details Bytes - [`Client::non_tradable_limited_details_bytes`]
 //!   - Fetch Collectible Product ID - [`Client::collectible_product_id`]
 //!   - Fetch Collectible Product ID Bulk - [`Client::collectible_product_id_bulk`]
 //!   - Fetch Collectible Creator ID - [`Client::collectible_creator_id`]
 //!   - Purchase Non-Tradable Limited - [`Client::purchase_non_tradable_limited`]
+//!   - Purchase Non-Tradable Limited With Idempotency Key - [`Client::purchase_non_tradable_limited_with_idempotency_key`]
 //! * Catalog API
 //!   - Fetch Item Details - [`Client::item_details`]
+//!   - Fetch Item Details Bytes - [`Client::item_details_bytes`]
 //!   - Fetch Product ID - [`Client::product_id`]  
 //!   - Fetch Product ID Bulk - [`Client::product_id_bulk`]
 //!   - Fetch Collectible Item ID - [`Client::collectible_item_id`]
@@ -29,6 +32,7 @@
 //! * Economy API
 //!   - Fetch Robux Balance - [`Client::robux`]
 //!   - Fetch Resellers - [`Client::resellers`]
+//!   - Fetch Resellers Bytes - [`Client::resellers_bytes`]
 //!   - Fetch User Sales - [`Client::user_sales`]
 //!   - Put Limited On Sale - [`Client::put_limited_on_sale`]
 //!   - Take Limited Off Sale - [`Client::take_limited_off_sale`]
@@ -37,6 +41,12 @@
 //!   - Fetch Group Roles - [`Client::group_roles`]
 //!   - Fetch Group Role Members - [`Client::group_role_members`]
 //!   - Set Group Member Role - [`Client::set_group_member_role`]
+//! * Inventory API
+//!   - Fetch Collectibles - [`Client::collectibles`]
+//!   - Fetch Inventory - [`Client::inventory`]
+//!   - Fetch Bundles - [`Client::bundles`]
+//!   - Fetch Can View Inventory - [`Client::can_view_inventory`]
+//!   - Fetch Is Owned - [`Client::is_owned`]
 //! * Presence API
 //!   - Register Presence - [`Client::register_presence`]
 //!   - Fetch Users Presence - [`Client::fetch_users_presence`]
@@ -48,10 +58,16 @@
 //! * Trades API
 //!   - Accept Trade - [`Client::accept_trade`]
 //!   - Decline Trade - [`Client::decline_trade`]
+//!   - Decline Trades Where - [`Client::decline_trades_where`]
 //!   - Send Trade - [`Client::send_trade`]
+//!   - Counter Trade - [`Client::counter_trade`]
+//!   - Counter Trade With Partner - [`Client::counter_trade_with_partner`]
 //!   - Fetch Trade Details - [`Client::trade_details`]
 //!   - Fetch Trades List - [`Client::trades`]
 //!   - Fetch Trade Count - [`Client::trade_count`]
+//!   - Fetch Can Trade With - [`Client::can_trade_with`]
+//!   - Fetch Trade Metadata - [`Client::trade_metadata`]
+//!   - Fetch Trade Privacy - [`Client::trade_privacy`]
 //! * Users API
 //!   - Fetch User ID - [`Client::user_id`]
 //!   - Fetch Username - [`Client::username`]
@@ -77,9 +93,33 @@
 //!   - Get Client Version - [`Client::client_version`]
 //!   - Get Client Version for Channel - [`Client::client_version_for_channel`]
 //!   - Get User Channel - [`Client::user_channel`]
+//! * Any Endpoint
+//!   - Raw Request - [`Client::request`]
+//! * Client Utilities
+//!   - Response Metadata - [`Client::with_meta`]
+//!   - Audit Journal - [`audit::AuditSink`]
+//!   - Config From Environment - [`ClientBuilder::from_env`]
+//!   - Config From File - [`ClientBuilder::from_config`]
+//!   - Export State - [`Client::export_state`]
+//!   - Restore State - [`ClientBuilder::restore_state`]
+//!   - Warm Up Connections - [`Client::warm_up`]
+//!   - Low Latency Preset - [`ClientBuilder::low_latency`]
+//!   - Schema Drift Detection - [`schema::SchemaDriftHandler`]
+//! * Trade Tools
+//!   - Trade Analysis - [`trades::analysis::TradeAnalyzer`]
+//!   - Trade Responder - [`trades::responder::TradeResponder`]
+//!   - Trade Export - [`trades::export::TradeExporter`]
+//!   - Trade Proposals - [`trades::proposals::TradeProposer`]
+//!   - Trade Watcher - [`trades::watcher::TradeWatcher`]
 //! * UNDER CONSTRUCTION
 //!   - Upload Classic Clothing To Group - [`Client::upload_classic_clothing_to_group`]
 //!
+//! # Features
+//! * `tracing` - Emits a [`tracing`](https://docs.rs/tracing) span for every request, carrying the endpoint name,
+//!   method, status, latency, retry count, and the user id of the account (if cached). Cookies and x-csrf-tokens are redacted.
+//!
+//! Per-request metrics can also be exported using a [`metrics::MetricsSink`], regardless of enabled features.
+//!
 //! # Quick Start Examples
 //!
 //! ## Example 1 - Purchase Free UGC Limited
@@ -222,10 +262,13 @@ use serde::{Deserialize, Serialize};
 pub use bedev2::PurchaseNonTradableLimitedError;
 pub use client::{Client, ClientBuilder};
 pub use economy::PurchaseTradableLimitedError;
+pub use spending::SpendingPolicyViolation;
 
 ///
 /// A module for endpoints prefixed with <https://assetdelivery.roblox.com/*>
 pub mod assetdelivery;
+/// A module related to the audit journal written by the [`Client`].
+pub mod audit;
 /// A module for endpoints prefixed with <https://auth.roblox.com/*>.
 mod auth;
 /// A module for endpoints prefixed with <https://apis.roblox.com/*>.
@@ -238,12 +281,23 @@ mod chat;
 mod client;
 /// A module for endpoints prefixed with <https://clientsettings.roblox.com/*>.
 pub mod clientsettings;
+/// A module related to configuring a [`Client`] from the environment or a config file.
+pub mod config;
 /// A module for endpoints prefixed with <https://economy.roblox.com/*>.
 pub mod economy;
+mod enums;
+/// A module related to writing checkpoints and logs to files.
+mod files;
 /// A module for endpoints prefixed with <https://friends.roblox.com/*>.
 pub mod friends;
 /// A module for endpoints prefixed with <https://groups.roblox.com/*>.
 pub mod groups;
+/// A module related to warming up connections and measuring latency for time-critical calls.
+pub mod latency;
+/// A module related to the metadata of responses received from Roblox.
+pub mod meta;
+/// A module related to the metrics emitted by the [`Client`].
+pub mod metrics;
 
 /// A module for endpoints prefixed with <https://games.roblox.com/*>
 pub mod games;
@@ -251,14 +305,30 @@ pub mod games;
 /// A module for endpoints prefixed with <https://www.roblox.com/ide/*>
 // This is used for private APIs like ide/uploadnewanimation and ide/places/createV2
 pub mod ide;
+/// A module for endpoints prefixed with <https://inventory.roblox.com/*>.
+pub mod inventory;
 /// A module for endpoints prefixed with <https://presence.roblox.com/*>.
 pub mod presence;
 /// A module for endpoints prefixed with <https://privatemessages.roblox.com/*>.
 pub mod private_messages;
+/// A module related to sending requests to endpoints not covered by this crate.
+pub mod raw;
+/// A module related to detecting changes to the responses returned by Roblox.
+pub mod schema;
+/// A module related to saving and restoring the state of a [`Client`].
+pub mod session;
+/// A module related to limiting the purchases made by the [`Client`].
+pub mod spending;
+/// A module related to testing code built on this crate without live Roblox access.
+pub mod testing;
 /// A module for endpoints prefixed with <https://thumbnails.roblox.com/*>.
 pub mod thumbnails;
+/// A module related to parsing the timestamps returned by Roblox.
+mod timestamps;
 /// A module for endpoints prefixed with <https://trades.roblox.com/*>.
 pub mod trades;
+/// A module related to sending requests.
+mod transport;
 /// A module for endpoints prefixed with <https://users.roblox.com/*>.
 pub mod users;
 /// A module related to validating requests.
@@ -391,6 +461,33 @@ pub enum RoboatError {
     /// Used when a file system path passed to a method is invalid.
     #[error("Invalid Path {0}")]
     InvalidPath(String),
+    /// Used when a request sent by a client using a [`testing::Replayer`] does not
+    /// match any interaction in the cassette. The string contains a diff of the request.
+    #[error("Cassette Mismatch: {0}")]
+    CassetteMismatch(String),
+    /// Used when a mutating method is called on a client with [`ClientBuilder::dry_run`] enabled.
+    /// Contains the request that would have been sent, with the cookie and x-csrf-token redacted.
+    #[error("Dry Run. {} {} Was Not Sent.", .0.method, .0.url)]
+    DryRun(Box<testing::RecordedRequest>),
+    /// Used when a [`ClientBuilder`] is configured with invalid settings (such as a roblosecurity with
+    /// invalid characters), or when a config file or environment variable cannot be parsed.
+    #[error("Invalid Config: {0}")]
+    InvalidConfig(String),
+    /// Used when a purchase is blocked by the [`spending::SpendingPolicy`] of the client.
+    #[error("Spending Policy Violation: {0}")]
+    SpendingPolicyViolation(SpendingPolicyViolation),
+    /// Used when a response does not match the type it is parsed into, or cannot be parsed,
+    /// and [`ClientBuilder::strict_schema`] is enabled.
+    #[error("Schema Drift In {}", .0.response_type)]
+    SchemaDrift(Box<schema::SchemaDriftReport>),
+    /// Used when [`Client::send_trade`] or [`Client::counter_trade`] is given an offer
+    /// that breaks the limits of a trade. The trade is not sent.
+    #[error("Invalid Trade Offer: {0}")]
+    InvalidTradeOffer(trades::TradeOfferViolation),
+    /// Used when a request built with [`Client::request`] cannot be copied to be sent (or repeated
+    /// when the x-csrf-token is invalid), such as when its body is a stream. The request is not sent.
+    #[error("Request Not Cloneable")]
+    RequestNotCloneable,
 }
 
 /// The type of the challenge required to complete a request.
@@ -401,15 +498,15 @@ pub enum RoboatError {
 pub enum ChallengeType {
     #[default]
     TwoStep,
+    /// A challenge type not known by this crate, such as a captcha.
+    Unknown(String),
 }
 
-impl TryFrom<String> for ChallengeType {
-    type Error = RoboatError;
-
-    fn try_from(raw: String) -> Result<Self, Self::Error> {
+impl From<String> for ChallengeType {
+    fn from(raw: String) -> Self {
         match raw.as_str() {
-            "twostepverification" => Ok(ChallengeType::TwoStep),
-            _ => Err(RoboatError::MalformedResponse),
+            "twostepverification" => ChallengeType::TwoStep,
+            _ => ChallengeType::Unknown(raw),
         }
     }
 }
@@ -429,6 +526,42 @@ pub struct ChallengeInfo {
     pub challenge_type: ChallengeType,
 }
 
+/// The item bought by a purchase, identified the same way as in the purchase method.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PurchasedItem {
+    /// A tradable (legacy) limited bought using [`Client::purchase_tradable_limited`].
+    TradableLimited {
+        /// The product id of the limited.
+        product_id: u64,
+    },
+    /// A non-tradable limited bought using [`Client::purchase_non_tradable_limited`].
+    NonTradableLimited {
+        /// The collectible item id of the limited.
+        collectible_item_id: String,
+        /// The collectible product id of the limited.
+        collectible_product_id: String,
+    },
+}
+
+/// The receipt of a successful purchase.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PurchaseReceipt {
+    /// The item bought.
+    pub item: PurchasedItem,
+    /// The price paid for the item.
+    pub price: u64,
+    /// The user id of the seller.
+    pub seller_id: u64,
+    /// The unique asset id of the copy bought. Only exists for tradable limiteds.
+    pub uaid: Option<u64>,
+    /// The instance id of the copy bought. Only exists for non-tradable limiteds, and only if Roblox returns it.
+    pub collectible_item_instance_id: Option<String>,
+    /// The idempotency key the purchase was sent with. Only exists for non-tradable limiteds.
+    pub idempotency_key: Option<String>,
+    /// The raw purchase result message returned by Roblox (e.g. `Purchase transaction success.`).
+    pub purchase_result: String,
+}
+
 /// The universal struct for a Roblox user in this crate.
 #[allow(missing_docs)]
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
@@ -0,0 +1,214 @@
+use crate::{Client, RoboatError};
+use reqwest::header::{self, HeaderMap, HeaderName};
+use std::cell::RefCell;
+use std::future::Future;
+use std::time::Duration;
+
+/// Headers that are always kept in [`ResponseMeta::headers`].
+const SELECTED_HEADERS: [&str; 12] = [
+    "roblox-machine-id",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    "retry-after",
+    "cache-control",
+    "etag",
+    "age",
+    "expires",
+    "last-modified",
+    "date",
+    "content-type",
+];
+
+/// Substrings of header names that are kept in [`ResponseMeta::headers`], used for request
+/// and correlation ids as their names vary between Roblox APIs.
+const SELECTED_HEADER_SUBSTRINGS: [&str; 3] = ["correlation", "request-id", "trace-id"];
+
+tokio::task_local! {
+    /// The metadata of the last response received inside [`Client::with_meta`].
+    static RESPONSE_META: RefCell<Option<ResponseMeta>>;
+}
+
+/// The result of a method, along with the metadata of the response it was parsed from.
+///
+/// Returned by [`Client::with_meta`] as a `Response<Result<T, RoboatError>>`, so that the metadata is
+/// kept when the method fails (for example, to read the `retry-after` header of a 429).
+#[derive(Clone, Debug)]
+pub struct Response<T> {
+    /// The result of the method.
+    pub data: T,
+    /// The metadata of the last response received by the method.
+    ///
+    /// This is `None` if the method did not send a request, such as when [`Client::user_id`]
+    /// returns a cached value, or when the request could not be sent.
+    pub meta: Option<ResponseMeta>,
+}
+
+impl<T, E> Response<Result<T, E>> {
+    /// Turns a response holding a result into a result holding a response, dropping the
+    /// metadata if the method failed.
+    pub fn transpose(self) -> Result<Response<T>, E> {
+        Ok(Response {
+            data: self.data?,
+            meta: self.meta,
+        })
+    }
+}
+
+/// The metadata of a response received from Roblox.
+#[derive(Clone, Debug)]
+pub struct ResponseMeta {
+    /// The status code of the response.
+    pub status: u16,
+    /// A selection of the headers of the response, such as `roblox-machine-id`,
+    /// correlation ids, rate limit headers, and caching headers.
+    pub headers: HeaderMap,
+    /// The time taken from sending the request to receiving the response headers.
+    pub elapsed: Duration,
+    /// The final url of the response, after redirects.
+    pub url: String,
+}
+
+impl ResponseMeta {
+    /// Returns the value of the `roblox-machine-id` header, which identifies the server
+    /// that handled the request.
+    pub fn machine_id(&self) -> Option<&str> {
+        self.header("roblox-machine-id")
+    }
+
+    /// Returns the value of the first correlation or request id header, if any.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(name, _)| {
+                SELECTED_HEADER_SUBSTRINGS
+                    .iter()
+                    .any(|x| name.as_str().contains(x))
+            })
+            .and_then(|(_, value)| value.to_str().ok())
+    }
+
+    /// Returns the value of the `x-ratelimit-limit` header, if it exists and is a number.
+    pub fn rate_limit_limit(&self) -> Option<u64> {
+        self.header("x-ratelimit-limit")?.parse().ok()
+    }
+
+    /// Returns the value of the `x-ratelimit-remaining` header, if it exists and is a number.
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        self.header("x-ratelimit-remaining")?.parse().ok()
+    }
+
+    /// Returns the value of the `x-ratelimit-reset` header (in seconds), if it exists and is a number.
+    pub fn rate_limit_reset(&self) -> Option<Duration> {
+        self.header("x-ratelimit-reset")?
+            .parse()
+            .ok()
+            .map(Duration::from_secs)
+    }
+
+    /// Returns the value of the `retry-after` header (in seconds), if it exists and is a number.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.headers
+            .get(header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+            .map(Duration::from_secs)
+    }
+
+    /// Returns the value of a header as a string, if it was kept and is valid ascii.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    fn new(response: &reqwest::Response, elapsed: Duration) -> Self {
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| is_selected_header(name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        Self {
+            status: response.status().as_u16(),
+            headers,
+            elapsed,
+            url: response.url().to_string(),
+        }
+    }
+}
+
+impl Client {
+    /// Runs a method of a client and returns its result along with the metadata of the
+    /// last response it received (status, selected headers, elapsed time, and final url).
+    ///
+    /// # Notes
+    /// * Any method (or group of methods) of any [`Client`] can be passed in.
+    /// * If the method sends more than one request (for example, when the x-csrf-token is refreshed),
+    ///   the metadata of the last one is returned.
+    /// * The metadata is returned even if the method fails, as long as a response was received.
+    ///   Use [`Response::transpose`] to only keep it on success.
+    /// * Does not add any overhead to methods called outside of this.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use roboat::{Client, ClientBuilder, RoboatError};
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let response = Client::with_meta(client.robux()).await;
+    ///
+    /// match response.data {
+    ///     Ok(robux) => println!("Robux: {}", robux),
+    ///     Err(RoboatError::TooManyRequests) => {
+    ///         let retry_after = response.meta.and_then(|x| x.retry_after());
+    ///         println!("Rate limited, retry after {:?}", retry_after);
+    ///     }
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    ///
+    /// // Only keeps the metadata if the method succeeds.
+    /// let response = Client::with_meta(client.robux()).await.transpose()?;
+    ///
+    /// if let Some(meta) = response.meta {
+    ///     println!("Status: {}", meta.status);
+    ///     println!("Machine Id: {:?}", meta.machine_id());
+    ///     println!("Elapsed: {:?}", meta.elapsed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_meta<T, F>(future: F) -> Response<Result<T, RoboatError>>
+    where
+        F: Future<Output = Result<T, RoboatError>>,
+    {
+        RESPONSE_META
+            .scope(RefCell::new(None), async move {
+                let data = future.await;
+                let meta = RESPONSE_META.with(|x| x.borrow_mut().take());
+
+                Response { data, meta }
+            })
+            .await
+    }
+}
+
+/// Records the metadata of a response if it was received inside [`Client::with_meta`].
+pub(crate) fn record_response_meta(response: &reqwest::Response, elapsed: Duration) {
+    let _ = RESPONSE_META.try_with(|x| {
+        *x.borrow_mut() = Some(ResponseMeta::new(response, elapsed));
+    });
+}
+
+fn is_selected_header(name: &HeaderName) -> bool {
+    SELECTED_HEADERS.contains(&name.as_str())
+        || SELECTED_HEADER_SUBSTRINGS
+            .iter()
+            .any(|x| name.as_str().contains(x))
+}
@@ -0,0 +1,58 @@
+//! Per-request metrics emitted by a [`Client`](crate::Client).
+//!
+//! A [`MetricsSink`] can be attached using [`ClientBuilder::metrics_sink`](crate::ClientBuilder::metrics_sink).
+//! Every request sent by the client then reports the following metrics to the sink:
+//!
+//! | Name | Kind | Labels |
+//! |------|------|--------|
+//! | [`REQUESTS_TOTAL`] | Counter | `endpoint`, `method`, `status` |
+//! | [`REQUEST_ERRORS_TOTAL`] | Counter | `endpoint`, `method`, `status` |
+//! | [`REQUEST_RETRIES_TOTAL`] | Counter | `endpoint`, `method` |
+//! | [`REQUEST_DURATION_SECONDS`] | Histogram | `endpoint`, `method`, `status` |
+//!
+//! The `status` label is the status code returned by Roblox, or `none` if no response was received.
+
+/// Counter incremented once for every request sent.
+pub const REQUESTS_TOTAL: &str = "roboat_requests_total";
+/// Counter incremented once for every request that did not return a status code of 200.
+pub const REQUEST_ERRORS_TOTAL: &str = "roboat_request_errors_total";
+/// Counter incremented once for every request that is a retry of a previous request
+/// (for example, after the x-csrf-token was rotated).
+pub const REQUEST_RETRIES_TOTAL: &str = "roboat_request_retries_total";
+/// Histogram of the time taken to receive a response, in seconds.
+pub const REQUEST_DURATION_SECONDS: &str = "roboat_request_duration_seconds";
+
+/// A label attached to a metric, in the form of `(key, value)`.
+pub type MetricLabel = (&'static str, String);
+
+/// A receiver for the metrics emitted by a [`Client`](crate::Client).
+///
+/// Implement this to export request metrics to your own stack (Prometheus, StatsD, etc.).
+///
+/// # Example
+/// ```
+/// use roboat::metrics::{MetricLabel, MetricsSink};
+/// use roboat::ClientBuilder;
+///
+/// #[derive(Debug)]
+/// struct PrintSink;
+///
+/// impl MetricsSink for PrintSink {
+///     fn increment_counter(&self, name: &'static str, labels: &[MetricLabel]) {
+///         println!("{} {:?}", name, labels);
+///     }
+///
+///     fn record_histogram(&self, name: &'static str, value: f64, labels: &[MetricLabel]) {
+///         println!("{} {} {:?}", name, value, labels);
+///     }
+/// }
+///
+/// let client = ClientBuilder::new().metrics_sink(PrintSink).build();
+/// ```
+pub trait MetricsSink: std::fmt::Debug + Send + Sync {
+    /// Increments the counter `name` by one.
+    fn increment_counter(&self, name: &'static str, labels: &[MetricLabel]);
+
+    /// Records `value` into the histogram `name`.
+    fn record_histogram(&self, name: &'static str, value: f64, labels: &[MetricLabel]);
+}
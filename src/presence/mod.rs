@@ -15,19 +15,19 @@ pub enum PresenceType {
     InGame,
     InStudio,
     Invisible,
+    /// A presence type id not known by this crate.
+    Unknown(u8),
 }
 
-impl TryFrom<u8> for PresenceType {
-    type Error = RoboatError;
-
-    fn try_from(v: u8) -> Result<Self, Self::Error> {
+impl From<u8> for PresenceType {
+    fn from(v: u8) -> Self {
         match v {
-            0 => Ok(Self::Offline),
-            1 => Ok(Self::Online),
-            2 => Ok(Self::InGame),
-            3 => Ok(Self::InStudio),
-            4 => Ok(Self::Invisible),
-            _ => Err(RoboatError::MalformedResponse),
+            0 => Self::Offline,
+            1 => Self::Online,
+            2 => Self::InGame,
+            3 => Self::InStudio,
+            4 => Self::Invisible,
+            x => Self::Unknown(x),
         }
     }
 }
@@ -74,7 +74,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.register_presence_internal().await
+                    Self::retry(self.register_presence_internal()).await
                 }
                 _ => Err(e),
             },
@@ -109,7 +109,7 @@ impl Client {
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
     /// let users = vec![1, 35958674918];
-    /// match client.fetch_user_presence(users).await {
+    /// match client.fetch_users_presence(users).await {
     ///    Ok(user_statuses) => println!("Successfully registered presence: {:?}", user_statuses),
     ///    Err(e) => println!("Error: {}", e),
     /// }
@@ -143,16 +143,16 @@ mod internal {
                 "location": "Home",
             });
 
-            let request_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(REGISTER_PRESENCE_API)
                 .header(header::COOKIE, cookie)
                 .header(XCSRF_HEADER, self.xcsrf().await)
-                .json(&json)
-                .send()
-                .await;
+                .json(&json);
 
-            let _ = Self::validate_request_result(request_result).await?;
+            let _ = self
+                .send_mutating_request(request_builder, "presence.register_presence")
+                .await?;
 
             // We don't care about the response, just that it's a status code 200.
             Ok(())
@@ -177,10 +177,10 @@ mod internal {
                 })
                 .unwrap_or_else(|_| self.reqwest_client.post(USER_PRESENCE_API).json(&json));
 
-            let request_result = request.send().await;
-
-            let response = Self::validate_request_result(request_result).await?;
-            let presense_json = Self::parse_to_raw::<UserPresenceResponse>(response).await?;
+            let response = self
+                .send_request(request, "presence.fetch_users_presence")
+                .await?;
+            let presense_json = self.parse_to_raw::<UserPresenceResponse>(response).await?;
             Ok(presense_json)
         }
     }
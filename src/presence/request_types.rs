@@ -29,5 +29,5 @@ where
     D: Deserializer<'de>,
 {
     let value = u8::deserialize(deserializer).unwrap_or(0);
-    Ok(PresenceType::try_from(value).unwrap_or(PresenceType::Offline))
+    Ok(PresenceType::from(value))
 }
@@ -115,15 +115,17 @@ impl Client {
             .replace("{message_tab_type}", message_tab_type.to_string().as_str())
             .replace("{page_number}", page.to_string().as_str());
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(&url)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::MessagesResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "private_messages.messages")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::MessagesResponse>(response)
+            .await?;
 
         let messages = raw
             .collection
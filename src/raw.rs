@@ -0,0 +1,234 @@
+use crate::{Client, RoboatError, XCSRF_HEADER};
+use bytes::Bytes;
+use reqwest::header::{self, HeaderName, HeaderValue};
+use reqwest::multipart::Form;
+use reqwest::{Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// The endpoint label used for metrics and spans of raw requests.
+const RAW_REQUEST_ENDPOINT: &str = "raw.request";
+
+/// A builder for a request to an endpoint not covered by this crate, created with [`Client::request`].
+///
+/// The request goes through the same pipeline as the built-in methods, meaning that:
+/// * The roblosecurity is attached (if set).
+/// * The x-csrf-token is attached, and the request is repeated once if it is invalid.
+/// * Errors are mapped to [`RoboatError`] the same way, including [`RoboatError::ChallengeRequired`].
+/// * Metrics, spans, and any interceptor set on the [`ClientBuilder`](crate::ClientBuilder) apply.
+///
+/// Send the request using [`RawRequestBuilder::send`], [`RawRequestBuilder::send_json`],
+/// or [`RawRequestBuilder::send_bytes`].
+pub struct RawRequestBuilder<'a> {
+    client: &'a Client,
+    request_builder: RequestBuilder,
+    multipart: Option<Arc<dyn Fn() -> Form + Send + Sync>>,
+    xcsrf: bool,
+    mutating: bool,
+}
+
+impl Client {
+    /// Creates a [`RawRequestBuilder`] for an endpoint not covered by this crate.
+    ///
+    /// # Notes
+    /// * The roblosecurity is sent if it is set, but is not required.
+    /// * Sends the x-csrf-token for every method other than `GET` and `HEAD`, which can be changed using
+    ///   [`RawRequestBuilder::xcsrf`].
+    /// * Treats every method other than `GET` and `HEAD` as mutating, which can be changed using
+    ///   [`RawRequestBuilder::mutating`].
+    /// * Will repeat once if the x-csrf-token is invalid.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    /// * [`RoboatError::RequestNotCloneable`] - If the request cannot be copied to be sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    /// use reqwest::Method;
+    /// use serde::Deserialize;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// #[derive(Deserialize)]
+    /// struct CountResponse {
+    ///     count: u64,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let response: CountResponse = client
+    ///     .request(Method::GET, "https://friends.roblox.com/v1/user/friend-requests/count")
+    ///     .send_json()
+    ///     .await?;
+    ///
+    /// println!("Pending friend requests: {}", response.count);
+    ///
+    /// client
+    ///     .request(Method::POST, "https://friends.roblox.com/v1/users/1/request-friendship")
+    ///     .json(&serde_json::json!({}))
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn request(&self, method: Method, url: impl AsRef<str>) -> RawRequestBuilder<'_> {
+        // Requests with any method other than `GET` and `HEAD` are treated as mutating.
+        let mutating = method != Method::GET && method != Method::HEAD;
+
+        RawRequestBuilder {
+            client: self,
+            request_builder: self.reqwest_client.request(method, url.as_ref()),
+            multipart: None,
+            xcsrf: mutating,
+            mutating,
+        }
+    }
+}
+
+impl RawRequestBuilder<'_> {
+    /// Adds a header to the request.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.request_builder = self.request_builder.header(key, value);
+        self
+    }
+
+    /// Appends query parameters to the url of the request.
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.request_builder = self.request_builder.query(query);
+        self
+    }
+
+    /// Sets a json body, along with the `Content-Type: application/json` header.
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        self.request_builder = self.request_builder.json(json);
+        self
+    }
+
+    /// Sets a raw body. The `Content-Type` header can be set using [`RawRequestBuilder::header`].
+    pub fn bytes(mut self, bytes: impl Into<Bytes>) -> Self {
+        self.request_builder = self.request_builder.body(bytes.into());
+        self
+    }
+
+    /// Sets a multipart body.
+    ///
+    /// As a [`Form`] cannot be cloned, this takes a function that creates the form so that
+    /// the request can be repeated when the x-csrf-token is invalid.
+    ///
+    /// As a [`Form`] cannot be read back either, its parts are not included in the
+    /// [`RecordedRequest`](crate::testing::RecordedRequest) of a dry run or audit entry.
+    pub fn multipart<F>(mut self, form: F) -> Self
+    where
+        F: Fn() -> Form + Send + Sync + 'static,
+    {
+        self.multipart = Some(Arc::new(form));
+        self
+    }
+
+    /// Sets whether the x-csrf-token is sent with the request.
+    pub fn xcsrf(mut self, xcsrf: bool) -> Self {
+        self.xcsrf = xcsrf;
+        self
+    }
+
+    /// Sets whether the request changes state on Roblox.
+    ///
+    /// Mutating requests are not sent in dry run mode, are never repeated by the
+    /// [`RetryPolicy`](crate::config::RetryPolicy), and are written to the
+    /// [`AuditSink`](crate::audit::AuditSink) if one is set. Set this to `false` for
+    /// endpoints that only read data but use another method, such as bulk lookups sent with `POST`.
+    pub fn mutating(mut self, mutating: bool) -> Self {
+        self.mutating = mutating;
+        self
+    }
+
+    /// Sends the request, discarding the response body.
+    pub async fn send(self) -> Result<(), RoboatError> {
+        let _ = self.send_response().await?;
+        Ok(())
+    }
+
+    /// Sends the request and deserializes the json response into `T`.
+    ///
+    /// Returns [`RoboatError::MalformedResponse`] if the response cannot be deserialized into `T`.
+    pub async fn send_json<T: DeserializeOwned>(self) -> Result<T, RoboatError> {
+        let response = self.send_response().await?;
+        self.client.parse_to_raw::<T>(response).await
+    }
+
+    /// Sends the request and returns the raw response body.
+    pub async fn send_bytes(self) -> Result<Bytes, RoboatError> {
+        let response = self.send_response().await?;
+        response.bytes().await.map_err(RoboatError::ReqwestError)
+    }
+
+    async fn send_response(&self) -> Result<Response, RoboatError> {
+        match self.send_response_internal().await {
+            Ok(x) => Ok(x),
+            Err(e) => match e {
+                RoboatError::InvalidXcsrf(new_xcsrf) if self.xcsrf => {
+                    self.client.set_xcsrf(new_xcsrf).await;
+
+                    Client::retry(self.send_response_internal()).await
+                }
+                _ => Err(e),
+            },
+        }
+    }
+
+    async fn send_response_internal(&self) -> Result<Response, RoboatError> {
+        // Bodies set through this builder are never streams, so this should not fail.
+        let mut request_builder = self
+            .request_builder
+            .try_clone()
+            .ok_or(RoboatError::RequestNotCloneable)?;
+
+        if let Ok(cookie) = self.client.cookie_string() {
+            request_builder = request_builder.header(header::COOKIE, cookie);
+        }
+
+        if self.xcsrf {
+            request_builder = request_builder.header(XCSRF_HEADER, self.client.xcsrf().await);
+        }
+
+        if let Some(form) = &self.multipart {
+            request_builder = request_builder.multipart(form());
+        }
+
+        match self.mutating {
+            true => {
+                self.client
+                    .send_mutating_request(request_builder, RAW_REQUEST_ENDPOINT)
+                    .await
+            }
+            false => {
+                self.client
+                    .send_request(request_builder, RAW_REQUEST_ENDPOINT)
+                    .await
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for RawRequestBuilder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawRequestBuilder")
+            .field("request_builder", &self.request_builder)
+            .field("multipart", &self.multipart.is_some())
+            .field("xcsrf", &self.xcsrf)
+            .field("mutating", &self.mutating)
+            .finish()
+    }
+}
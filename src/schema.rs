@@ -0,0 +1,619 @@
+//! Detection of changes to the shape of the responses returned by Roblox (schema drift).
+//!
+//! Roblox changes its responses without notice. Fields are added, optional fields stop being
+//! sent, and enums gain new values. By default, unknown fields are ignored and anything that does
+//! not fit the expected shape becomes a bare [`RoboatError::MalformedResponse`].
+//!
+//! Once a [`SchemaDriftHandler`] is attached using [`ClientBuilder::schema_drift_handler`](crate::ClientBuilder::schema_drift_handler),
+//! every response is compared to the type it is parsed into, and a [`SchemaDriftReport`] is sent to the handler
+//! for every response that does not match it exactly. With [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema),
+//! responses with unknown fields or enum values are rejected with [`RoboatError::SchemaDrift`] instead, and responses that
+//! cannot be parsed return [`RoboatError::SchemaDrift`] rather than [`RoboatError::MalformedResponse`] so that the cause is known.
+//! Responses to requests that change state on Roblox (such as purchases and trades) are only reported, never rejected,
+//! as the change was already made.
+//!
+//! # Notes
+//! * Responses are decoded into a [`serde_json::Value`] first while drift is detected, which is slower.
+//! * Fields that are always absent from some responses (such as the fields of bundles in
+//!   [`Client::item_details`](crate::Client::item_details)) are reported as missing for every such response.
+
+use crate::{Client, RoboatError};
+use serde::de::value::StrDeserializer;
+use serde::de::{DeserializeSeed, Error as _, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// The differences between a response and the type it is parsed into.
+///
+/// Paths are written like `data[3].seller.name`. The path of the top level of the response is empty.
+#[derive(Clone, Debug, PartialEq, Default, Serialize)]
+pub struct SchemaDriftReport {
+    /// The name of the type the response is parsed into.
+    pub response_type: &'static str,
+    /// The url of the request, if known.
+    pub url: Option<String>,
+    /// Fields of the response that are not part of the type, keyed by path.
+    pub unknown_fields: BTreeMap<String, Value>,
+    /// Paths of fields of the type that are not part of the response.
+    pub missing_fields: Vec<String>,
+    /// Values of enums that are not known by this crate.
+    pub unexpected_enum_values: Vec<UnexpectedEnumValue>,
+    /// The error that prevented the response from being parsed, prefixed with the path it occurred at (if any).
+    pub error: Option<String>,
+}
+
+/// A value of an enum that is not known by this crate, such as a new [`TradeStatus`](crate::trades::TradeStatus).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct UnexpectedEnumValue {
+    /// The path of the value in the response.
+    pub path: String,
+    /// The name of the enum, such as `TradeStatus`.
+    pub enum_name: &'static str,
+    /// The value sent by Roblox.
+    pub value: String,
+}
+
+impl SchemaDriftReport {
+    /// Returns whether the response matched the type exactly.
+    pub fn is_empty(&self) -> bool {
+        self.unknown_fields.is_empty()
+            && self.missing_fields.is_empty()
+            && self.unexpected_enum_values.is_empty()
+            && self.error.is_none()
+    }
+
+    /// Returns whether the report contains unknown fields, unknown enum values, or an error.
+    /// These are rejected by [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema),
+    /// while missing fields are only reported.
+    pub fn is_strict_violation(&self) -> bool {
+        !self.unknown_fields.is_empty()
+            || !self.unexpected_enum_values.is_empty()
+            || self.error.is_some()
+    }
+
+    /// Records a value of an enum that is not known by this crate.
+    pub(crate) fn unexpected_enum_value(
+        &mut self,
+        path: String,
+        enum_name: &'static str,
+        value: String,
+    ) {
+        self.unexpected_enum_values.push(UnexpectedEnumValue {
+            path,
+            enum_name,
+            value,
+        });
+    }
+
+    /// Removes unknown fields that are sent by Roblox but deliberately not parsed. In `fields`,
+    /// `[]` matches any index, such as `data[].seller.name`.
+    pub(crate) fn ignore_unused_fields(&mut self, fields: &[&str]) {
+        self.unknown_fields
+            .retain(|path, _| !fields.contains(&strip_indices(path).as_str()));
+    }
+}
+
+/// Replaces every index in a path with `[]`.
+fn strip_indices(path: &str) -> String {
+    let mut stripped = String::with_capacity(path.len());
+    let mut in_index = false;
+
+    for c in path.chars() {
+        match c {
+            '[' => in_index = true,
+            ']' => {
+                in_index = false;
+                stripped.push_str("[]");
+            }
+            _ if in_index => {}
+            _ => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+/// A receiver for the [`SchemaDriftReport`]s of a [`Client`].
+///
+/// # Example
+/// ```
+/// use roboat::schema::{SchemaDriftHandler, SchemaDriftReport};
+/// use roboat::ClientBuilder;
+///
+/// #[derive(Debug)]
+/// struct PrintHandler;
+///
+/// impl SchemaDriftHandler for PrintHandler {
+///     fn on_drift(&self, report: &SchemaDriftReport) {
+///         for (path, value) in &report.unknown_fields {
+///             println!("{}: new field {} = {}", report.response_type, path, value);
+///         }
+///     }
+/// }
+///
+/// let client = ClientBuilder::new().schema_drift_handler(PrintHandler).build();
+/// ```
+pub trait SchemaDriftHandler: std::fmt::Debug + Send + Sync {
+    /// Called for every response that does not match the type it is parsed into.
+    fn on_drift(&self, report: &SchemaDriftReport);
+}
+
+impl Client {
+    /// Returns whether responses are compared to the types they are parsed into.
+    pub(crate) fn detects_schema_drift(&self) -> bool {
+        self.schema_drift_handler.is_some() || self.strict_schema
+    }
+
+    /// Parses `bytes` into `T`, reporting any drift to the [`SchemaDriftHandler`] of the client.
+    ///
+    /// `check` is called with the parsed value to report values that are only validated after
+    /// parsing, such as enums sent as numbers. If `mutating` is true, the response is never rejected
+    /// by [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema).
+    ///
+    /// Nothing is borrowed from `bytes`, so types that usually borrow from the response can be given
+    /// with a `'static` lifetime.
+    pub(crate) fn parse_tracked<T: Deserialize<'static>>(
+        &self,
+        bytes: &[u8],
+        url: Option<String>,
+        mutating: bool,
+        check: impl FnOnce(&T, &mut SchemaDriftReport),
+    ) -> Result<T, RoboatError> {
+        let mut report = SchemaDriftReport {
+            response_type: std::any::type_name::<T>(),
+            url,
+            ..Default::default()
+        };
+
+        let result = match serde_json::from_slice::<Value>(bytes) {
+            Ok(value) => {
+                let state = RefCell::new(TrackingState::default());
+                let result = T::deserialize(Tracked {
+                    value: &value,
+                    path: String::new(),
+                    state: &state,
+                });
+
+                let state = state.into_inner();
+                report.unknown_fields = state.unknown_fields;
+                report.missing_fields = state.missing_fields;
+                report.unexpected_enum_values = state.unexpected_enum_values;
+
+                result.map_err(|e| {
+                    report.error = Some(match state.error_path {
+                        Some(path) if !path.is_empty() => format!("{}: {}", path, e),
+                        _ => e.to_string(),
+                    });
+                })
+            }
+            Err(e) => {
+                report.error = Some(e.to_string());
+                Err(())
+            }
+        };
+
+        if let Ok(x) = &result {
+            check(x, &mut report);
+        }
+
+        if report.is_empty() {
+            return result.map_err(|_| RoboatError::MalformedResponse);
+        }
+
+        if let Some(handler) = &self.schema_drift_handler {
+            handler.on_drift(&report);
+        }
+
+        // The change was already made, so failing would report a successful call as failed.
+        if self.strict_schema && !mutating && report.is_strict_violation() {
+            return Err(RoboatError::SchemaDrift(Box::new(report)));
+        }
+
+        result.map_err(|_| RoboatError::MalformedResponse)
+    }
+
+    /// Compares `bytes` to `T` if drift detection is enabled, without keeping the parsed value.
+    /// Used by methods that return the body of a response as is.
+    pub(crate) fn check_schema_drift<T: Deserialize<'static>>(
+        &self,
+        bytes: &[u8],
+        url: Option<String>,
+        check: impl FnOnce(&T, &mut SchemaDriftReport),
+    ) -> Result<(), RoboatError> {
+        if !self.detects_schema_drift() {
+            return Ok(());
+        }
+
+        self.parse_tracked::<T>(bytes, url, false, check)
+            .map(|_| ())
+    }
+}
+
+#[derive(Default)]
+struct TrackingState {
+    unknown_fields: BTreeMap<String, Value>,
+    missing_fields: Vec<String>,
+    unexpected_enum_values: Vec<UnexpectedEnumValue>,
+    /// The path of the innermost value that failed to parse.
+    error_path: Option<String>,
+}
+
+fn field_path(path: &str, field: &str) -> String {
+    match path.is_empty() {
+        true => field.to_string(),
+        false => format!("{}.{}", path, field),
+    }
+}
+
+/// A deserializer over a [`Value`] that records how it differs from the type deserialized from it.
+///
+/// It never lends out borrowed data, so it can deserialize types with any lifetime.
+struct Tracked<'v, 's> {
+    value: &'v Value,
+    path: String,
+    state: &'s RefCell<TrackingState>,
+}
+
+impl<'v, 's> Tracked<'v, 's> {
+    fn child(&self, value: &'v Value, path: String) -> Self {
+        Self {
+            value,
+            path,
+            state: self.state,
+        }
+    }
+
+    fn record_error<T>(
+        &self,
+        result: Result<T, serde_json::Error>,
+    ) -> Result<T, serde_json::Error> {
+        if result.is_err() {
+            let mut state = self.state.borrow_mut();
+            if state.error_path.is_none() {
+                state.error_path = Some(self.path.clone());
+            }
+        }
+
+        result
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracked<'_, '_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let result = match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(x) => visitor.visit_bool(*x),
+            Value::Number(x) => match (x.as_u64(), x.as_i64(), x.as_f64()) {
+                (Some(x), _, _) => visitor.visit_u64(x),
+                (None, Some(x), _) => visitor.visit_i64(x),
+                (None, None, Some(x)) => visitor.visit_f64(x),
+                (None, None, None) => Err(serde_json::Error::custom("invalid number")),
+            },
+            Value::String(x) => visitor.visit_str(x),
+            Value::Array(x) => visitor.visit_seq(TrackedSeq {
+                iter: x.iter().enumerate(),
+                tracked: &self,
+            }),
+            Value::Object(x) => visitor.visit_map(TrackedMap {
+                iter: x.iter(),
+                next_value: None,
+                tracked: &self,
+            }),
+        };
+
+        self.record_error(result)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if let Value::Object(object) = self.value {
+            self.compare_fields(object, fields);
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let variant = match self.value {
+            Value::String(x) => Some(x),
+            Value::Object(x) if x.len() == 1 => x.keys().next(),
+            _ => None,
+        };
+
+        if let Some(variant) = variant.filter(|x| !variants.contains(&x.as_str())) {
+            self.state
+                .borrow_mut()
+                .unexpected_enum_values
+                .push(UnexpectedEnumValue {
+                    path: self.path.clone(),
+                    enum_name: name,
+                    value: variant.clone(),
+                });
+        }
+
+        // Enums are rare and small, so the fields of their variants are not tracked.
+        let result = match self.value {
+            Value::String(x) => visitor.visit_enum(x.as_str().into_deserializer()),
+            value => value.clone().deserialize_enum(name, variants, visitor),
+        };
+
+        self.record_error(result)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+    }
+}
+
+impl Tracked<'_, '_> {
+    fn compare_fields(&self, object: &Map<String, Value>, fields: &'static [&'static str]) {
+        let mut state = self.state.borrow_mut();
+
+        for (key, value) in object {
+            if !fields.contains(&key.as_str()) {
+                state
+                    .unknown_fields
+                    .insert(field_path(&self.path, key), value.clone());
+            }
+        }
+
+        for field in fields {
+            if !object.contains_key(*field) {
+                state.missing_fields.push(field_path(&self.path, field));
+            }
+        }
+    }
+}
+
+struct TrackedSeq<'a, 'v, 's> {
+    iter: std::iter::Enumerate<std::slice::Iter<'v, Value>>,
+    tracked: &'a Tracked<'v, 's>,
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'_, '_, '_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((i, value)) => {
+                let path = format!("{}[{}]", self.tracked.path, i);
+                seed.deserialize(self.tracked.child(value, path)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct TrackedMap<'a, 'v, 's> {
+    iter: serde_json::map::Iter<'v>,
+    next_value: Option<(&'v String, &'v Value)>,
+    tracked: &'a Tracked<'v, 's>,
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'_, '_, '_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.next_value = Some((key, value));
+                seed.deserialize(KeyDeserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .next_value
+            .take()
+            .ok_or_else(|| serde_json::Error::custom("value requested before key"))?;
+
+        seed.deserialize(
+            self.tracked
+                .child(value, field_path(&self.tracked.path, key)),
+        )
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes the key of an object, parsing it as a number if a number is requested
+/// (as keys of maps such as `HashMap<u64, _>` are sent as strings).
+struct KeyDeserializer<'v>(&'v str);
+
+macro_rules! deserialize_key_number {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(x) => visitor.$visit(x),
+                    Err(_) => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for KeyDeserializer<'_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.0)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let deserializer: StrDeserializer<'_, Self::Error> = self.0.into_deserializer();
+        deserializer.deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_key_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SchemaDriftHandler, SchemaDriftReport};
+    use crate::testing::{Cassette, Interaction, RecordedRequest, RecordedResponse, Replayer};
+    use crate::{ClientBuilder, RoboatError};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Debug, Default)]
+    struct CollectingHandler {
+        reports: Arc<Mutex<Vec<SchemaDriftReport>>>,
+    }
+
+    impl SchemaDriftHandler for CollectingHandler {
+        fn on_drift(&self, report: &SchemaDriftReport) {
+            self.reports.lock().unwrap().push(report.clone());
+        }
+    }
+
+    fn interaction(method: &str, url: &str, request_body: Option<&str>, body: &str) -> Interaction {
+        Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                url: url.to_string(),
+                body: request_body.map(|x| x.to_string()),
+                ..Default::default()
+            },
+            response: RecordedResponse {
+                status: 200,
+                body: body.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn strict_client(interactions: Vec<Interaction>, handler: CollectingHandler) -> crate::Client {
+        ClientBuilder::new()
+            .roblosecurity("cookie".to_string())
+            .replayer(Replayer::new(Cassette { interactions }))
+            .schema_drift_handler(handler)
+            .strict_schema(true)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn rejects_drift_in_read_only_responses() {
+        let handler = CollectingHandler::default();
+        let client = strict_client(
+            vec![
+                interaction(
+                    "GET",
+                    "https://users.roblox.com/v1/users/authenticated",
+                    None,
+                    r#"{"id":1,"name":"buyer","displayName":"buyer"}"#,
+                ),
+                interaction(
+                    "GET",
+                    "https://economy.roblox.com/v1/users/1/currency",
+                    None,
+                    r#"{"robux":5,"newField":true}"#,
+                ),
+            ],
+            handler.clone(),
+        );
+
+        let result = client.robux().await;
+
+        match result {
+            Err(RoboatError::SchemaDrift(report)) => {
+                assert!(report.unknown_fields.contains_key("newField"))
+            }
+            x => panic!("expected schema drift, got {:?}", x),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_drift_in_mutating_responses_without_failing() {
+        let handler = CollectingHandler::default();
+        let client = strict_client(
+            vec![interaction(
+                "POST",
+                "https://economy.roblox.com/v1/purchases/products/1",
+                Some(
+                    r#"{"expectedCurrency":1,"expectedPrice":500,"expectedSellerId":2,"userAssetId":3}"#,
+                ),
+                r#"{"purchased":true,"reason":"Success","errorMsg":"","newField":true}"#,
+            )],
+            handler.clone(),
+        );
+
+        let receipt = client
+            .purchase_tradable_limited(1, 2, 3, 500)
+            .await
+            .unwrap();
+        assert_eq!(receipt.uaid, Some(3));
+
+        let reports = handler.reports.lock().unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].unknown_fields.contains_key("newField"));
+    }
+}
@@ -0,0 +1,280 @@
+//! Saving and restoring the state of a [`Client`], so that it does not have to fetch
+//! its x-csrf-token and user information again after a restart.
+//!
+//! The state is exported using [`Client::export_state`] and restored using
+//! [`ClientBuilder::restore_state`]. [`ClientState`] can be serialized with serde
+//! into any format, such as json.
+
+use crate::users::ClientUserInformation;
+use crate::{Client, ClientBuilder, RoboatError};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+/// The version of the [`ClientState`] format written by this crate.
+const STATE_VERSION: u32 = 1;
+
+const SALT_LENGTH: usize = 16;
+
+/// Replaces the roblosecurity and x-csrf-token in the [`Debug`] output of a [`ClientState`].
+const REDACTED: &str = "[redacted]";
+
+/// The state of a [`Client`], returned by [`Client::export_state`].
+///
+/// # Notes
+/// * The roblosecurity is the latest one known by the client, including one that was rotated by Roblox.
+/// * Unless it was exported with a passphrase, the state contains the roblosecurity in plain text and must be stored securely.
+/// * The roblosecurity and x-csrf-token are redacted from the [`Debug`] output.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    version: u32,
+    roblosecurity: Option<StoredRoblosecurity>,
+    xcsrf: Option<String>,
+    user_information: Option<ClientUserInformation>,
+}
+
+/// The roblosecurity stored in a [`ClientState`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+enum StoredRoblosecurity {
+    Plain {
+        value: String,
+    },
+    /// Encrypted with ChaCha20-Poly1305, using a key derived from the passphrase with Argon2id.
+    /// Every field is base64 encoded.
+    Encrypted {
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+impl std::fmt::Debug for ClientState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientState")
+            .field("version", &self.version)
+            .field(
+                "roblosecurity",
+                &self.roblosecurity.as_ref().map(|_| REDACTED),
+            )
+            .field("encrypted", &self.is_encrypted())
+            .field("xcsrf", &self.xcsrf.as_ref().map(|_| REDACTED))
+            .field("user_information", &self.user_information)
+            .finish()
+    }
+}
+
+impl ClientState {
+    /// Returns the user id of the account, if it was cached when the state was exported.
+    pub fn user_id(&self) -> Option<u64> {
+        self.user_information.as_ref().map(|x| x.user_id)
+    }
+
+    /// Returns whether the state contains a roblosecurity.
+    pub fn has_roblosecurity(&self) -> bool {
+        self.roblosecurity.is_some()
+    }
+
+    /// Returns whether the roblosecurity is encrypted with a passphrase.
+    pub fn is_encrypted(&self) -> bool {
+        matches!(
+            self.roblosecurity,
+            Some(StoredRoblosecurity::Encrypted { .. })
+        )
+    }
+}
+
+impl Client {
+    /// Exports the roblosecurity, x-csrf-token, and user information of the client so that
+    /// they can be restored using [`ClientBuilder::restore_state`].
+    ///
+    /// If `passphrase` is given, the roblosecurity is encrypted with it, and the same passphrase
+    /// is required to restore the state.
+    ///
+    /// # Notes
+    /// * Does not send any requests. Only values already cached by the client are exported.
+    /// * Settings of the [`ClientBuilder`] (sinks, policies, etc.) are not part of the state.
+    ///
+    /// # Errors
+    /// * [`RoboatError::InvalidConfig`] - If the roblosecurity could not be encrypted.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    /// let user_id = client.user_id().await?;
+    ///
+    /// let state = client.export_state(Some("passphrase")).await?;
+    /// std::fs::write("state.json", serde_json::to_string(&state)?)?;
+    ///
+    /// // After a restart.
+    /// let state = serde_json::from_str(&std::fs::read_to_string("state.json")?)?;
+    /// let client = ClientBuilder::new()
+    ///     .restore_state(state, Some("passphrase"))?
+    ///     .build();
+    ///
+    /// // The user id is cached, so no request is sent.
+    /// assert_eq!(client.user_id().await?, user_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn export_state(&self, passphrase: Option<&str>) -> Result<ClientState, RoboatError> {
+        let roblosecurity = match (self.roblosecurity(), passphrase) {
+            (Some(value), None) => Some(StoredRoblosecurity::Plain { value }),
+            (Some(value), Some(passphrase)) => Some(encrypt(&value, passphrase)?),
+            (None, _) => None,
+        };
+
+        let xcsrf = self.xcsrf().await;
+
+        Ok(ClientState {
+            version: STATE_VERSION,
+            roblosecurity,
+            xcsrf: (!xcsrf.is_empty()).then_some(xcsrf),
+            user_information: self.user_information.read().await.clone(),
+        })
+    }
+}
+
+impl ClientBuilder {
+    /// Restores the roblosecurity, x-csrf-token, and user information exported by [`Client::export_state`].
+    ///
+    /// `passphrase` must be the passphrase the state was exported with, if any.
+    ///
+    /// # Notes
+    /// * The restored x-csrf-token may have expired. If it has, it is refreshed automatically on first use.
+    /// * The roblosecurity of the state replaces any roblosecurity already set on the builder.
+    ///
+    /// # Errors
+    /// * [`RoboatError::InvalidConfig`] - If the state was written by a newer version of this crate, or
+    ///   if the roblosecurity is encrypted and the passphrase is missing or wrong.
+    pub fn restore_state(
+        mut self,
+        state: ClientState,
+        passphrase: Option<&str>,
+    ) -> Result<Self, RoboatError> {
+        if state.version > STATE_VERSION {
+            return Err(RoboatError::InvalidConfig(format!(
+                "unsupported client state version {}",
+                state.version
+            )));
+        }
+
+        let roblosecurity = match (state.roblosecurity, passphrase) {
+            (Some(StoredRoblosecurity::Plain { value }), _) => Some(value),
+            (Some(StoredRoblosecurity::Encrypted { .. }), None) => {
+                return Err(RoboatError::InvalidConfig(
+                    "the client state is encrypted, but no passphrase was given".to_string(),
+                ))
+            }
+            (
+                Some(StoredRoblosecurity::Encrypted {
+                    salt,
+                    nonce,
+                    ciphertext,
+                }),
+                Some(passphrase),
+            ) => Some(decrypt(&salt, &nonce, &ciphertext, passphrase)?),
+            (None, _) => None,
+        };
+
+        if let Some(x) = roblosecurity {
+            self = self.roblosecurity(x);
+        }
+
+        self.xcsrf = state.xcsrf;
+        self.user_information = state.user_information;
+
+        Ok(self)
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, RoboatError> {
+    let mut key = Key::default();
+
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| RoboatError::InvalidConfig(format!("failed to derive key: {}", e)))?;
+
+    Ok(key)
+}
+
+fn encrypt(roblosecurity: &str, passphrase: &str) -> Result<StoredRoblosecurity, RoboatError> {
+    let mut salt = [0; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+
+    let key = derive_key(passphrase, &salt)?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = ChaCha20Poly1305::new(&key)
+        .encrypt(&nonce, roblosecurity.as_bytes())
+        .map_err(|_| RoboatError::InvalidConfig("failed to encrypt roblosecurity".to_string()))?;
+
+    Ok(StoredRoblosecurity::Encrypted {
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt(
+    salt: &str,
+    nonce: &str,
+    ciphertext: &str,
+    passphrase: &str,
+) -> Result<String, RoboatError> {
+    let invalid = || {
+        RoboatError::InvalidConfig(
+            "the client state could not be decrypted, the passphrase is wrong or the state is corrupted"
+                .to_string(),
+        )
+    };
+
+    let salt = STANDARD.decode(salt).map_err(|_| invalid())?;
+    let nonce = STANDARD.decode(nonce).map_err(|_| invalid())?;
+    let ciphertext = STANDARD.decode(ciphertext).map_err(|_| invalid())?;
+
+    if nonce.len() != Nonce::default().len() {
+        return Err(invalid());
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+
+    let plaintext = ChaCha20Poly1305::new(&key)
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| invalid())?;
+
+    String::from_utf8(plaintext).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientState, StoredRoblosecurity, STATE_VERSION};
+
+    #[test]
+    fn debug_redacts_the_roblosecurity_and_xcsrf() {
+        let state = ClientState {
+            version: STATE_VERSION,
+            roblosecurity: Some(StoredRoblosecurity::Plain {
+                value: "secret_cookie".to_string(),
+            }),
+            xcsrf: Some("secret_xcsrf".to_string()),
+            user_information: None,
+        };
+
+        let debug = format!("{:?}", state);
+
+        assert!(!debug.contains("secret_cookie"));
+        assert!(!debug.contains("secret_xcsrf"));
+        assert!(debug.contains("[redacted]"));
+    }
+}
@@ -0,0 +1,503 @@
+//! A guard that every purchase made by a [`Client`] must pass.
+//!
+//! A [`SpendingPolicy`] can be attached using [`ClientBuilder::spending_policy`](crate::ClientBuilder::spending_policy).
+//! [`Client::purchase_tradable_limited`] and [`Client::purchase_non_tradable_limited`] then check the policy
+//! before sending anything, and return [`RoboatError::SpendingPolicyViolation`] if the purchase is not allowed.
+
+use crate::{Client, RoboatError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+const DAY: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// The id of an item as known by a purchase method.
+///
+/// [`Client::purchase_tradable_limited`] identifies items by their product id, and
+/// [`Client::purchase_non_tradable_limited`] identifies items by their collectible item id.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SpendingItemId {
+    /// The product id of a tradable (legacy) limited.
+    ProductId(u64),
+    /// The collectible item id of a non-tradable limited.
+    CollectibleItemId(String),
+}
+
+/// A reason a purchase was blocked by a [`SpendingPolicy`].
+#[non_exhaustive]
+#[derive(
+    thiserror::Error, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum SpendingPolicyViolation {
+    /// The item is on the deny list.
+    #[error("Item Denied: {0:?}")]
+    ItemDenied(SpendingItemId),
+    /// The allow list is not empty, and the item is not on it.
+    #[error("Item Not Allowed: {0:?}")]
+    ItemNotAllowed(SpendingItemId),
+    /// The price is above the maximum price per item.
+    #[error("Price {price} Above Max Price {max_price}")]
+    PriceAboveMax {
+        /// The price of the purchase.
+        price: u64,
+        /// The maximum price per item.
+        max_price: u64,
+    },
+    /// The price is too far above the recent average price of the item.
+    #[error("Price {price} More Than {max_percent}% Above Rap {rap}")]
+    PriceAboveRap {
+        /// The price of the purchase.
+        price: u64,
+        /// The recent average price of the item.
+        rap: u64,
+        /// The maximum percentage the price may be above the rap.
+        max_percent: u64,
+    },
+    /// The rap of the item is required, but the [`RapProvider`] does not know it.
+    #[error("Rap Unknown For Item: {0:?}")]
+    RapUnknown(SpendingItemId),
+    /// The purchase would exceed a rolling budget.
+    #[error(
+        "Budget Of {budget} Per {window_seconds} Seconds Exceeded ({spent} Spent, Price {price})"
+    )]
+    BudgetExceeded {
+        /// The length of the rolling window, in seconds.
+        window_seconds: u64,
+        /// The robux already spent inside the window.
+        spent: u64,
+        /// The price of the purchase.
+        price: u64,
+        /// The budget of the window.
+        budget: u64,
+    },
+}
+
+/// A source of recent average prices used by [`SpendingPolicy::max_percent_above_rap`].
+///
+/// # Example
+/// ```
+/// use roboat::spending::{RapProvider, SpendingItemId};
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug)]
+/// struct StaticRaps(HashMap<u64, u64>);
+///
+/// impl RapProvider for StaticRaps {
+///     fn rap(&self, item: &SpendingItemId) -> Option<u64> {
+///         match item {
+///             SpendingItemId::ProductId(x) => self.0.get(x).copied(),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait RapProvider: std::fmt::Debug + Send + Sync {
+    /// Returns the recent average price of an item, or `None` if it is unknown.
+    fn rap(&self, item: &SpendingItemId) -> Option<u64>;
+}
+
+/// Limits on the purchases made by a [`Client`].
+///
+/// The policy is cheap to clone, and clones share the same spending history. This means
+/// a single policy can be used as a global budget for multiple clients.
+///
+/// # Notes
+/// * Every limit is optional, a policy with no limits allows everything.
+/// * The deny list takes priority over the allow list. If the allow list is empty, every item not denied is allowed.
+/// * If the rap of an item is required but unknown, the purchase is blocked.
+/// * Robux is counted towards the budgets as soon as a purchase passes the policy, so that concurrent
+///   purchases cannot exceed the budget. It is only given back if the purchase was definitely not made:
+///   Roblox declined it ([`RoboatError::PurchaseTradableLimitedError`] or
+///   [`RoboatError::PurchaseNonTradableLimitedError`]), rejected the request with a 4xx status code,
+///   or the request was never sent. If the request failed to send, Roblox returned a 5xx status code,
+///   or the response could not be parsed, it is unknown whether the purchase went through, so the
+///   robux stays counted.
+/// * A purchase made with an idempotency key that is already counted (such as a retry of
+///   [`Client::purchase_non_tradable_limited_with_idempotency_key`] after a [`RoboatError::ReqwestError`])
+///   reuses the existing reservation instead of being checked and counted again.
+///
+/// # Example
+/// ```
+/// use roboat::spending::{SpendingItemId, SpendingPolicy};
+/// use roboat::ClientBuilder;
+///
+/// let policy = SpendingPolicy::new()
+///     .max_price(10_000)
+///     .hourly_budget(20_000)
+///     .daily_budget(100_000)
+///     .deny(SpendingItemId::ProductId(12345));
+///
+/// let client = ClientBuilder::new().spending_policy(policy).build();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SpendingPolicy {
+    max_price: Option<u64>,
+    hourly_budget: Option<u64>,
+    daily_budget: Option<u64>,
+    allow_list: HashSet<SpendingItemId>,
+    deny_list: HashSet<SpendingItemId>,
+    max_percent_above_rap: Option<(u64, Arc<dyn RapProvider>)>,
+    history: Arc<Mutex<SpendingHistory>>,
+}
+
+#[derive(Debug, Default)]
+struct SpendingHistory {
+    spends: VecDeque<Spend>,
+    next_id: u64,
+}
+
+#[derive(Debug)]
+struct Spend {
+    id: u64,
+    at: Instant,
+    price: u64,
+    /// The idempotency key of the purchase, if it has one.
+    idempotency_key: Option<String>,
+}
+
+impl SpendingPolicy {
+    /// Creates a new policy with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum price of a single item.
+    pub fn max_price(mut self, max_price: u64) -> Self {
+        self.max_price = Some(max_price);
+        self
+    }
+
+    /// Sets the maximum robux spent in any rolling hour.
+    pub fn hourly_budget(mut self, budget: u64) -> Self {
+        self.hourly_budget = Some(budget);
+        self
+    }
+
+    /// Sets the maximum robux spent in any rolling day.
+    pub fn daily_budget(mut self, budget: u64) -> Self {
+        self.daily_budget = Some(budget);
+        self
+    }
+
+    /// Adds an item to the allow list. Once the allow list is not empty, only items on it can be purchased.
+    pub fn allow(mut self, item: SpendingItemId) -> Self {
+        self.allow_list.insert(item);
+        self
+    }
+
+    /// Adds an item to the deny list.
+    pub fn deny(mut self, item: SpendingItemId) -> Self {
+        self.deny_list.insert(item);
+        self
+    }
+
+    /// Requires the price to be at most `max_percent`% above the rap of the item, as
+    /// returned by `rap_provider`. For example, a `max_percent` of 10 allows paying 110 for an item with a rap of 100.
+    pub fn max_percent_above_rap<T: RapProvider + 'static>(
+        mut self,
+        max_percent: u64,
+        rap_provider: T,
+    ) -> Self {
+        self.max_percent_above_rap = Some((max_percent, Arc::new(rap_provider)));
+        self
+    }
+
+    /// Returns the robux counted towards the budgets in the last `window`.
+    pub fn spent_within(&self, window: Duration) -> u64 {
+        let history = self.history.lock().unwrap();
+        spent_since(&history, Instant::now(), window)
+    }
+
+    /// Checks a purchase against the policy without counting it towards the budgets.
+    ///
+    /// # Errors
+    /// * [`RoboatError::SpendingPolicyViolation`] - If the purchase is not allowed.
+    pub fn check(&self, item: &SpendingItemId, price: u64) -> Result<(), RoboatError> {
+        let history = self.history.lock().unwrap();
+        self.check_with_history(&history, item, price)
+            .map_err(RoboatError::SpendingPolicyViolation)
+    }
+
+    /// Checks a purchase and counts it towards the budgets, returning the id of the reservation.
+    ///
+    /// If a reservation with the same idempotency key is still counted, its id is returned instead.
+    fn reserve(
+        &self,
+        item: &SpendingItemId,
+        price: u64,
+        idempotency_key: Option<&str>,
+    ) -> Result<u64, SpendingPolicyViolation> {
+        let mut history = self.history.lock().unwrap();
+
+        // Spends older than the longest window are never looked at again.
+        let now = Instant::now();
+        while history
+            .spends
+            .front()
+            .is_some_and(|x| now.duration_since(x.at) >= DAY)
+        {
+            history.spends.pop_front();
+        }
+
+        if let Some(key) = idempotency_key {
+            let existing = history
+                .spends
+                .iter()
+                .find(|x| x.idempotency_key.as_deref() == Some(key));
+
+            if let Some(existing) = existing {
+                return Ok(existing.id);
+            }
+        }
+
+        self.check_with_history(&history, item, price)?;
+
+        history.next_id += 1;
+        let id = history.next_id;
+        history.spends.push_back(Spend {
+            id,
+            at: now,
+            price,
+            idempotency_key: idempotency_key.map(|x| x.to_string()),
+        });
+
+        Ok(id)
+    }
+
+    /// Gives back the robux of a reservation.
+    fn release(&self, id: u64) {
+        let mut history = self.history.lock().unwrap();
+        history.spends.retain(|x| x.id != id);
+    }
+
+    fn check_with_history(
+        &self,
+        history: &SpendingHistory,
+        item: &SpendingItemId,
+        price: u64,
+    ) -> Result<(), SpendingPolicyViolation> {
+        if self.deny_list.contains(item) {
+            return Err(SpendingPolicyViolation::ItemDenied(item.clone()));
+        }
+
+        if !self.allow_list.is_empty() && !self.allow_list.contains(item) {
+            return Err(SpendingPolicyViolation::ItemNotAllowed(item.clone()));
+        }
+
+        if let Some(max_price) = self.max_price {
+            if price > max_price {
+                return Err(SpendingPolicyViolation::PriceAboveMax { price, max_price });
+            }
+        }
+
+        if let Some((max_percent, rap_provider)) = &self.max_percent_above_rap {
+            let rap = rap_provider
+                .rap(item)
+                .ok_or_else(|| SpendingPolicyViolation::RapUnknown(item.clone()))?;
+
+            // Compared as `price / rap <= 1 + max_percent / 100` without any division.
+            if price as u128 * 100 > rap as u128 * (100 + *max_percent as u128) {
+                return Err(SpendingPolicyViolation::PriceAboveRap {
+                    price,
+                    rap,
+                    max_percent: *max_percent,
+                });
+            }
+        }
+
+        let now = Instant::now();
+
+        for (window, budget) in [(HOUR, self.hourly_budget), (DAY, self.daily_budget)] {
+            let budget = match budget {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let spent = spent_since(history, now, window);
+
+            if spent.saturating_add(price) > budget {
+                return Err(SpendingPolicyViolation::BudgetExceeded {
+                    window_seconds: window.as_secs(),
+                    spent,
+                    price,
+                    budget,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn spent_since(history: &SpendingHistory, now: Instant, window: Duration) -> u64 {
+    history
+        .spends
+        .iter()
+        .filter(|x| now.duration_since(x.at) < window)
+        .map(|x| x.price)
+        .sum()
+}
+
+impl Client {
+    /// Checks a purchase against the [`SpendingPolicy`] of the client (if any) and counts it towards the budgets.
+    /// Returns the id of the reservation, which must be passed to [`Client::settle_spending`].
+    ///
+    /// A blocked purchase is written to the [`AuditSink`](crate::audit::AuditSink) of the client as `endpoint`.
+    /// Purchases with the same `idempotency_key` share a single reservation.
+    pub(crate) async fn reserve_spending(
+        &self,
+        endpoint: &'static str,
+        item: SpendingItemId,
+        price: u64,
+        idempotency_key: Option<&str>,
+    ) -> Result<Option<u64>, RoboatError> {
+        let policy = match &self.spending_policy {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+
+        match policy.reserve(&item, price, idempotency_key) {
+            Ok(x) => Ok(Some(x)),
+            Err(violation) => {
+                let error = RoboatError::SpendingPolicyViolation(violation);
+
+                self.record_rejected_call(
+                    endpoint,
+                    serde_json::json!({ "item": item, "price": price }),
+                    &error,
+                )
+                .await;
+
+                Err(error)
+            }
+        }
+    }
+
+    /// Gives back the robux of a reservation if the purchase was definitely not made.
+    pub(crate) fn settle_spending<T>(
+        &self,
+        reservation: Option<u64>,
+        result: &Result<T, RoboatError>,
+    ) {
+        let (policy, id) = match (&self.spending_policy, reservation) {
+            (Some(policy), Some(id)) => (policy, id),
+            _ => return,
+        };
+
+        if let Err(e) = result {
+            if is_rejected_purchase(e) {
+                policy.release(id);
+            }
+        }
+    }
+}
+
+/// Returns whether an error returned by a purchase means that it was definitely not made.
+///
+/// Errors raised after a 2xx or 5xx response (or while sending) are not, as the purchase may
+/// have gone through.
+fn is_rejected_purchase(error: &RoboatError) -> bool {
+    match error {
+        // Roblox answered with `purchased: false`.
+        RoboatError::PurchaseTradableLimitedError(_)
+        | RoboatError::PurchaseNonTradableLimitedError(_) => true,
+        // Roblox rejected the request with a 4xx status code.
+        RoboatError::TooManyRequests
+        | RoboatError::BadRequest
+        | RoboatError::InvalidRoblosecurity
+        | RoboatError::UnknownRobloxErrorCode { .. }
+        | RoboatError::InvalidXcsrf(_)
+        | RoboatError::XcsrfNotReturned
+        | RoboatError::ChallengeRequired(_)
+        | RoboatError::UnknownStatus403Format => true,
+        RoboatError::UnidentifiedStatusCode(status) => (400..500).contains(status),
+        // The request was never sent.
+        RoboatError::RoblosecurityNotSet
+        | RoboatError::DryRun(_)
+        | RoboatError::InvalidConfig(_)
+        | RoboatError::RequestNotCloneable
+        | RoboatError::CassetteMismatch(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpendingPolicy, DAY};
+    use crate::testing::{FakeRoblox, FakeUser, Fault};
+    use crate::{Client, ClientBuilder, RoboatError};
+
+    const PRICE: u64 = 500;
+
+    fn setup() -> (FakeRoblox, SpendingPolicy, Client) {
+        let fake = FakeRoblox::new();
+        fake.add_user(
+            FakeUser::new(1, "buyer")
+                .roblosecurity("cookie")
+                .robux(1000),
+        );
+
+        let policy = SpendingPolicy::new().daily_budget(10_000);
+
+        let client = ClientBuilder::new()
+            .roblosecurity("cookie".to_string())
+            .fake_roblox(fake.clone())
+            .spending_policy(policy.clone())
+            .build();
+
+        (fake, policy, client)
+    }
+
+    #[tokio::test]
+    async fn keeps_the_reservation_when_a_successful_response_cannot_be_parsed() {
+        let (fake, policy, client) = setup();
+
+        // Roblox returns 200 (so the purchase may have gone through) with a body that is not json.
+        fake.inject_fault(Fault::Status(200));
+
+        let result = client.purchase_tradable_limited(1, 2, 3, PRICE).await;
+
+        assert!(matches!(result, Err(RoboatError::MalformedResponse)));
+        assert_eq!(policy.spent_within(DAY), PRICE);
+    }
+
+    #[tokio::test]
+    async fn keeps_the_reservation_on_server_errors() {
+        let (fake, policy, client) = setup();
+
+        fake.inject_fault(Fault::Status(500));
+
+        let result = client.purchase_tradable_limited(1, 2, 3, PRICE).await;
+
+        assert!(matches!(result, Err(RoboatError::InternalServerError)));
+        assert_eq!(policy.spent_within(DAY), PRICE);
+    }
+
+    #[tokio::test]
+    async fn releases_the_reservation_when_the_purchase_is_declined() {
+        let (_, policy, client) = setup();
+
+        // The item does not exist, so Roblox answers with `purchased: false`.
+        let result = client.purchase_tradable_limited(1, 2, 3, PRICE).await;
+
+        assert!(matches!(
+            result,
+            Err(RoboatError::PurchaseTradableLimitedError(_))
+        ));
+        assert_eq!(policy.spent_within(DAY), 0);
+    }
+
+    #[tokio::test]
+    async fn releases_the_reservation_when_the_request_is_rejected() {
+        let (fake, policy, client) = setup();
+
+        fake.inject_fault(Fault::TooManyRequests);
+
+        let result = client.purchase_tradable_limited(1, 2, 3, PRICE).await;
+
+        assert!(matches!(result, Err(RoboatError::TooManyRequests)));
+        assert_eq!(policy.spent_within(DAY), 0);
+    }
+}
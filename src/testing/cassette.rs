@@ -0,0 +1,693 @@
+use crate::transport::redact_headers;
+use crate::RoboatError;
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use reqwest::{Request, Response, ResponseBuilderExt};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// The value used in place of scrubbed response header values.
+const SCRUBBED: &str = "[redacted]";
+
+/// Response headers that are scrubbed before an interaction is recorded.
+const SCRUBBED_RESPONSE_HEADERS: &[&str] = &["x-csrf-token", "set-cookie"];
+
+/// Body fields that differ between every call, and are therefore ignored by default when matching requests.
+const DEFAULT_IGNORED_BODY_FIELDS: &[&str] = &["idempotencyKey"];
+
+/// A list of recorded request/response pairs that can be saved to, and loaded from, a json file.
+///
+/// Created by a [`Recorder`] and replayed using a [`Replayer`].
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    /// The recorded interactions, in the order they were sent.
+    pub interactions: Vec<Interaction>,
+}
+
+/// A single request and the response that was returned for it.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A recorded request. The values of the cookie and x-csrf-token headers are scrubbed.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// The http method (e.g. `GET`).
+    pub method: String,
+    /// The full url, including the query.
+    pub url: String,
+    /// The headers sent with the request, in the form of `(name, value)`.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request. This is `None` for requests without a body, and for
+    /// multipart/streamed bodies.
+    pub body: Option<String>,
+    /// The parts of a multipart body, if the method that sent the request described them.
+    /// Only set on requests returned in [`RoboatError::DryRun`] and written to an
+    /// [`AuditSink`](crate::audit::AuditSink).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<RecordedPart>,
+}
+
+/// A part of a multipart request body.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RecordedPart {
+    /// The name of the form field.
+    pub name: String,
+    /// The file name of the part, if it is a file.
+    pub file_name: Option<String>,
+    /// The length of the content of the part, in bytes.
+    pub len: u64,
+    /// The content of the part, if it is text. File contents are not recorded.
+    pub text: Option<String>,
+}
+
+/// A recorded response. The values of the x-csrf-token and set-cookie headers are scrubbed.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers returned with the response, in the form of `(name, value)`.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response. If `base64_body` is true, this is encoded in base64.
+    pub body: String,
+    /// Whether `body` is encoded in base64. This is used for bodies that are not valid utf-8.
+    #[serde(default)]
+    pub base64_body: bool,
+}
+
+/// Records every request sent by a [`Client`](crate::Client) into a [`Cassette`].
+///
+/// Requests are still sent to Roblox. Attach using [`ClientBuilder::recorder`](crate::ClientBuilder::recorder).
+///
+/// # Example
+/// ```no_run
+/// use roboat::testing::Recorder;
+/// use roboat::ClientBuilder;
+///
+/// const ROBLOSECURITY: &str = "roblosecurity";
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let recorder = Recorder::new();
+///
+/// let client = ClientBuilder::new()
+///     .roblosecurity(ROBLOSECURITY.to_string())
+///     .recorder(recorder.clone())
+///     .build();
+///
+/// let robux = client.robux().await?;
+///
+/// recorder.save("tests/cassettes/robux.json")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Recorder {
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+/// Replays the interactions in a [`Cassette`] instead of sending requests to Roblox.
+///
+/// A request is answered by the first unused interaction with the same method, url, and body.
+/// If no interaction matches, [`RoboatError::CassetteMismatch`] is returned with a diff against the
+/// next unused interaction. Attach using [`ClientBuilder::replayer`](crate::ClientBuilder::replayer).
+///
+/// # Example
+/// ```no_run
+/// use roboat::testing::{Cassette, Replayer};
+/// use roboat::ClientBuilder;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let cassette = Cassette::load("tests/cassettes/robux.json")?;
+/// let replayer = Replayer::new(cassette);
+///
+/// let client = ClientBuilder::new()
+///     .roblosecurity("roblosecurity".to_string())
+///     .replayer(replayer.clone())
+///     .build();
+///
+/// let robux = client.robux().await?;
+///
+/// assert!(replayer.is_exhausted());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replayer {
+    state: Arc<Mutex<ReplayState>>,
+}
+
+#[derive(Debug)]
+struct ReplayState {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+    ignored_body_fields: Vec<String>,
+}
+
+impl Cassette {
+    /// Loads a cassette from a json file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RoboatError> {
+        let contents = std::fs::read(path)?;
+
+        serde_json::from_slice(&contents).map_err(|e| {
+            RoboatError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })
+    }
+
+    /// Saves the cassette to a json file, overwriting it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RoboatError> {
+        let contents = serde_json::to_vec_pretty(self).map_err(|e| {
+            RoboatError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })?;
+
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+}
+
+impl Recorder {
+    /// Creates a new [`Recorder`] with an empty cassette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the cassette recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    /// Saves the cassette recorded so far to a json file, overwriting it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RoboatError> {
+        self.cassette().save(path)
+    }
+
+    /// Sends the request using `reqwest_client` and records the interaction.
+    ///
+    /// The response returned is rebuilt from the recorded parts, without any values scrubbed.
+    pub(crate) async fn record(
+        &self,
+        reqwest_client: &reqwest::Client,
+        request: Request,
+    ) -> Result<Response, RoboatError> {
+        let recorded_request = RecordedRequest::from_request(&request);
+
+        let response = reqwest_client
+            .execute(request)
+            .await
+            .map_err(RoboatError::ReqwestError)?;
+
+        let status = response.status();
+        let url = response.url().clone();
+        let headers = response.headers().clone();
+        let body = response.bytes().await.map_err(RoboatError::ReqwestError)?;
+
+        let recorded_headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if SCRUBBED_RESPONSE_HEADERS.contains(&name.as_str()) {
+                    SCRUBBED.to_string()
+                } else {
+                    value.to_str().unwrap_or(SCRUBBED).to_string()
+                };
+
+                (name.to_string(), value)
+            })
+            .collect();
+
+        let (recorded_body, base64_body) = match std::str::from_utf8(&body) {
+            Ok(x) => (x.to_string(), false),
+            Err(_) => (general_purpose::STANDARD.encode(&body), true),
+        };
+
+        self.cassette
+            .lock()
+            .unwrap()
+            .interactions
+            .push(Interaction {
+                request: recorded_request,
+                response: RecordedResponse {
+                    status: status.as_u16(),
+                    headers: recorded_headers,
+                    body: recorded_body,
+                    base64_body,
+                },
+            });
+
+        let mut builder = http::Response::builder().status(status).url(url);
+
+        for (name, value) in headers.iter() {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder
+            .body(body)
+            .map_err(|_| RoboatError::MalformedResponse)?;
+
+        Ok(Response::from(response))
+    }
+}
+
+impl Replayer {
+    /// Creates a new [`Replayer`] that replays the interactions in `cassette`.
+    ///
+    /// The `idempotencyKey` body field is ignored when matching requests by default, as it
+    /// is randomly generated for every purchase.
+    pub fn new(cassette: Cassette) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+
+        let state = ReplayState {
+            interactions: cassette.interactions,
+            used,
+            ignored_body_fields: DEFAULT_IGNORED_BODY_FIELDS
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+        };
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Ignores a top level field in json request bodies when matching requests.
+    /// Use this for fields that differ between every call.
+    pub fn ignore_body_field(self, field: impl Into<String>) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .ignored_body_fields
+            .push(field.into());
+
+        self
+    }
+
+    /// Returns the amount of interactions that have not been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .used
+            .iter()
+            .filter(|x| !**x)
+            .count()
+    }
+
+    /// Returns whether every interaction in the cassette has been replayed.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the response of the first unused interaction matching the request.
+    pub(crate) fn replay(&self, request: Request) -> Result<Response, RoboatError> {
+        let actual = RecordedRequest::from_request(&request);
+        let mut state = self.state.lock().unwrap();
+
+        let matching_index = (0..state.interactions.len()).find(|i| {
+            !state.used[*i]
+                && state.interactions[*i]
+                    .request
+                    .matches(&actual, &state.ignored_body_fields)
+        });
+
+        let index = match matching_index {
+            Some(x) => x,
+            None => {
+                let next_unused = (0..state.interactions.len()).find(|i| !state.used[*i]);
+
+                let message = match next_unused {
+                    Some(i) => format!(
+                        "request does not match the next unused interaction (#{}):\n{}",
+                        i,
+                        state.interactions[i].request.diff(&actual)
+                    ),
+                    None => format!(
+                        "cassette is exhausted, but another request was sent:\n+ {} {}",
+                        actual.method, actual.url
+                    ),
+                };
+
+                return Err(RoboatError::CassetteMismatch(message));
+            }
+        };
+
+        state.used[index] = true;
+
+        state.interactions[index]
+            .response
+            .to_response(request.url().clone())
+    }
+}
+
+impl RecordedRequest {
+    pub(crate) fn from_request(request: &Request) -> Self {
+        let body = request
+            .body()
+            .and_then(|x| x.as_bytes())
+            .map(|x| String::from_utf8_lossy(x).to_string());
+
+        Self {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: redact_headers(request.headers()),
+            body,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Whether the method, url, and body of `other` match this request. Json bodies are compared
+    /// structurally, without the fields in `ignored_body_fields`.
+    fn matches(&self, other: &RecordedRequest, ignored_body_fields: &[String]) -> bool {
+        self.method == other.method
+            && self.url == other.url
+            && normalize_body(&self.body, ignored_body_fields)
+                == normalize_body(&other.body, ignored_body_fields)
+    }
+
+    /// Returns a line based diff between this request (`-`) and `actual` (`+`).
+    fn diff(&self, actual: &RecordedRequest) -> String {
+        let mut lines = Vec::new();
+
+        if self.method != actual.method || self.url != actual.url {
+            lines.push(format!("- {} {}", self.method, self.url));
+            lines.push(format!("+ {} {}", actual.method, actual.url));
+        } else {
+            lines.push(format!("  {} {}", self.method, self.url));
+        }
+
+        if self.body != actual.body {
+            lines.push(format!(
+                "- body: {}",
+                self.body.as_deref().unwrap_or("<none>")
+            ));
+            lines.push(format!(
+                "+ body: {}",
+                actual.body.as_deref().unwrap_or("<none>")
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self, url: reqwest::Url) -> Result<Response, RoboatError> {
+        let body = match self.base64_body {
+            true => general_purpose::STANDARD
+                .decode(&self.body)
+                .map_err(|_| RoboatError::MalformedResponse)?,
+            false => self.body.clone().into_bytes(),
+        };
+
+        let mut builder = http::Response::builder().status(self.status).url(url);
+
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder
+            .body(Bytes::from(body))
+            .map_err(|_| RoboatError::MalformedResponse)?;
+
+        Ok(Response::from(response))
+    }
+}
+
+/// Parses a body as json (removing ignored fields) so that key order and whitespace do not matter.
+/// Bodies that are not json are compared as-is.
+fn normalize_body(
+    body: &Option<String>,
+    ignored_body_fields: &[String],
+) -> Option<serde_json::Value> {
+    let body = body.as_ref()?;
+
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(mut value) => {
+            if let Some(object) = value.as_object_mut() {
+                for field in ignored_body_fields {
+                    object.remove(field);
+                }
+            }
+
+            Some(value)
+        }
+        Err(_) => Some(serde_json::Value::String(body.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cassette, Interaction, RecordedRequest, RecordedResponse, Recorder, Replayer};
+    use crate::RoboatError;
+    use reqwest::Request;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const BODY: &str = r#"{"robux":5}"#;
+
+    /// Serves a single http response with a rotated x-csrf-token and roblosecurity, returning its url.
+    async fn serve_once() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            // The request is small enough to be read in one go.
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer).await.unwrap();
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\n\
+                 content-type: application/json\r\n\
+                 x-csrf-token: new-token\r\n\
+                 set-cookie: .ROBLOSECURITY=new-cookie\r\n\
+                 content-length: {}\r\n\
+                 connection: close\r\n\r\n{}",
+                BODY.len(),
+                BODY
+            );
+
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        format!("http://{}/v1/user/currency", address)
+    }
+
+    fn request(method: &str, url: &str, body: Option<&str>) -> Request {
+        let mut builder = reqwest::Client::new()
+            .request(method.parse().unwrap(), url)
+            .header("cookie", ".ROBLOSECURITY=secret-cookie")
+            .header("x-csrf-token", "secret-token");
+
+        if let Some(body) = body {
+            builder = builder.body(body.to_string());
+        }
+
+        builder.build().unwrap()
+    }
+
+    fn interaction(method: &str, url: &str, body: Option<&str>) -> Interaction {
+        Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                url: url.to_string(),
+                body: body.map(|x| x.to_string()),
+                ..Default::default()
+            },
+            response: RecordedResponse {
+                status: 200,
+                body: BODY.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn mismatch_message(error: RoboatError) -> String {
+        match error {
+            RoboatError::CassetteMismatch(x) => x,
+            e => panic!("expected a cassette mismatch, got {:?}", e),
+        }
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> &'a str {
+        headers
+            .iter()
+            .find(|(x, _)| x == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn records_and_replays_a_round_trip() {
+        let url = serve_once().await;
+        let recorder = Recorder::new();
+
+        let response = recorder
+            .record(&reqwest::Client::new(), request("POST", &url, Some("{}")))
+            .await
+            .unwrap();
+
+        // The response returned while recording is not scrubbed.
+        assert_eq!(response.headers()["x-csrf-token"], "new-token");
+        assert_eq!(response.text().await.unwrap(), BODY);
+
+        let path =
+            std::env::temp_dir().join(format!("roboat-cassette-{}.json", uuid::Uuid::new_v4()));
+        recorder.save(&path).unwrap();
+        let cassette = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(cassette, recorder.cassette());
+
+        let replayer = Replayer::new(cassette);
+        assert_eq!(replayer.remaining(), 1);
+
+        let response = replayer.replay(request("POST", &url, Some("{}"))).unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), BODY);
+        assert!(replayer.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn scrubs_secrets_from_recorded_interactions() {
+        let url = serve_once().await;
+        let recorder = Recorder::new();
+
+        recorder
+            .record(&reqwest::Client::new(), request("GET", &url, None))
+            .await
+            .unwrap();
+
+        let cassette = recorder.cassette();
+        let interaction = &cassette.interactions[0];
+
+        assert_eq!(header(&interaction.request.headers, "cookie"), "[redacted]");
+        assert_eq!(
+            header(&interaction.request.headers, "x-csrf-token"),
+            "[redacted]"
+        );
+        assert_eq!(
+            header(&interaction.response.headers, "x-csrf-token"),
+            "[redacted]"
+        );
+        assert_eq!(
+            header(&interaction.response.headers, "set-cookie"),
+            "[redacted]"
+        );
+
+        let saved = serde_json::to_string(&cassette).unwrap();
+
+        for secret in ["secret-cookie", "secret-token", "new-cookie", "new-token"] {
+            assert!(!saved.contains(secret), "{} was recorded", secret);
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_base64_bodies() {
+        let mut interaction = interaction("GET", "https://example.com/", None);
+        interaction.response.body = "AP8=".to_string();
+        interaction.response.base64_body = true;
+
+        let replayer = Replayer::new(Cassette {
+            interactions: vec![interaction],
+        });
+
+        let response = replayer
+            .replay(request("GET", "https://example.com/", None))
+            .unwrap();
+
+        assert_eq!(response.bytes().await.unwrap().as_ref(), &[0x00, 0xff]);
+    }
+
+    #[test]
+    fn matches_json_bodies_structurally() {
+        let replayer = Replayer::new(Cassette {
+            interactions: vec![interaction(
+                "POST",
+                "https://example.com/",
+                Some(r#"{"a":1,"b":2,"idempotencyKey":"x"}"#),
+            )],
+        })
+        .ignore_body_field("c");
+
+        let actual = request(
+            "POST",
+            "https://example.com/",
+            Some(r#"{ "b": 2, "a": 1, "idempotencyKey": "y", "c": 3 }"#),
+        );
+
+        assert!(replayer.replay(actual).is_ok());
+    }
+
+    #[test]
+    fn replays_each_interaction_once() {
+        let replayer = Replayer::new(Cassette {
+            interactions: vec![interaction("GET", "https://example.com/", None)],
+        });
+
+        assert!(replayer
+            .replay(request("GET", "https://example.com/", None))
+            .is_ok());
+
+        let error = replayer
+            .replay(request("GET", "https://example.com/", None))
+            .unwrap_err();
+
+        assert_eq!(
+            mismatch_message(error),
+            "cassette is exhausted, but another request was sent:\n+ GET https://example.com/"
+        );
+    }
+
+    #[test]
+    fn diffs_the_method_and_url_of_mismatches() {
+        let replayer = Replayer::new(Cassette {
+            interactions: vec![interaction("GET", "https://example.com/a", None)],
+        });
+
+        let error = replayer
+            .replay(request("POST", "https://example.com/b", None))
+            .unwrap_err();
+
+        assert_eq!(
+            mismatch_message(error),
+            "request does not match the next unused interaction (#0):\n\
+             - GET https://example.com/a\n\
+             + POST https://example.com/b"
+        );
+        assert_eq!(replayer.remaining(), 1);
+    }
+
+    #[test]
+    fn diffs_the_body_of_mismatches() {
+        let replayer = Replayer::new(Cassette {
+            interactions: vec![
+                interaction("GET", "https://example.com/a", None),
+                interaction("POST", "https://example.com/b", Some(r#"{"a":1}"#)),
+            ],
+        });
+
+        assert!(replayer
+            .replay(request("GET", "https://example.com/a", None))
+            .is_ok());
+
+        let error = replayer
+            .replay(request("POST", "https://example.com/b", Some(r#"{"a":2}"#)))
+            .unwrap_err();
+
+        assert_eq!(
+            mismatch_message(error),
+            "request does not match the next unused interaction (#1):\n  \
+             POST https://example.com/b\n\
+             - body: {\"a\":1}\n\
+             + body: {\"a\":2}"
+        );
+    }
+}
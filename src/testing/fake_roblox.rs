@@ -0,0 +1,2077 @@
+use crate::catalog::{CreatorType, ItemType};
+use crate::groups::Role;
+use crate::trades::TradeStatus;
+use crate::{RoboatError, XCSRF_HEADER};
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use reqwest::header;
+use reqwest::{Method, Request, Response, ResponseBuilderExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// The timestamp used for everything created by the fake, so that responses are deterministic.
+const FAKE_TIMESTAMP: &str = "2023-01-01T00:00:00.000Z";
+/// The expiration timestamp used for trades created by the fake.
+const FAKE_EXPIRATION_TIMESTAMP: &str = "2023-01-05T00:00:00.000Z";
+/// The page size used for endpoints that take a limit the fake does not parse.
+const DEFAULT_PAGE_SIZE: usize = 10;
+/// The fraction of the price a seller receives from a sale.
+const SELLER_SHARE: f64 = 0.7;
+/// The message Roblox returns when a challenge is required.
+const CHALLENGE_MESSAGE: &str = "Challenge is required to authorize the request";
+
+/// An in-process fake of the Roblox API with in-memory state.
+///
+/// Serves the trades, account settings, inventory, economy, catalog, friends, groups, users, thumbnails and presence
+/// endpoints used by [`Client`](crate::Client), so bots can be tested end-to-end with no network.
+/// For example, [`Client::send_trade`](crate::Client::send_trade) creates a trade that is then
+/// listed by [`Client::trades`](crate::Client::trades), and [`Client::accept_trade`](crate::Client::accept_trade)
+/// moves the items between the fake inventories.
+///
+/// Mutating endpoints require a valid x-csrf-token, which the fake hands out through a 403 response
+/// like Roblox does. Faults such as x-csrf-token rotation, status code 429, and challenges can be
+/// injected using [`FakeRoblox::inject_fault`].
+///
+/// Attach using [`ClientBuilder::fake_roblox`](crate::ClientBuilder::fake_roblox). The fake is cheap to
+/// clone and every clone shares the same state, so it can be used by multiple clients at once.
+///
+/// # Example
+/// ```
+/// use roboat::testing::{FakeCollectible, FakeRoblox, FakeUser};
+/// use roboat::trades::TradeType;
+/// use roboat::{ClientBuilder, Limit};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let fake = FakeRoblox::new();
+///
+/// fake.add_user(FakeUser::new(1, "alice").roblosecurity("alice-cookie").robux(1000));
+/// fake.add_user(FakeUser::new(2, "bob").roblosecurity("bob-cookie"));
+/// fake.add_collectible(1, FakeCollectible::new(100, 1365767, "Valkyrie Helm").rap(50000));
+/// fake.add_collectible(2, FakeCollectible::new(200, 1029025, "The Classic ROBLOX Fedora").rap(9000));
+///
+/// let alice = ClientBuilder::new()
+///     .roblosecurity("alice-cookie".to_string())
+///     .fake_roblox(fake.clone())
+///     .build();
+///
+/// let bob = ClientBuilder::new()
+///     .roblosecurity("bob-cookie".to_string())
+///     .fake_roblox(fake.clone())
+///     .build();
+///
+/// let trade_id = alice.send_trade(2, vec![100], 0, vec![200], 0).await?;
+///
+/// let (outbound, _) = alice.trades(TradeType::Outbound, Limit::Ten, None).await?;
+/// assert_eq!(outbound[0].trade_id, trade_id);
+///
+/// bob.accept_trade(trade_id).await?;
+///
+/// assert_eq!(fake.collectibles(2)[0].uaid, 100);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FakeRoblox {
+    state: Arc<Mutex<FakeState>>,
+}
+
+/// A user that exists in a [`FakeRoblox`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct FakeUser {
+    /// The id of the user.
+    pub user_id: u64,
+    /// The username of the user.
+    pub username: String,
+    /// The display name of the user. Defaults to the username.
+    pub display_name: String,
+    /// The roblosecurity that authenticates as this user, if any.
+    pub roblosecurity: Option<String>,
+    /// The robux balance of the user.
+    pub robux: u64,
+}
+
+/// A limited item owned by a user in a [`FakeRoblox`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct FakeCollectible {
+    /// The unique asset id of this copy of the item.
+    pub uaid: u64,
+    /// The asset id of the item.
+    pub asset_id: u64,
+    /// The name of the item.
+    pub name: String,
+    /// The serial number of the item. Only exists for limited Us.
+    pub serial_number: Option<u64>,
+    /// The recent average price of the item.
+    pub rap: u64,
+    /// The price the item was originally sold for.
+    pub original_price: Option<u64>,
+    /// The resale price of this copy, if it is on sale.
+    pub resale_price: Option<u64>,
+    /// Whether this copy is on hold, which prevents it from being traded.
+    pub on_hold: bool,
+}
+
+/// An item in the catalog of a [`FakeRoblox`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct FakeCatalogItem {
+    /// The id of the item (or bundle).
+    pub id: u64,
+    /// The type of the item.
+    pub item_type: ItemType,
+    /// The raw asset type id of the item (e.g. 8 for hats). Only used for assets.
+    pub asset_type_id: Option<u64>,
+    /// The name of the item.
+    pub name: String,
+    /// The description of the item.
+    pub description: String,
+    /// The product id of the item, used to purchase it.
+    pub product_id: u64,
+    /// The price of the item, if it is on sale from the creator.
+    pub price: Option<u64>,
+    /// The id of the creator.
+    pub creator_id: u64,
+    /// The name of the creator.
+    pub creator_name: String,
+    /// Whether the creator is a user or a group.
+    pub creator_type: CreatorType,
+}
+
+/// A group in a [`FakeRoblox`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct FakeGroup {
+    /// The id of the group.
+    pub group_id: u64,
+    /// The roles of the group. `member_count` is computed by the fake.
+    pub roles: Vec<Role>,
+    /// The members of the group, mapped to the id of their role.
+    pub members: BTreeMap<u64, u64>,
+}
+
+/// A trade stored in a [`FakeRoblox`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct FakeTrade {
+    /// The id of the trade.
+    pub trade_id: u64,
+    /// The user that sent the trade.
+    pub sender_id: u64,
+    /// The uaids offered by the sender.
+    pub sender_uaids: Vec<u64>,
+    /// The robux offered by the sender.
+    pub sender_robux: u64,
+    /// The user that received the trade.
+    pub receiver_id: u64,
+    /// The uaids requested from the receiver.
+    pub receiver_uaids: Vec<u64>,
+    /// The robux requested from the receiver.
+    pub receiver_robux: u64,
+    /// The status of the trade.
+    pub status: TradeStatus,
+}
+
+/// A fault that a [`FakeRoblox`] injects into the next request it receives.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Fault {
+    /// Rotates the x-csrf-token, so that the next request requiring one is rejected with a new token.
+    RotateXcsrf,
+    /// Responds with status code 429.
+    TooManyRequests,
+    /// Responds with a 403 that requires a challenge with the given challenge id.
+    Challenge(String),
+    /// Responds with the given status code and an empty body.
+    Status(u16),
+    /// If the request is authenticated, replaces the roblosecurity of the user with the given one
+    /// and sends it back in a `set-cookie` header, like Roblox does when it rotates a roblosecurity.
+    RotateRoblosecurity(String),
+}
+
+#[derive(Debug, Default)]
+struct FakeState {
+    users: BTreeMap<u64, FakeUser>,
+    collectibles: BTreeMap<u64, Vec<FakeCollectible>>,
+    catalog: BTreeMap<u64, FakeCatalogItem>,
+    groups: BTreeMap<u64, FakeGroup>,
+    friendships: BTreeMap<u64, Vec<u64>>,
+    /// Pending friend requests, in the form of `(sender, receiver)`.
+    friend_requests: Vec<(u64, u64)>,
+    presences: HashMap<u64, u8>,
+    trades: Vec<FakeTrade>,
+    /// Sales made by each user, in the form of `(sale id, buyer, asset id, robux received)`.
+    sales: BTreeMap<u64, Vec<(u64, u64, u64, u64)>>,
+    next_id: u64,
+    xcsrf_generation: u64,
+    faults: VecDeque<Fault>,
+}
+
+/// A parsed request that the fake routes on.
+struct FakeRequest {
+    method: Method,
+    host: String,
+    segments: Vec<String>,
+    query: HashMap<String, String>,
+    body: Value,
+    user_id: Option<u64>,
+    has_valid_xcsrf: bool,
+}
+
+/// A response produced by the fake, before it is converted into a [`Response`].
+struct FakeResponse {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Value,
+}
+
+impl FakeUser {
+    /// Creates a new user with the display name set to the username, no roblosecurity, and no robux.
+    pub fn new(user_id: u64, username: impl Into<String>) -> Self {
+        let username = username.into();
+
+        Self {
+            user_id,
+            display_name: username.clone(),
+            username,
+            roblosecurity: None,
+            robux: 0,
+        }
+    }
+
+    /// Sets the display name of the user.
+    pub fn display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = display_name.into();
+        self
+    }
+
+    /// Sets the roblosecurity that authenticates as this user.
+    pub fn roblosecurity(mut self, roblosecurity: impl Into<String>) -> Self {
+        self.roblosecurity = Some(roblosecurity.into());
+        self
+    }
+
+    /// Sets the robux balance of the user.
+    pub fn robux(mut self, robux: u64) -> Self {
+        self.robux = robux;
+        self
+    }
+}
+
+impl FakeCollectible {
+    /// Creates a new collectible with no serial number, no rap, and not on sale.
+    pub fn new(uaid: u64, asset_id: u64, name: impl Into<String>) -> Self {
+        Self {
+            uaid,
+            asset_id,
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the serial number of the collectible.
+    pub fn serial_number(mut self, serial_number: u64) -> Self {
+        self.serial_number = Some(serial_number);
+        self
+    }
+
+    /// Sets the recent average price of the collectible.
+    pub fn rap(mut self, rap: u64) -> Self {
+        self.rap = rap;
+        self
+    }
+
+    /// Sets the original price of the collectible.
+    pub fn original_price(mut self, original_price: u64) -> Self {
+        self.original_price = Some(original_price);
+        self
+    }
+
+    /// Puts the collectible on sale for `price`.
+    pub fn resale_price(mut self, price: u64) -> Self {
+        self.resale_price = Some(price);
+        self
+    }
+
+    /// Puts the collectible on hold, or takes it off hold.
+    pub fn on_hold(mut self, on_hold: bool) -> Self {
+        self.on_hold = on_hold;
+        self
+    }
+}
+
+impl FakeRoblox {
+    /// Creates a new, empty fake.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a user, replacing any user with the same id.
+    pub fn add_user(&self, user: FakeUser) {
+        self.state.lock().unwrap().users.insert(user.user_id, user);
+    }
+
+    /// Gives a collectible to a user.
+    pub fn add_collectible(&self, user_id: u64, collectible: FakeCollectible) {
+        self.state
+            .lock()
+            .unwrap()
+            .collectibles
+            .entry(user_id)
+            .or_default()
+            .push(collectible);
+    }
+
+    /// Adds an item to the catalog, replacing any item with the same id.
+    pub fn add_catalog_item(&self, item: FakeCatalogItem) {
+        self.state.lock().unwrap().catalog.insert(item.id, item);
+    }
+
+    /// Adds a group, replacing any group with the same id.
+    pub fn add_group(&self, group: FakeGroup) {
+        self.state
+            .lock()
+            .unwrap()
+            .groups
+            .insert(group.group_id, group);
+    }
+
+    /// Makes two users friends.
+    pub fn add_friendship(&self, user_id_1: u64, user_id_2: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.add_friendship(user_id_1, user_id_2);
+    }
+
+    /// Sends a friend request from `sender_id` to `receiver_id`.
+    pub fn add_friend_request(&self, sender_id: u64, receiver_id: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .friend_requests
+            .push((sender_id, receiver_id));
+    }
+
+    /// Sets the raw presence type of a user (0 = offline, 1 = online, 2 = in game, 3 = in studio, 4 = invisible).
+    pub fn set_presence(&self, user_id: u64, presence_type: u8) {
+        self.state
+            .lock()
+            .unwrap()
+            .presences
+            .insert(user_id, presence_type);
+    }
+
+    /// Queues a fault that is injected into the next request received. Faults are injected in the
+    /// order they were queued, one per request.
+    pub fn inject_fault(&self, fault: Fault) {
+        self.state.lock().unwrap().faults.push_back(fault);
+    }
+
+    /// Returns a copy of a user, if it exists.
+    pub fn user(&self, user_id: u64) -> Option<FakeUser> {
+        self.state.lock().unwrap().users.get(&user_id).cloned()
+    }
+
+    /// Returns the robux balance of a user, or 0 if the user does not exist.
+    pub fn robux(&self, user_id: u64) -> u64 {
+        self.user(user_id).map(|x| x.robux).unwrap_or_default()
+    }
+
+    /// Returns a copy of the collectibles owned by a user.
+    pub fn collectibles(&self, user_id: u64) -> Vec<FakeCollectible> {
+        self.state
+            .lock()
+            .unwrap()
+            .collectibles
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns a copy of a trade, if it exists.
+    pub fn trade(&self, trade_id: u64) -> Option<FakeTrade> {
+        self.state
+            .lock()
+            .unwrap()
+            .trades
+            .iter()
+            .find(|x| x.trade_id == trade_id)
+            .cloned()
+    }
+
+    /// Returns a copy of a group, if it exists.
+    pub fn group(&self, group_id: u64) -> Option<FakeGroup> {
+        self.state.lock().unwrap().groups.get(&group_id).cloned()
+    }
+
+    /// Returns the ids of the friends of a user.
+    pub fn friends(&self, user_id: u64) -> Vec<u64> {
+        self.state
+            .lock()
+            .unwrap()
+            .friendships
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Answers a request using the in-memory state.
+    pub(crate) fn handle(&self, request: Request) -> Result<Response, RoboatError> {
+        let url = request.url().clone();
+        let mut state = self.state.lock().unwrap();
+
+        let fake_request = state.parse_request(&request);
+        let fake_response = state.route(fake_request);
+
+        let mut builder = http::Response::builder()
+            .status(fake_response.status)
+            .url(url)
+            .header(header::CONTENT_TYPE, "application/json");
+
+        for (name, value) in fake_response.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder
+            .body(Bytes::from(fake_response.body.to_string()))
+            .map_err(|_| RoboatError::MalformedResponse)?;
+
+        Ok(Response::from(response))
+    }
+}
+
+impl FakeResponse {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    fn error(status: u16, code: u16, message: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: json!({ "errors": [{ "code": code, "message": message }] }),
+        }
+    }
+
+    fn unauthorized() -> Self {
+        Self::error(401, 0, "Authorization has been denied for this request.")
+    }
+
+    fn not_found() -> Self {
+        Self::error(404, 0, "NotFound")
+    }
+
+    fn bad_request(message: &str) -> Self {
+        Self::error(400, 1, message)
+    }
+}
+
+impl FakeState {
+    fn parse_request(&self, request: &Request) -> FakeRequest {
+        let url = request.url();
+
+        let segments = url
+            .path_segments()
+            .map(|x| x.filter(|x| !x.is_empty()).map(|x| x.to_string()).collect())
+            .unwrap_or_default();
+
+        let query = url
+            .query_pairs()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let body = request
+            .body()
+            .and_then(|x| x.as_bytes())
+            .and_then(|x| serde_json::from_slice(x).ok())
+            .unwrap_or(Value::Null);
+
+        let user_id = request
+            .headers()
+            .get(header::COOKIE)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.strip_prefix(".ROBLOSECURITY="))
+            .and_then(|roblosecurity| {
+                self.users
+                    .values()
+                    .find(|x| x.roblosecurity.as_deref() == Some(roblosecurity))
+                    .map(|x| x.user_id)
+            });
+
+        let has_valid_xcsrf = request
+            .headers()
+            .get(XCSRF_HEADER)
+            .map(|x| x.as_bytes() == self.xcsrf().as_bytes())
+            .unwrap_or(false);
+
+        FakeRequest {
+            method: request.method().clone(),
+            host: url.host_str().unwrap_or_default().to_string(),
+            segments,
+            query,
+            body,
+            user_id,
+            has_valid_xcsrf,
+        }
+    }
+
+    fn xcsrf(&self) -> String {
+        format!("fake-xcsrf-{}", self.xcsrf_generation)
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn add_friendship(&mut self, user_id_1: u64, user_id_2: u64) {
+        let friends_1 = self.friendships.entry(user_id_1).or_default();
+        if !friends_1.contains(&user_id_2) {
+            friends_1.push(user_id_2);
+        }
+
+        let friends_2 = self.friendships.entry(user_id_2).or_default();
+        if !friends_2.contains(&user_id_1) {
+            friends_2.push(user_id_1);
+        }
+    }
+
+    fn user_json(&self, user_id: u64) -> Value {
+        let user = self.users.get(&user_id).cloned().unwrap_or_default();
+
+        json!({
+            "id": user_id,
+            "name": user.username,
+            "displayName": user.display_name,
+        })
+    }
+
+    fn route(&mut self, mut request: FakeRequest) -> FakeResponse {
+        let mut rotated_roblosecurity = None;
+
+        if let Some(fault) = self.faults.pop_front() {
+            match fault {
+                Fault::RotateXcsrf => {
+                    self.xcsrf_generation += 1;
+                    request.has_valid_xcsrf = false;
+                }
+                Fault::TooManyRequests => return FakeResponse::error(429, 0, "Too many requests"),
+                Fault::Challenge(challenge_id) => return challenge_response(&challenge_id),
+                Fault::Status(status) => {
+                    return FakeResponse {
+                        status,
+                        headers: Vec::new(),
+                        body: Value::Null,
+                    }
+                }
+                Fault::RotateRoblosecurity(roblosecurity) => {
+                    if let Some(user) = request.user_id.and_then(|x| self.users.get_mut(&x)) {
+                        user.roblosecurity = Some(roblosecurity.clone());
+                        rotated_roblosecurity = Some(roblosecurity);
+                    }
+                }
+            }
+        }
+
+        let mut response = self.route_request(request);
+
+        if let Some(roblosecurity) = rotated_roblosecurity {
+            response.headers.push((
+                "set-cookie",
+                format!(
+                    ".ROBLOSECURITY={}; domain=.roblox.com; path=/; secure; HttpOnly",
+                    roblosecurity
+                ),
+            ));
+        }
+
+        response
+    }
+
+    fn route_request(&mut self, request: FakeRequest) -> FakeResponse {
+        let segments: Vec<&str> = request.segments.iter().map(|x| x.as_str()).collect();
+        let method = request.method.clone();
+
+        let route = (method.as_str(), request.host.as_str(), segments.as_slice());
+
+        // Requests that the client sends an x-csrf-token with are rejected without a valid one.
+        let requires_xcsrf = !matches!(
+            route,
+            ("GET", _, _)
+                | ("POST", "thumbnails.roblox.com", _)
+                | ("POST", "users.roblox.com", _)
+                | ("POST", "presence.roblox.com", [_, _, "users"])
+        );
+
+        if requires_xcsrf && !request.has_valid_xcsrf {
+            return FakeResponse {
+                status: 403,
+                headers: vec![("x-csrf-token", self.xcsrf())],
+                body: json!({ "errors": [{ "code": 0, "message": "Token Validation Failed" }] }),
+            };
+        }
+
+        match route {
+            // Users API
+            ("GET", "users.roblox.com", ["v1", "users", "authenticated"]) => {
+                match request.user_id {
+                    Some(user_id) => FakeResponse::ok(self.user_json(user_id)),
+                    None => FakeResponse::unauthorized(),
+                }
+            }
+            ("GET", "users.roblox.com", ["v1", "users", "search"]) => self.user_search(&request),
+            ("GET", "users.roblox.com", ["v1", "users", user_id]) => self.user_details(user_id),
+            ("POST", "users.roblox.com", ["v1", "usernames", "users"]) => {
+                self.username_user_details(&request)
+            }
+            // Inventory API
+            ("GET", "inventory.roblox.com", ["v1", "users", user_id, "assets", "collectibles"]) => {
+                self.inventory_collectibles(&request, user_id)
+            }
+            ("GET", "inventory.roblox.com", ["v2", "users", user_id, "inventory", _]) => {
+                self.inventory(&request, user_id)
+            }
+            ("GET", "inventory.roblox.com", ["v1", "users", user_id, "can-view-inventory"]) => {
+                match self
+                    .users
+                    .contains_key(&user_id.parse().unwrap_or_default())
+                {
+                    true => FakeResponse::ok(json!({ "canView": true })),
+                    false => FakeResponse::bad_request("The specified user does not exist!"),
+                }
+            }
+            (
+                "GET",
+                "inventory.roblox.com",
+                ["v1", "users", user_id, "items", item_type, item_id, "is-owned"],
+            ) => self.is_owned(user_id, item_type, item_id),
+            // Economy API
+            ("GET", "economy.roblox.com", ["v1", "users", user_id, "currency"]) => {
+                self.currency(&request, user_id)
+            }
+            ("GET", "economy.roblox.com", ["v1", "assets", asset_id, "resellers"]) => {
+                self.resellers(&request, asset_id)
+            }
+            ("GET", "economy.roblox.com", ["v2", "users", user_id, "transactions"]) => {
+                self.user_sales(&request, user_id)
+            }
+            ("PATCH", "economy.roblox.com", ["v1", "assets", _, "resellable-copies", uaid]) => {
+                self.toggle_sale(&request, uaid)
+            }
+            ("POST", "economy.roblox.com", ["v1", "purchases", "products", product_id]) => {
+                self.purchase_limited(&request, product_id)
+            }
+            // Trades API
+            ("GET", "trades.roblox.com", ["v1", "trades", "inbound", "count"]) => {
+                self.trade_count(&request)
+            }
+            ("POST", "trades.roblox.com", ["v1", "trades", "send"]) => self.send_trade(&request),
+            ("POST", "trades.roblox.com", ["v1", "trades", trade_id, "accept"]) => {
+                self.accept_trade(&request, trade_id)
+            }
+            ("POST", "trades.roblox.com", ["v1", "trades", trade_id, "decline"]) => {
+                self.decline_trade(&request, trade_id)
+            }
+            ("POST", "trades.roblox.com", ["v1", "trades", trade_id, "counter"]) => {
+                self.counter_trade(&request, trade_id)
+            }
+            ("GET", "trades.roblox.com", ["v1", "trades", "metadata"]) => {
+                self.trade_metadata(&request)
+            }
+            ("GET", "trades.roblox.com", ["v1", "users", user_id, "can-trade-with"]) => {
+                self.can_trade_with(&request, user_id)
+            }
+            (
+                "GET",
+                "trades.roblox.com",
+                ["v1", "trades", "inbound" | "outbound" | "completed" | "inactive"],
+            ) => self.trades(&request, segments[2]),
+            ("GET", "trades.roblox.com", ["v1", "trades", trade_id]) => {
+                self.trade_details(&request, trade_id)
+            }
+            // Account Settings API
+            ("GET", "accountsettings.roblox.com", ["v1", "trade-privacy"]) => {
+                match request.user_id {
+                    Some(_) => FakeResponse::ok(json!({ "tradePrivacy": "All" })),
+                    None => FakeResponse::unauthorized(),
+                }
+            }
+            // Catalog API
+            ("POST", "catalog.roblox.com", ["v1", "catalog", "items", "details"]) => {
+                self.item_details(&request)
+            }
+            ("GET", "catalog.roblox.com", ["v1", "search", "items"]) => self.catalog_search(),
+            ("GET", "catalog.roblox.com", ["v1", "users", user_id, "bundles"]) => {
+                self.bundles(&request, user_id)
+            }
+            // Friends API
+            ("GET", "friends.roblox.com", ["v1", "users", user_id, "friends"]) => {
+                self.friends_list(user_id)
+            }
+            ("GET", "friends.roblox.com", ["v1", "my", "friends", "requests"]) => {
+                self.friend_requests(&request)
+            }
+            ("GET", "friends.roblox.com", ["v1", "user", "friend-requests", "count"]) => {
+                self.pending_friend_requests(&request)
+            }
+            ("POST", "friends.roblox.com", ["v1", "users", user_id, action]) => {
+                self.friend_action(&request, user_id, action)
+            }
+            // Groups API
+            ("GET", "groups.roblox.com", ["v1", "groups", group_id, "roles"]) => {
+                self.group_roles(group_id)
+            }
+            ("GET", "groups.roblox.com", ["v1", "groups", group_id, "roles", role_id, "users"]) => {
+                self.group_role_members(&request, group_id, role_id)
+            }
+            ("PATCH", "groups.roblox.com", ["v1", "groups", group_id, "users", user_id]) => {
+                self.set_group_member_role(&request, group_id, user_id)
+            }
+            // Thumbnails API
+            ("POST", "thumbnails.roblox.com", ["v1", "batch"]) => self.thumbnails(&request),
+            // Presence API
+            ("POST", "presence.roblox.com", ["v1", "presence", "register-app-presence"]) => {
+                match request.user_id {
+                    Some(user_id) => {
+                        self.presences.insert(user_id, 1);
+                        FakeResponse::ok(json!({}))
+                    }
+                    None => FakeResponse::unauthorized(),
+                }
+            }
+            ("POST", "presence.roblox.com", ["v1", "presence", "users"]) => {
+                self.users_presence(&request)
+            }
+            _ => FakeResponse::not_found(),
+        }
+    }
+
+    fn user_search(&self, request: &FakeRequest) -> FakeResponse {
+        let keyword = request
+            .query
+            .get("keyword")
+            .map(|x| x.to_lowercase())
+            .unwrap_or_default();
+
+        let data: Vec<Value> = self
+            .users
+            .values()
+            .filter(|x| {
+                x.username.to_lowercase().contains(&keyword)
+                    || x.display_name.to_lowercase().contains(&keyword)
+            })
+            .map(|x| {
+                json!({
+                    "id": x.user_id,
+                    "name": x.username,
+                    "hasVerifiedBadge": false,
+                    "previousUsernames": [],
+                    "displayName": x.display_name,
+                })
+            })
+            .collect();
+
+        FakeResponse::ok(json!({
+            "previousPageCursor": null,
+            "nextPageCursor": "",
+            "data": data,
+        }))
+    }
+
+    fn user_details(&self, user_id: &str) -> FakeResponse {
+        let user = match user_id.parse().ok().and_then(|x: u64| self.users.get(&x)) {
+            Some(x) => x,
+            None => return FakeResponse::not_found(),
+        };
+
+        FakeResponse::ok(json!({
+            "name": user.username,
+            "displayName": user.display_name,
+            "id": user.user_id,
+            "description": "",
+            "created": FAKE_TIMESTAMP,
+            "isBanned": false,
+            "hasVerifiedBadge": false,
+        }))
+    }
+
+    fn username_user_details(&self, request: &FakeRequest) -> FakeResponse {
+        let usernames: Vec<String> =
+            serde_json::from_value(request.body["usernames"].clone()).unwrap_or_default();
+
+        let data: Vec<Value> = usernames
+            .iter()
+            .filter_map(|requested_username| {
+                self.users
+                    .values()
+                    .find(|x| x.username.eq_ignore_ascii_case(requested_username))
+                    .map(|x| {
+                        json!({
+                            "requestedUsername": requested_username,
+                            "hasVerifiedBadge": false,
+                            "id": x.user_id,
+                            "name": x.username,
+                            "displayName": x.display_name,
+                        })
+                    })
+            })
+            .collect();
+
+        FakeResponse::ok(json!({ "data": data }))
+    }
+
+    fn currency(&self, request: &FakeRequest, user_id: &str) -> FakeResponse {
+        // Roblox only lets you view your own balance.
+        match request.user_id {
+            Some(x) if x.to_string() == user_id => {
+                FakeResponse::ok(json!({ "robux": self.users[&x].robux }))
+            }
+            _ => FakeResponse::unauthorized(),
+        }
+    }
+
+    fn inventory_collectibles(&self, request: &FakeRequest, user_id: &str) -> FakeResponse {
+        let user_id: u64 = user_id.parse().unwrap_or_default();
+
+        if !self.users.contains_key(&user_id) {
+            return FakeResponse::bad_request("The specified user does not exist!");
+        }
+
+        let data: Vec<Value> = self
+            .collectibles
+            .get(&user_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|x| {
+                json!({
+                    "userAssetId": x.uaid,
+                    "serialNumber": x.serial_number,
+                    "assetId": x.asset_id,
+                    "name": x.name,
+                    "recentAveragePrice": x.rap,
+                    "originalPrice": x.original_price,
+                    "assetStock": null,
+                    "buildersClubMembershipType": "None",
+                    "isOnHold": x.on_hold,
+                })
+            })
+            .collect();
+
+        paginate(request, data)
+    }
+
+    /// Collectibles have no asset type in the fake, so they are listed under every asset type.
+    fn inventory(&self, request: &FakeRequest, user_id: &str) -> FakeResponse {
+        let user_id: u64 = user_id.parse().unwrap_or_default();
+
+        if !self.users.contains_key(&user_id) {
+            return FakeResponse::bad_request("The specified user does not exist!");
+        }
+
+        let data: Vec<Value> = self
+            .collectibles
+            .get(&user_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|x| {
+                json!({
+                    "userAssetId": x.uaid,
+                    "assetId": x.asset_id,
+                    "assetName": x.name,
+                    "serialNumber": x.serial_number,
+                    "created": "2020-01-01T00:00:00Z",
+                    "updated": "2020-01-01T00:00:00Z",
+                })
+            })
+            .collect();
+
+        paginate(request, data)
+    }
+
+    /// Bundles are not modelled by the fake, so every user owns none.
+    fn bundles(&self, request: &FakeRequest, user_id: &str) -> FakeResponse {
+        let user_id: u64 = user_id.parse().unwrap_or_default();
+
+        if !self.users.contains_key(&user_id) {
+            return FakeResponse::bad_request("The specified user does not exist!");
+        }
+
+        paginate(request, Vec::new())
+    }
+
+    fn is_owned(&self, user_id: &str, item_type: &str, item_id: &str) -> FakeResponse {
+        let user_id: u64 = user_id.parse().unwrap_or_default();
+        let item_id: u64 = item_id.parse().unwrap_or_default();
+
+        if !self.users.contains_key(&user_id) {
+            return FakeResponse::bad_request("The specified user does not exist!");
+        }
+
+        // Only assets can be owned in the fake, other items are never owned.
+        let owned = match item_type {
+            "Asset" => self
+                .collectibles
+                .get(&user_id)
+                .is_some_and(|x| x.iter().any(|x| x.asset_id == item_id)),
+            "Bundle" | "GamePass" | "Badge" => false,
+            _ => return FakeResponse::bad_request("The specified item type does not exist."),
+        };
+
+        FakeResponse::ok(json!(owned))
+    }
+
+    fn resellers(&self, request: &FakeRequest, asset_id: &str) -> FakeResponse {
+        if request.user_id.is_none() {
+            return FakeResponse::unauthorized();
+        }
+
+        let asset_id: u64 = asset_id.parse().unwrap_or_default();
+
+        let mut listings: Vec<(u64, &FakeCollectible)> = self
+            .collectibles
+            .iter()
+            .flat_map(|(owner, items)| items.iter().map(move |x| (*owner, x)))
+            .filter(|(_, x)| x.asset_id == asset_id && x.resale_price.is_some())
+            .collect();
+
+        listings.sort_by_key(|(_, x)| (x.resale_price, x.uaid));
+
+        let data: Vec<Value> = listings
+            .iter()
+            .map(|(owner, x)| {
+                json!({
+                    "userAssetId": x.uaid,
+                    "seller": {
+                        "hasVerifiedBadge": false,
+                        "id": owner,
+                        "type": "User",
+                        "name": self.users.get(owner).map(|x| x.username.clone()).unwrap_or_default(),
+                    },
+                    "price": x.resale_price,
+                    "serialNumber": x.serial_number,
+                })
+            })
+            .collect();
+
+        paginate(request, data)
+    }
+
+    fn user_sales(&self, request: &FakeRequest, user_id: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) if x.to_string() == user_id => x,
+            _ => return FakeResponse::unauthorized(),
+        };
+
+        let data: Vec<Value> = self
+            .sales
+            .get(&user_id)
+            .map(|sales| {
+                sales
+                    .iter()
+                    .rev()
+                    .map(|(sale_id, buyer_id, asset_id, robux_received)| {
+                        let asset_name = self
+                            .catalog
+                            .get(asset_id)
+                            .map(|x| x.name.clone())
+                            .unwrap_or_default();
+
+                        json!({
+                            "id": sale_id,
+                            "isPending": false,
+                            "agent": {
+                                "id": buyer_id,
+                                "name": self.users.get(buyer_id).map(|x| x.display_name.clone()).unwrap_or_default(),
+                            },
+                            "details": { "id": asset_id, "name": asset_name },
+                            "currency": { "amount": robux_received, "type": "Robux" },
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        paginate(request, data)
+    }
+
+    fn toggle_sale(&mut self, request: &FakeRequest, uaid: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let uaid: u64 = uaid.parse().unwrap_or_default();
+        let price = request.body["price"].as_u64();
+
+        match self
+            .collectibles
+            .get_mut(&user_id)
+            .and_then(|items| items.iter_mut().find(|x| x.uaid == uaid))
+        {
+            Some(collectible) => {
+                collectible.resale_price = price;
+                FakeResponse::ok(json!({}))
+            }
+            None => FakeResponse::bad_request("The user does not own this item."),
+        }
+    }
+
+    fn purchase_limited(&mut self, request: &FakeRequest, product_id: &str) -> FakeResponse {
+        let buyer_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let purchase_error =
+            |message: &str| FakeResponse::ok(json!({ "purchased": false, "errorMsg": message }));
+
+        let product_id: u64 = product_id.parse().unwrap_or_default();
+        let expected_price = request.body["expectedPrice"].as_u64().unwrap_or_default();
+        let seller_id = request.body["expectedSellerId"]
+            .as_u64()
+            .unwrap_or_default();
+        let uaid = request.body["userAssetId"].as_u64().unwrap_or_default();
+
+        let asset_id = self
+            .catalog
+            .values()
+            .find(|x| x.product_id == product_id)
+            .map(|x| x.id);
+
+        let index = self.collectibles.get(&seller_id).and_then(|items| {
+            items
+                .iter()
+                .position(|x| x.uaid == uaid && Some(x.asset_id) == asset_id)
+        });
+
+        let index = match index {
+            Some(x) if self.collectibles[&seller_id][x].resale_price.is_some() => x,
+            _ => return purchase_error("This item is not for sale."),
+        };
+
+        if seller_id == buyer_id {
+            return purchase_error("You already own this item.");
+        }
+
+        let price = self.collectibles[&seller_id][index]
+            .resale_price
+            .unwrap_or_default();
+
+        if price != expected_price {
+            return purchase_error("This item has changed price. Please try again.");
+        }
+
+        if self
+            .users
+            .get(&buyer_id)
+            .map(|x| x.robux)
+            .unwrap_or_default()
+            < price
+        {
+            return purchase_error("You do not have enough Robux to purchase this item.");
+        }
+
+        let mut collectible = self.collectibles.get_mut(&seller_id).unwrap().remove(index);
+        collectible.resale_price = None;
+        let asset_id = collectible.asset_id;
+
+        self.collectibles
+            .entry(buyer_id)
+            .or_default()
+            .push(collectible);
+
+        let robux_received = (price as f64 * SELLER_SHARE) as u64;
+
+        if let Some(buyer) = self.users.get_mut(&buyer_id) {
+            buyer.robux -= price;
+        }
+
+        if let Some(seller) = self.users.get_mut(&seller_id) {
+            seller.robux += robux_received;
+        }
+
+        let sale_id = self.next_id();
+        self.sales.entry(seller_id).or_default().push((
+            sale_id,
+            buyer_id,
+            asset_id,
+            robux_received,
+        ));
+
+        FakeResponse::ok(json!({ "purchased": true, "errorMsg": "", "reason": "Success" }))
+    }
+
+    fn trade_count(&self, request: &FakeRequest) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let count = self
+            .trades
+            .iter()
+            .filter(|x| x.receiver_id == user_id && x.status == TradeStatus::Open)
+            .count();
+
+        FakeResponse::ok(json!({ "count": count }))
+    }
+
+    fn trade_metadata(&self, request: &FakeRequest) -> FakeResponse {
+        if request.user_id.is_none() {
+            return FakeResponse::unauthorized();
+        }
+
+        FakeResponse::ok(json!({
+            "maxItemsPerSide": 4,
+            "minValueRatio": 0.5,
+            "tradeSystemMaxRobuxPercent": 0.5,
+            "tradeRobuxTaxPercent": 0.3,
+        }))
+    }
+
+    fn can_trade_with(&self, request: &FakeRequest, user_id: &str) -> FakeResponse {
+        let own_user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let status = match user_id.parse::<u64>() {
+            Ok(x) if x == own_user_id => "CannotTradeWithSelf",
+            Ok(x) if self.users.contains_key(&x) => "CanTrade",
+            _ => "UnknownUser",
+        };
+
+        FakeResponse::ok(json!({ "canTrade": status == "CanTrade", "status": status }))
+    }
+
+    fn send_trade(&mut self, request: &FakeRequest) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        match self.create_trade(request, user_id) {
+            Ok(trade_id) => FakeResponse::ok(json!({ "id": trade_id })),
+            Err(response) => response,
+        }
+    }
+
+    fn counter_trade(&mut self, request: &FakeRequest, trade_id: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        // Only the receiver of an open trade can counter it, and only with the same partner.
+        let index = match self.trades.iter().position(|x| {
+            x.trade_id.to_string() == trade_id
+                && x.receiver_id == user_id
+                && x.status == TradeStatus::Open
+        }) {
+            Some(x) => x,
+            None => {
+                return FakeResponse::bad_request("The trade cannot be found or is not active.")
+            }
+        };
+
+        let partner_id = self.trades[index].sender_id;
+
+        let partner_matches = request.body["offers"]
+            .as_array()
+            .is_some_and(|x| x.iter().any(|x| x["userId"] == json!(partner_id)));
+
+        if !partner_matches {
+            return FakeResponse::bad_request("Invalid trade request.");
+        }
+
+        match self.create_trade(request, user_id) {
+            Ok(new_trade_id) => {
+                self.trades[index].status = TradeStatus::Countered;
+                FakeResponse::ok(json!({ "id": new_trade_id }))
+            }
+            Err(response) => response,
+        }
+    }
+
+    /// Creates an open trade from the offers in the body of a request, returning the id of the trade.
+    fn create_trade(&mut self, request: &FakeRequest, user_id: u64) -> Result<u64, FakeResponse> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct OfferReq {
+            user_id: u64,
+            user_asset_ids: Vec<u64>,
+            robux: u64,
+        }
+
+        let offers: Vec<OfferReq> = match serde_json::from_value(request.body["offers"].clone()) {
+            Ok(x) => x,
+            Err(_) => return Err(FakeResponse::bad_request("Invalid trade request.")),
+        };
+
+        let (own_offer, partner_offer) = match offers.as_slice() {
+            [a, b] if b.user_id == user_id => (b, a),
+            [a, b] if a.user_id == user_id => (a, b),
+            _ => return Err(FakeResponse::bad_request("Invalid trade request.")),
+        };
+
+        if !self.users.contains_key(&partner_offer.user_id) {
+            return Err(FakeResponse::bad_request("The user cannot be traded with."));
+        }
+
+        if !self.owns_all(user_id, &own_offer.user_asset_ids)
+            || !self.owns_all(partner_offer.user_id, &partner_offer.user_asset_ids)
+        {
+            return Err(FakeResponse::bad_request(
+                "One or more userAssets are invalid.",
+            ));
+        }
+
+        let trade_id = self.next_id();
+
+        self.trades.push(FakeTrade {
+            trade_id,
+            sender_id: user_id,
+            sender_uaids: own_offer.user_asset_ids.clone(),
+            sender_robux: own_offer.robux,
+            receiver_id: partner_offer.user_id,
+            receiver_uaids: partner_offer.user_asset_ids.clone(),
+            receiver_robux: partner_offer.robux,
+            status: TradeStatus::Open,
+        });
+
+        Ok(trade_id)
+    }
+
+    /// Returns whether the user owns every item, and none of them are on hold.
+    fn owns_all(&self, user_id: u64, uaids: &[u64]) -> bool {
+        let owned = self.collectibles.get(&user_id);
+
+        uaids.iter().all(|uaid| {
+            owned
+                .map(|items| items.iter().any(|x| x.uaid == *uaid && !x.on_hold))
+                .unwrap_or(false)
+        })
+    }
+
+    fn accept_trade(&mut self, request: &FakeRequest, trade_id: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let index = match self.trades.iter().position(|x| {
+            x.trade_id.to_string() == trade_id
+                && x.receiver_id == user_id
+                && x.status == TradeStatus::Open
+        }) {
+            Some(x) => x,
+            None => {
+                return FakeResponse::bad_request("The trade cannot be found or is not active.")
+            }
+        };
+
+        let trade = self.trades[index].clone();
+
+        let robux_available = |state: &FakeState, user_id: u64, robux: u64| {
+            state
+                .users
+                .get(&user_id)
+                .map(|x| x.robux)
+                .unwrap_or_default()
+                >= robux
+        };
+
+        if !self.owns_all(trade.sender_id, &trade.sender_uaids)
+            || !self.owns_all(trade.receiver_id, &trade.receiver_uaids)
+            || !robux_available(self, trade.sender_id, trade.sender_robux)
+            || !robux_available(self, trade.receiver_id, trade.receiver_robux)
+        {
+            self.trades[index].status = TradeStatus::RejectedDueToError;
+            return FakeResponse::ok(json!({}));
+        }
+
+        self.transfer(trade.sender_id, trade.receiver_id, &trade.sender_uaids);
+        self.transfer(trade.receiver_id, trade.sender_id, &trade.receiver_uaids);
+
+        // Robux sent in a trade is taxed 30%.
+        self.transfer_robux(trade.sender_id, trade.receiver_id, trade.sender_robux);
+        self.transfer_robux(trade.receiver_id, trade.sender_id, trade.receiver_robux);
+
+        self.trades[index].status = TradeStatus::Completed;
+
+        FakeResponse::ok(json!({}))
+    }
+
+    fn transfer(&mut self, from: u64, to: u64, uaids: &[u64]) {
+        let from_items = self.collectibles.entry(from).or_default();
+
+        let (moved, kept): (Vec<FakeCollectible>, Vec<FakeCollectible>) =
+            from_items.drain(..).partition(|x| uaids.contains(&x.uaid));
+
+        *from_items = kept;
+
+        self.collectibles
+            .entry(to)
+            .or_default()
+            .extend(moved.into_iter().map(|mut x| {
+                x.resale_price = None;
+                x
+            }));
+    }
+
+    fn transfer_robux(&mut self, from: u64, to: u64, robux: u64) {
+        if let Some(user) = self.users.get_mut(&from) {
+            user.robux -= robux;
+        }
+
+        if let Some(user) = self.users.get_mut(&to) {
+            user.robux += (robux as f64 * SELLER_SHARE) as u64;
+        }
+    }
+
+    fn decline_trade(&mut self, request: &FakeRequest, trade_id: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        // Both parties can decline an open trade. The sender declining it cancels it.
+        match self.trades.iter_mut().find(|x| {
+            x.trade_id.to_string() == trade_id
+                && (x.receiver_id == user_id || x.sender_id == user_id)
+                && x.status == TradeStatus::Open
+        }) {
+            Some(trade) => {
+                trade.status = TradeStatus::Declined;
+                FakeResponse::ok(json!({}))
+            }
+            None => FakeResponse::bad_request("The trade cannot be found or is not active."),
+        }
+    }
+
+    fn trades(&self, request: &FakeRequest, trade_type: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let data: Vec<Value> = self
+            .trades
+            .iter()
+            .rev()
+            .filter(|x| match trade_type {
+                "inbound" => x.receiver_id == user_id && x.status == TradeStatus::Open,
+                "outbound" => x.sender_id == user_id && x.status == TradeStatus::Open,
+                "completed" => {
+                    (x.receiver_id == user_id || x.sender_id == user_id)
+                        && x.status == TradeStatus::Completed
+                }
+                _ => {
+                    (x.receiver_id == user_id || x.sender_id == user_id)
+                        && x.status != TradeStatus::Open
+                        && x.status != TradeStatus::Completed
+                }
+            })
+            .map(|x| {
+                let partner_id = match x.sender_id == user_id {
+                    true => x.receiver_id,
+                    false => x.sender_id,
+                };
+
+                json!({
+                    "id": x.trade_id,
+                    "user": self.user_json(partner_id),
+                    "created": FAKE_TIMESTAMP,
+                    "expiration": FAKE_EXPIRATION_TIMESTAMP,
+                    "isActive": x.status == TradeStatus::Open,
+                    "status": x.status,
+                })
+            })
+            .collect();
+
+        paginate(request, data)
+    }
+
+    fn trade_details(&self, request: &FakeRequest, trade_id: &str) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let trade = match self.trades.iter().find(|x| {
+            x.trade_id.to_string() == trade_id
+                && (x.receiver_id == user_id || x.sender_id == user_id)
+        }) {
+            Some(x) => x,
+            None => return FakeResponse::bad_request("The trade cannot be found."),
+        };
+
+        let sender_offer =
+            self.offer_json(trade.sender_id, &trade.sender_uaids, trade.sender_robux);
+        let receiver_offer = self.offer_json(
+            trade.receiver_id,
+            &trade.receiver_uaids,
+            trade.receiver_robux,
+        );
+
+        // The account's offer always comes first.
+        let (offers, partner_id) = match trade.sender_id == user_id {
+            true => (vec![sender_offer, receiver_offer], trade.receiver_id),
+            false => (vec![receiver_offer, sender_offer], trade.sender_id),
+        };
+
+        FakeResponse::ok(json!({
+            "offers": offers,
+            "id": trade.trade_id,
+            "user": self.user_json(partner_id),
+            "created": FAKE_TIMESTAMP,
+            "expiration": FAKE_EXPIRATION_TIMESTAMP,
+            "isActive": trade.status == TradeStatus::Open,
+            "status": trade.status,
+        }))
+    }
+
+    fn offer_json(&self, user_id: u64, uaids: &[u64], robux: u64) -> Value {
+        let user_assets: Vec<Value> = self
+            .collectibles
+            .values()
+            .flatten()
+            .filter(|x| uaids.contains(&x.uaid))
+            .map(|x| {
+                json!({
+                    "id": x.uaid,
+                    "serialNumber": x.serial_number,
+                    "assetId": x.asset_id,
+                    "name": x.name,
+                    "recentAveragePrice": x.rap,
+                    "originalPrice": x.original_price,
+                    "assetStock": null,
+                    "membershipType": null,
+                })
+            })
+            .collect();
+
+        json!({
+            "user": self.user_json(user_id),
+            "userAssets": user_assets,
+            "robux": robux,
+        })
+    }
+
+    fn item_details(&self, request: &FakeRequest) -> FakeResponse {
+        let ids: Vec<u64> = request.body["items"]
+            .as_array()
+            .map(|items| items.iter().filter_map(|x| x["id"].as_u64()).collect())
+            .unwrap_or_default();
+
+        let data: Vec<Value> = ids
+            .iter()
+            .filter_map(|id| self.catalog.get(id))
+            .map(|x| {
+                json!({
+                    "id": x.id,
+                    "itemType": x.item_type,
+                    "assetType": x.asset_type_id,
+                    "name": x.name,
+                    "description": x.description,
+                    "productId": x.product_id,
+                    "creatorHasVerifiedBadge": false,
+                    "creatorType": x.creator_type,
+                    "creatorTargetId": x.creator_id,
+                    "creatorName": x.creator_name,
+                    "price": x.price,
+                    "isOffSale": x.price.is_none(),
+                    "saleLocationType": "NotApplicable",
+                })
+            })
+            .collect();
+
+        FakeResponse::ok(json!({ "data": data }))
+    }
+
+    fn catalog_search(&self) -> FakeResponse {
+        let data: Vec<Value> = self
+            .catalog
+            .values()
+            .map(|x| json!({ "id": x.id, "itemType": x.item_type }))
+            .collect();
+
+        FakeResponse::ok(json!({
+            "previousPageCursor": null,
+            "nextPageCursor": null,
+            "data": data,
+        }))
+    }
+
+    fn friends_list(&self, user_id: &str) -> FakeResponse {
+        let user_id: u64 = user_id.parse().unwrap_or_default();
+
+        let data: Vec<Value> = self
+            .friendships
+            .get(&user_id)
+            .map(|x| x.iter().map(|x| self.user_json(*x)).collect())
+            .unwrap_or_default();
+
+        FakeResponse::ok(json!({ "data": data }))
+    }
+
+    fn friend_requests(&self, request: &FakeRequest) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let data: Vec<Value> = self
+            .friend_requests
+            .iter()
+            .filter(|(_, receiver)| *receiver == user_id)
+            .map(|(sender, _)| {
+                let mut value = self.user_json(*sender);
+
+                value["friendRequest"] = json!({
+                    "sentAt": FAKE_TIMESTAMP,
+                    "senderId": sender,
+                    "sourceUniverseId": 0,
+                    "originSourceType": "Unknown",
+                });
+                value["mutualFriendsList"] = json!([]);
+
+                value
+            })
+            .collect();
+
+        paginate(request, data)
+    }
+
+    fn pending_friend_requests(&self, request: &FakeRequest) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let count = self
+            .friend_requests
+            .iter()
+            .filter(|(_, receiver)| *receiver == user_id)
+            .count();
+
+        FakeResponse::ok(json!({ "count": count }))
+    }
+
+    fn friend_action(
+        &mut self,
+        request: &FakeRequest,
+        target_id: &str,
+        action: &str,
+    ) -> FakeResponse {
+        let user_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let target_id: u64 = match target_id.parse() {
+            Ok(x) if self.users.contains_key(&x) => x,
+            _ => return FakeResponse::bad_request("The target user is invalid or does not exist."),
+        };
+
+        let request_index = self
+            .friend_requests
+            .iter()
+            .position(|x| *x == (target_id, user_id));
+
+        match (action, request_index) {
+            ("accept-friend-request", Some(index)) => {
+                self.friend_requests.remove(index);
+                self.add_friendship(user_id, target_id);
+            }
+            ("decline-friend-request", Some(index)) => {
+                self.friend_requests.remove(index);
+            }
+            ("request-friendship", _) => {
+                if !self.friend_requests.contains(&(user_id, target_id)) {
+                    self.friend_requests.push((user_id, target_id));
+                }
+            }
+            ("unfriend", _) => {
+                if let Some(friends) = self.friendships.get_mut(&user_id) {
+                    friends.retain(|x| *x != target_id);
+                }
+
+                if let Some(friends) = self.friendships.get_mut(&target_id) {
+                    friends.retain(|x| *x != user_id);
+                }
+            }
+            ("accept-friend-request" | "decline-friend-request", None) => {
+                return FakeResponse::bad_request("The friend request does not exist.")
+            }
+            _ => return FakeResponse::not_found(),
+        }
+
+        FakeResponse::ok(json!({}))
+    }
+
+    fn group_roles(&self, group_id: &str) -> FakeResponse {
+        let group = match group_id.parse().ok().and_then(|x: u64| self.groups.get(&x)) {
+            Some(x) => x,
+            None => return FakeResponse::bad_request("Group is invalid or does not exist."),
+        };
+
+        let roles: Vec<Value> = group
+            .roles
+            .iter()
+            .map(|role| {
+                let member_count = group.members.values().filter(|x| **x == role.id).count();
+
+                json!({
+                    "id": role.id,
+                    "name": role.name,
+                    "rank": role.rank,
+                    "memberCount": member_count,
+                })
+            })
+            .collect();
+
+        FakeResponse::ok(json!({ "groupId": group.group_id, "roles": roles }))
+    }
+
+    fn group_role_members(
+        &self,
+        request: &FakeRequest,
+        group_id: &str,
+        role_id: &str,
+    ) -> FakeResponse {
+        let group = match group_id.parse().ok().and_then(|x: u64| self.groups.get(&x)) {
+            Some(x) => x,
+            None => return FakeResponse::bad_request("Group is invalid or does not exist."),
+        };
+
+        let role_id: u64 = role_id.parse().unwrap_or_default();
+
+        let data: Vec<Value> = group
+            .members
+            .iter()
+            .rev()
+            .filter(|(_, x)| **x == role_id)
+            .map(|(user_id, _)| {
+                let user = self.users.get(user_id).cloned().unwrap_or_default();
+
+                json!({
+                    "hasVerifiedBadge": false,
+                    "userId": user_id,
+                    "username": user.username,
+                    "displayName": user.display_name,
+                })
+            })
+            .collect();
+
+        paginate(request, data)
+    }
+
+    fn set_group_member_role(
+        &mut self,
+        request: &FakeRequest,
+        group_id: &str,
+        user_id: &str,
+    ) -> FakeResponse {
+        let requester_id = match request.user_id {
+            Some(x) => x,
+            None => return FakeResponse::unauthorized(),
+        };
+
+        let group = match group_id
+            .parse()
+            .ok()
+            .and_then(|x: u64| self.groups.get_mut(&x))
+        {
+            Some(x) => x,
+            None => return FakeResponse::bad_request("Group is invalid or does not exist."),
+        };
+
+        let user_id: u64 = user_id.parse().unwrap_or_default();
+        let role_id = request.body["roleId"].as_u64().unwrap_or_default();
+
+        let rank_of = |role_id: Option<&u64>| {
+            role_id
+                .and_then(|role_id| group.roles.iter().find(|x| x.id == *role_id))
+                .map(|x| x.rank)
+        };
+
+        let requester_rank = rank_of(group.members.get(&requester_id)).unwrap_or_default();
+        let target_rank = rank_of(group.members.get(&user_id));
+        let new_rank = rank_of(Some(&role_id));
+
+        match (target_rank, new_rank) {
+            (None, _) => FakeResponse::bad_request("The user is invalid or does not exist."),
+            (_, None) => FakeResponse::bad_request("The roleset is invalid or does not exist."),
+            (Some(target_rank), Some(new_rank))
+                if requester_rank <= target_rank || requester_rank <= new_rank =>
+            {
+                FakeResponse::error(403, 4, "You do not have permission to manage this member.")
+            }
+            _ => {
+                group.members.insert(user_id, role_id);
+                FakeResponse::ok(json!({}))
+            }
+        }
+    }
+
+    fn thumbnails(&self, request: &FakeRequest) -> FakeResponse {
+        let data: Vec<Value> = request
+            .body
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|x| {
+                        let target_id = x["targetId"].as_u64().unwrap_or_default();
+                        let thumbnail_type = x["type"].as_str().unwrap_or_default();
+                        let size = x["size"].as_str().unwrap_or_default();
+
+                        json!({
+                            "requestId": x["requestId"],
+                            "errorCode": 0,
+                            "errorMessage": "",
+                            "targetId": target_id,
+                            "state": "Completed",
+                            "imageUrl": format!("https://tr.rbxcdn.com/fake/{}/{}/{}", thumbnail_type, target_id, size),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        FakeResponse::ok(json!({ "data": data }))
+    }
+
+    fn users_presence(&self, request: &FakeRequest) -> FakeResponse {
+        let user_ids: Vec<u64> =
+            serde_json::from_value(request.body["userIds"].clone()).unwrap_or_default();
+
+        let user_presences: Vec<Value> = user_ids
+            .iter()
+            .map(|user_id| {
+                json!({
+                    "userPresenceType": self.presences.get(user_id).copied().unwrap_or(0),
+                    "lastLocation": "Website",
+                    "placeId": null,
+                    "rootPlaceId": null,
+                    "gameId": null,
+                    "universeId": null,
+                    "userId": user_id,
+                    "lastOnline": FAKE_TIMESTAMP,
+                })
+            })
+            .collect();
+
+        FakeResponse::ok(json!({ "userPresences": user_presences }))
+    }
+}
+
+/// Returns a page of `data` using the `cursor` and `limit` query parameters.
+/// The cursor used by the fake is the index of the first element of the page.
+fn paginate(request: &FakeRequest, data: Vec<Value>) -> FakeResponse {
+    let start: usize = request
+        .query
+        .get("cursor")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(0);
+
+    let limit: usize = request
+        .query
+        .get("limit")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+
+    let end = (start + limit).min(data.len());
+
+    let previous_page_cursor = match start {
+        0 => None,
+        _ => Some(start.saturating_sub(limit).to_string()),
+    };
+
+    let next_page_cursor = match end < data.len() {
+        true => Some(end.to_string()),
+        false => None,
+    };
+
+    FakeResponse::ok(json!({
+        "previousPageCursor": previous_page_cursor,
+        "nextPageCursor": next_page_cursor,
+        "data": data.get(start..end).unwrap_or_default(),
+    }))
+}
+
+/// Returns a 403 response requiring a challenge, in the format Roblox uses.
+fn challenge_response(challenge_id: &str) -> FakeResponse {
+    let metadata = json!({
+        "userId": "0",
+        "challengeId": challenge_id,
+        "shouldShowRememberDeviceCheckbox": false,
+        "rememberDevice": false,
+        "sessionCookie": "",
+        "verificationToken": "",
+        "actionType": "Generic",
+        "requestPath": "",
+        "requestMethod": "",
+    });
+
+    FakeResponse {
+        status: 403,
+        headers: vec![
+            ("rblx-challenge-id", challenge_id.to_string()),
+            ("rblx-challenge-type", "twostepverification".to_string()),
+            (
+                "rblx-challenge-metadata",
+                general_purpose::STANDARD.encode(metadata.to_string()),
+            ),
+        ],
+        body: json!({ "errors": [{ "code": 1, "message": CHALLENGE_MESSAGE }] }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FakeCollectible, FakeRoblox, FakeUser, Fault};
+    use crate::trades::{TradeStatus, TradeType};
+    use crate::{Client, ClientBuilder, Limit, RoboatError};
+
+    const ALICE_ID: u64 = 1;
+    const BOB_ID: u64 = 2;
+
+    /// Returns a fake where alice owns item 100 and 300 (on hold), bob owns item 200,
+    /// and a client for each of them.
+    fn setup() -> (FakeRoblox, Client, Client) {
+        let fake = FakeRoblox::new();
+
+        fake.add_user(
+            FakeUser::new(ALICE_ID, "alice")
+                .roblosecurity("alice-cookie")
+                .robux(1000),
+        );
+        fake.add_user(
+            FakeUser::new(BOB_ID, "bob")
+                .roblosecurity("bob-cookie")
+                .robux(500),
+        );
+        fake.add_collectible(ALICE_ID, FakeCollectible::new(100, 10, "Hat").rap(1000));
+        fake.add_collectible(
+            ALICE_ID,
+            FakeCollectible::new(300, 30, "Held Hat")
+                .rap(1000)
+                .on_hold(true),
+        );
+        fake.add_collectible(BOB_ID, FakeCollectible::new(200, 20, "Fedora").rap(1200));
+
+        let client = |roblosecurity: &str| {
+            ClientBuilder::new()
+                .roblosecurity(roblosecurity.to_string())
+                .fake_roblox(fake.clone())
+                .build()
+        };
+
+        let alice = client("alice-cookie");
+        let bob = client("bob-cookie");
+
+        (fake, alice, bob)
+    }
+
+    fn uaids(fake: &FakeRoblox, user_id: u64) -> Vec<u64> {
+        fake.collectibles(user_id).iter().map(|x| x.uaid).collect()
+    }
+
+    #[tokio::test]
+    async fn accepting_a_trade_moves_items_and_robux() {
+        let (fake, alice, bob) = setup();
+
+        let trade_id = alice
+            .send_trade(BOB_ID, vec![100], 100, vec![200], 0)
+            .await
+            .unwrap();
+
+        let (inbound, _) = bob
+            .trades(TradeType::Inbound, Limit::Ten, None)
+            .await
+            .unwrap();
+        assert_eq!(inbound.len(), 1);
+        assert_eq!(inbound[0].trade_id, trade_id);
+        assert_eq!(inbound[0].partner.user_id, ALICE_ID);
+
+        bob.accept_trade(trade_id).await.unwrap();
+
+        assert_eq!(fake.trade(trade_id).unwrap().status, TradeStatus::Completed);
+        assert_eq!(uaids(&fake, ALICE_ID), vec![300, 200]);
+        assert_eq!(uaids(&fake, BOB_ID), vec![100]);
+
+        // Robux sent in a trade is taxed 30%.
+        assert_eq!(fake.robux(ALICE_ID), 900);
+        assert_eq!(fake.robux(BOB_ID), 570);
+
+        let details = alice.trade_details(trade_id).await.unwrap();
+        assert_eq!(details.status, TradeStatus::Completed);
+        assert!(!details.is_active);
+    }
+
+    #[tokio::test]
+    async fn only_the_receiver_can_accept_a_trade() {
+        let (fake, alice, _) = setup();
+
+        let trade_id = alice
+            .send_trade(BOB_ID, vec![100], 0, vec![200], 0)
+            .await
+            .unwrap();
+
+        assert!(alice.accept_trade(trade_id).await.is_err());
+        assert_eq!(fake.trade(trade_id).unwrap().status, TradeStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn declining_an_outbound_trade_cancels_it() {
+        let (fake, alice, bob) = setup();
+
+        let trade_id = alice
+            .send_trade(BOB_ID, vec![100], 0, vec![200], 0)
+            .await
+            .unwrap();
+
+        alice.decline_trade(trade_id).await.unwrap();
+
+        assert_eq!(fake.trade(trade_id).unwrap().status, TradeStatus::Declined);
+        assert!(bob.accept_trade(trade_id).await.is_err());
+        assert_eq!(uaids(&fake, BOB_ID), vec![200]);
+    }
+
+    #[tokio::test]
+    async fn countering_a_trade_replaces_it() {
+        let (fake, alice, bob) = setup();
+
+        let trade_id = alice
+            .send_trade(BOB_ID, vec![100], 0, vec![200], 0)
+            .await
+            .unwrap();
+
+        let counter_id = bob
+            .counter_trade(trade_id, vec![200], 0, vec![100], 50)
+            .await
+            .unwrap();
+
+        assert_eq!(fake.trade(trade_id).unwrap().status, TradeStatus::Countered);
+
+        let counter = fake.trade(counter_id).unwrap();
+        assert_eq!(counter.sender_id, BOB_ID);
+        assert_eq!(counter.sender_uaids, vec![200]);
+        assert_eq!(counter.receiver_id, ALICE_ID);
+        assert_eq!(counter.receiver_uaids, vec![100]);
+        assert_eq!(counter.receiver_robux, 50);
+        assert_eq!(counter.status, TradeStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn trades_with_items_no_longer_owned_are_rejected() {
+        let (fake, alice, bob) = setup();
+
+        let first_id = alice
+            .send_trade(BOB_ID, vec![100], 0, vec![200], 0)
+            .await
+            .unwrap();
+        let second_id = alice
+            .send_trade(BOB_ID, vec![100], 0, vec![200], 0)
+            .await
+            .unwrap();
+
+        bob.accept_trade(first_id).await.unwrap();
+        bob.accept_trade(second_id).await.unwrap();
+
+        assert_eq!(
+            fake.trade(second_id).unwrap().status,
+            TradeStatus::RejectedDueToError
+        );
+        assert_eq!(uaids(&fake, BOB_ID), vec![100]);
+    }
+
+    #[tokio::test]
+    async fn items_on_hold_cannot_be_traded() {
+        let (_, alice, _) = setup();
+
+        assert!(alice
+            .send_trade(BOB_ID, vec![300], 0, vec![200], 0)
+            .await
+            .is_err());
+
+        let (outbound, _) = alice
+            .trades(TradeType::Outbound, Limit::Ten, None)
+            .await
+            .unwrap();
+        assert!(outbound.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rotated_xcsrf_tokens_are_retried() {
+        let (fake, alice, _) = setup();
+
+        let trade_id = alice
+            .send_trade(BOB_ID, vec![100], 0, vec![200], 0)
+            .await
+            .unwrap();
+
+        fake.inject_fault(Fault::RotateXcsrf);
+
+        alice.decline_trade(trade_id).await.unwrap();
+        assert_eq!(fake.trade(trade_id).unwrap().status, TradeStatus::Declined);
+    }
+
+    #[tokio::test]
+    async fn injects_status_faults_once() {
+        let (fake, alice, _) = setup();
+
+        alice.user_id().await.unwrap();
+
+        fake.inject_fault(Fault::TooManyRequests);
+        fake.inject_fault(Fault::Status(500));
+
+        assert!(matches!(
+            alice.robux().await,
+            Err(RoboatError::TooManyRequests)
+        ));
+        assert!(matches!(
+            alice.robux().await,
+            Err(RoboatError::InternalServerError)
+        ));
+        assert_eq!(alice.robux().await.unwrap(), 1000);
+    }
+
+    #[tokio::test]
+    async fn injects_challenges() {
+        let (fake, alice, _) = setup();
+
+        alice.user_id().await.unwrap();
+
+        fake.inject_fault(Fault::Challenge("challenge-id".to_string()));
+
+        assert!(matches!(
+            alice.robux().await,
+            Err(RoboatError::ChallengeRequired(x)) if x.contains("challenge-id")
+        ));
+    }
+
+    #[tokio::test]
+    async fn rotated_roblosecurities_are_kept_by_the_client() {
+        let (fake, alice, _) = setup();
+
+        alice.user_id().await.unwrap();
+
+        fake.inject_fault(Fault::RotateRoblosecurity("rotated-cookie".to_string()));
+
+        assert_eq!(alice.robux().await.unwrap(), 1000);
+        assert_eq!(
+            fake.user(ALICE_ID).unwrap().roblosecurity.as_deref(),
+            Some("rotated-cookie")
+        );
+
+        // The old roblosecurity is no longer valid, so this only succeeds with the new one.
+        assert_eq!(alice.robux().await.unwrap(), 1000);
+        assert_eq!(alice.roblosecurity().as_deref(), Some("rotated-cookie"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Tools for exercising a [`Client`](crate::Client) without live Roblox access.
+//!
+//! A [`Recorder`] records real request/response pairs into a [`Cassette`], which can be saved
+//! to a json file. A [`Replayer`] then answers requests from the cassette deterministically, so code
+//! built on this crate can be tested in CI.
+//!
+//! Cookies and x-csrf-tokens are scrubbed from recorded cassettes, so they are safe to commit.
+//!
+//! A [`FakeRoblox`] is an in-process fake of the Roblox API with in-memory state (users, inventories,
+//! trades, etc.), for testing flows end-to-end such as sending a trade and then accepting it.
+
+// Re-export all types so that they are easily accessible from the module root.
+pub use cassette::{
+    Cassette, Interaction, RecordedPart, RecordedRequest, RecordedResponse, Recorder, Replayer,
+};
+pub use fake_roblox::{
+    FakeCatalogItem, FakeCollectible, FakeGroup, FakeRoblox, FakeTrade, FakeUser, Fault,
+};
+
+/// Types related to recording and replaying cassettes.
+pub mod cassette;
+/// Types related to the in-process fake Roblox server.
+pub mod fake_roblox;
+
+/// Intercepts the requests sent by a [`Client`](crate::Client) instead of (or on top of)
+/// sending them straight to Roblox.
+#[derive(Clone, Debug)]
+pub(crate) enum Interceptor {
+    Recorder(Recorder),
+    Replayer(Replayer),
+    FakeRoblox(FakeRoblox),
+}
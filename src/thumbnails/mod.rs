@@ -93,7 +93,7 @@ impl Client {
     /// * Can handle up to 100 asset ids at once.
     /// * Does not appear to have a rate limit.
     /// * Note all types are implemented, the full list can be found [here](https://thumbnails.roblox.com/docs/index.html)
-    /// and the implemented ones can be found in [`ThumbnailType`].
+    ///   and the implemented ones can be found in [`ThumbnailType`].
     ///
     /// # Errors
     /// * All errors under [Standard Errors](#standard-errors).
@@ -170,16 +170,14 @@ impl Client {
 
         let body = serde_json::json!(json_item_requests);
 
-        let request_result = self
-            .reqwest_client
-            .post(THUMBNAIL_API_URL)
-            .json(&body)
-            .send()
-            .await;
+        let request_builder = self.reqwest_client.post(THUMBNAIL_API_URL).json(&body);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let mut raw =
-            Self::parse_to_raw::<request_types::AssetThumbnailUrlResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "thumbnails.thumbnail_url_bulk")
+            .await?;
+        let mut raw = self
+            .parse_to_raw::<request_types::AssetThumbnailUrlResponse>(response)
+            .await?;
 
         sort_url_datas_by_argument_order(&mut raw.data, &ids);
 
@@ -199,7 +197,7 @@ impl Client {
     /// * Can handle up to 100 asset ids at once.
     /// * Does not appear to have a rate limit.
     /// * Note all types are implemented, the full list can be found [here](https://thumbnails.roblox.com/docs/index.html)
-    /// and the implemented ones can be found in [`ThumbnailType`].
+    ///   and the implemented ones can be found in [`ThumbnailType`].
     ///
     /// # Errors
     /// * All errors under [Standard Errors](#standard-errors).
@@ -0,0 +1,172 @@
+//! Conversions between the ISO 8601 timestamps returned by Roblox and unix time.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the current time in milliseconds since the unix epoch.
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Parses a timestamp such as `2023-01-01T00:00:00.000Z` into milliseconds since the unix epoch.
+///
+/// The fractional seconds are optional. The timestamp may end in `Z`, in an offset from UTC such as
+/// `+00:00`, `-0500`, or `+01`, or in nothing (in which case it is treated as UTC). Returns `None` if
+/// the timestamp is not in this format or is before the unix epoch.
+pub(crate) fn parse_millis(timestamp: &str) -> Option<u64> {
+    let timestamp = timestamp.strip_suffix('Z').unwrap_or(timestamp);
+    let (date, time) = timestamp.split_once('T')?;
+
+    let mut date_parts = date.splitn(3, '-').map(|x| x.parse::<u64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+
+    // The time itself never contains a sign, so one marks the start of an offset.
+    let (time, offset_seconds) = match time.find(['+', '-']) {
+        Some(i) => (&time[..i], parse_offset_seconds(&time[i..])?),
+        None => (time, 0),
+    };
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time_parts = time.splitn(3, ':').map(|x| x.parse::<u64>().ok());
+    let (hour, minute, second) = (
+        time_parts.next()??,
+        time_parts.next()??,
+        time_parts.next()??,
+    );
+
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || year < 1970
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    // Only the first three digits are milliseconds, Roblox sometimes sends up to seven.
+    let millis = match fraction.get(..fraction.len().min(3)) {
+        Some("") | None => 0,
+        Some(x) => x.parse::<u64>().ok()? * 10_u64.pow(3 - x.len() as u32),
+    };
+
+    let seconds = days_since_epoch(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    let seconds = u64::try_from(seconds as i64 - offset_seconds).ok()?;
+
+    Some(seconds * 1_000 + millis)
+}
+
+/// Parses an offset from UTC such as `+01:00`, `-0130`, or `+01` into seconds.
+fn parse_offset_seconds(offset: &str) -> Option<i64> {
+    let sign = match offset.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+
+    let digits = offset[1..].replacen(':', "", 1);
+
+    if !matches!(digits.len(), 2 | 4) || !digits.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours = digits[..2].parse::<i64>().ok()?;
+    let minutes = match digits.len() {
+        4 => digits[2..].parse::<i64>().ok()?,
+        _ => 0,
+    };
+
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    Some(sign * (hours * 3_600 + minutes * 60))
+}
+
+/// Returns the number of days between the unix epoch and a date in the proleptic gregorian calendar.
+fn days_since_epoch(year: u64, month: u64, day: u64) -> u64 {
+    // Shifts the year to start in March, so that the leap day is the last day of the year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    // 719468 is the number of days between 0000-03-01 and 1970-01-01.
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_millis;
+
+    #[test]
+    fn parses_the_epoch() {
+        assert_eq!(parse_millis("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_millis("1970-01-01T00:00:00.001Z"), Some(1));
+    }
+
+    #[test]
+    fn parses_leap_days() {
+        assert_eq!(parse_millis("2000-02-29T00:00:00Z"), Some(951_782_400_000));
+        assert_eq!(
+            parse_millis("2024-02-29T12:34:56Z"),
+            Some(1_709_210_096_000)
+        );
+        assert_eq!(
+            parse_millis("2023-03-01T00:00:00Z"),
+            Some(1_677_628_800_000)
+        );
+        // 2100 is not a leap year, so March 1st follows February 28th.
+        assert_eq!(
+            parse_millis("2100-03-01T00:00:00Z"),
+            Some(4_107_542_400_000)
+        );
+    }
+
+    #[test]
+    fn parses_fractions() {
+        let base = 1_672_531_200_000;
+
+        assert_eq!(parse_millis("2023-01-01T00:00:00Z"), Some(base));
+        assert_eq!(parse_millis("2023-01-01T00:00:00.5Z"), Some(base + 500));
+        assert_eq!(parse_millis("2023-01-01T00:00:00.05Z"), Some(base + 50));
+        assert_eq!(parse_millis("2023-01-01T00:00:00.123Z"), Some(base + 123));
+        assert_eq!(
+            parse_millis("2023-01-01T00:00:00.1234567Z"),
+            Some(base + 123)
+        );
+        assert_eq!(parse_millis("2023-01-01T00:00:00.999"), Some(base + 999));
+    }
+
+    #[test]
+    fn parses_offsets() {
+        let base = Some(1_672_531_200_000);
+
+        assert_eq!(parse_millis("2023-01-01T00:00:00+00:00"), base);
+        assert_eq!(parse_millis("2023-01-01T05:30:00+05:30"), base);
+        assert_eq!(parse_millis("2022-12-31T19:00:00-05:00"), base);
+        assert_eq!(parse_millis("2022-12-31T19:00:00.000-0500"), base);
+        assert_eq!(parse_millis("2023-01-01T01:00:00+01"), base);
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert_eq!(parse_millis(""), None);
+        assert_eq!(parse_millis("2023-01-01"), None);
+        assert_eq!(parse_millis("2023-13-01T00:00:00Z"), None);
+        assert_eq!(parse_millis("2023-01-01T24:00:00Z"), None);
+        assert_eq!(parse_millis("2023-01-01T00:00:00.abcZ"), None);
+        assert_eq!(parse_millis("2023-01-01T00:00:00+5"), None);
+        assert_eq!(parse_millis("2023-01-01T00:00:00+24:00"), None);
+        assert_eq!(parse_millis("1969-12-31T23:59:59Z"), None);
+        // Before the epoch once the offset is applied.
+        assert_eq!(parse_millis("1970-01-01T00:00:00+01:00"), None);
+    }
+}
@@ -0,0 +1,371 @@
+//! Valuation of trades, and detection of common scam patterns.
+//!
+//! A [`TradeAnalyzer`] turns a [`TradeDetails`] into a [`TradeAnalysis`], which holds the value
+//! of each side, the net gain of the trade, the [`TradeFlag`]s raised, and a [`TradeVerdict`].
+//! Items are valued by their rap unless a [`ValueProvider`] is given.
+//!
+//! # Example
+//! ```
+//! use roboat::trades::analysis::{TradeAnalyzer, TradeVerdict};
+//! use roboat::trades::{TradeDetails, TradeItem};
+//! use std::collections::HashMap;
+//!
+//! let details = TradeDetails {
+//!     your_items: vec![TradeItem { item_id: 1, uaid: 10, rap: 1000, ..Default::default() }],
+//!     partner_items: vec![TradeItem { item_id: 2, uaid: 20, rap: 5000, ..Default::default() }],
+//!     ..Default::default()
+//! };
+//!
+//! // The rap of item 2 is projected, it is only worth 800.
+//! let values = HashMap::from([(1, 1000), (2, 800)]);
+//! let analysis = TradeAnalyzer::new().values(values).analyze(&details);
+//!
+//! assert_eq!(analysis.net_gain, -200);
+//! assert_eq!(analysis.verdict, TradeVerdict::Suspicious);
+//! ```
+
+use super::{TradeDetails, TradeItem, TradeMetadata, TradeSide};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The default margin used by [`TradeAnalyzer::fair_margin`].
+const DEFAULT_FAIR_MARGIN: f64 = 0.05;
+
+/// A source of item values used by a [`TradeAnalyzer`].
+///
+/// A `HashMap<u64, u64>` can be used as a provider, mapping item ids to values.
+///
+/// # Example
+/// ```
+/// use roboat::trades::analysis::ValueProvider;
+/// use roboat::trades::TradeItem;
+///
+/// /// Values every item at 90% of its rap.
+/// #[derive(Debug)]
+/// struct Discounted;
+///
+/// impl ValueProvider for Discounted {
+///     fn value(&self, item: &TradeItem) -> Option<u64> {
+///         Some(item.rap * 9 / 10)
+///     }
+/// }
+/// ```
+pub trait ValueProvider: std::fmt::Debug + Send + Sync {
+    /// Returns the value of an item, or `None` if it is unknown (in which case the rap is used).
+    fn value(&self, item: &TradeItem) -> Option<u64>;
+
+    /// Returns whether the rap of an item is inflated above what it is worth.
+    ///
+    /// By default, an item is projected if its rap is more than 25% above its value.
+    fn is_projected(&self, item: &TradeItem) -> bool {
+        self.value(item)
+            .is_some_and(|value| item.rap > value.saturating_add(value / 4))
+    }
+}
+
+/// A [`ValueProvider`] that values every item at its rap. Used by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct RapValues;
+
+impl ValueProvider for RapValues {
+    fn value(&self, item: &TradeItem) -> Option<u64> {
+        Some(item.rap)
+    }
+}
+
+impl ValueProvider for HashMap<u64, u64> {
+    fn value(&self, item: &TradeItem) -> Option<u64> {
+        self.get(&item.item_id).copied()
+    }
+}
+
+/// The value of one side of a trade.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct SideValue {
+    /// The value of the items, as given by the [`ValueProvider`].
+    pub items_value: u64,
+    /// The total rap of the items.
+    pub rap: u64,
+    /// The robux offered, before tax.
+    pub robux: u64,
+    /// The robux received by the other side, after tax.
+    pub robux_after_tax: u64,
+}
+
+impl SideValue {
+    /// Returns what the side gives up: the value of its items plus the robux before tax.
+    pub fn cost(&self) -> u64 {
+        self.items_value + self.robux
+    }
+
+    /// Returns what the other side receives: the value of the items plus the robux after tax.
+    pub fn received(&self) -> u64 {
+        self.items_value + self.robux_after_tax
+    }
+}
+
+/// A pattern found in a trade by a [`TradeAnalyzer`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TradeFlag {
+    /// The rap of an item is inflated above what it is worth, according to [`ValueProvider::is_projected`].
+    ProjectedItem {
+        /// The side offering the item.
+        side: TradeSide,
+        /// The uaid of the item.
+        uaid: u64,
+    },
+    /// A side offers no items and no robux.
+    EmptySide(TradeSide),
+    /// The partner offers a copy of an item you're offering, with a higher (or no) serial number.
+    SerialDowngrade {
+        /// The item id of the item.
+        item_id: u64,
+        /// The serial number of your copy.
+        your_serial: u64,
+        /// The serial number of the copy offered by the partner.
+        partner_serial: Option<u64>,
+    },
+    /// The [`ValueProvider`] does not know the value of an item, so its rap was used.
+    UnknownValue {
+        /// The side offering the item.
+        side: TradeSide,
+        /// The uaid of the item.
+        uaid: u64,
+    },
+}
+
+impl TradeFlag {
+    /// Returns whether the flag is a common sign of a scam against you.
+    ///
+    /// Projected items and empty sides only count when they are on the partner's side.
+    pub fn is_red_flag(&self) -> bool {
+        match self {
+            Self::ProjectedItem { side, .. } => *side == TradeSide::Partner,
+            Self::EmptySide(side) => *side == TradeSide::Partner,
+            Self::SerialDowngrade { .. } => true,
+            Self::UnknownValue { .. } => false,
+        }
+    }
+}
+
+/// The overall outcome of a trade for you.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum TradeVerdict {
+    /// You gain more than the fair margin.
+    Favorable,
+    /// The difference in value is within the fair margin.
+    #[default]
+    Even,
+    /// You lose more than the fair margin.
+    Unfavorable,
+    /// A [red flag](TradeFlag::is_red_flag) was raised, regardless of value.
+    Suspicious,
+}
+
+/// The result of [`TradeAnalyzer::analyze`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct TradeAnalysis {
+    /// The value of your side.
+    pub yours: SideValue,
+    /// The value of the partner's side.
+    pub partner: SideValue,
+    /// What you receive minus what you give up. Negative if you lose value.
+    pub net_gain: i64,
+    /// What the partner receives minus what they give up.
+    pub partner_net_gain: i64,
+    /// The patterns found in the trade.
+    pub flags: Vec<TradeFlag>,
+    /// The overall outcome of the trade for you.
+    pub verdict: TradeVerdict,
+}
+
+impl TradeAnalysis {
+    /// Returns whether any [red flag](TradeFlag::is_red_flag) was raised.
+    pub fn has_red_flags(&self) -> bool {
+        self.flags.iter().any(TradeFlag::is_red_flag)
+    }
+}
+
+/// Values trades and looks for common scam patterns.
+///
+/// # Notes
+/// * Items are valued using [`RapValues`] unless [`TradeAnalyzer::values`] is used.
+/// * Robux is taxed using [`TradeMetadata::default`] unless [`TradeAnalyzer::metadata`] is used.
+///
+/// # Example
+/// ```no_run
+/// use roboat::trades::analysis::{TradeAnalyzer, TradeVerdict};
+/// use roboat::ClientBuilder;
+///
+/// const ROBLOSECURITY: &str = "roblosecurity";
+/// const TRADE_ID: u64 = 123456789;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+///
+/// let analyzer = TradeAnalyzer::new()
+///     .metadata(client.trade_metadata().await?)
+///     .fair_margin(0.1);
+///
+/// let details = client.trade_details(TRADE_ID).await?;
+/// let analysis = analyzer.analyze(&details);
+///
+/// if analysis.verdict == TradeVerdict::Favorable {
+///     client.accept_trade(TRADE_ID).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TradeAnalyzer {
+    values: Arc<dyn ValueProvider>,
+    metadata: TradeMetadata,
+    fair_margin: f64,
+}
+
+impl Default for TradeAnalyzer {
+    fn default() -> Self {
+        Self {
+            values: Arc::new(RapValues),
+            metadata: TradeMetadata::default(),
+            fair_margin: DEFAULT_FAIR_MARGIN,
+        }
+    }
+}
+
+impl TradeAnalyzer {
+    /// Creates an analyzer that values items by their rap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the source of item values.
+    pub fn values<T: ValueProvider + 'static>(mut self, values: T) -> Self {
+        self.values = Arc::new(values);
+        self
+    }
+
+    /// Sets the trade limits used, such as the robux tax rate. Usually fetched with
+    /// [`Client::trade_metadata`](crate::Client::trade_metadata).
+    pub fn metadata(mut self, metadata: TradeMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets the fraction of the value you give up that the net gain can differ by while
+    /// still being [`TradeVerdict::Even`]. Defaults to 0.05 (5%).
+    pub fn fair_margin(mut self, fair_margin: f64) -> Self {
+        self.fair_margin = fair_margin;
+        self
+    }
+
+    /// Values both sides of a trade, and looks for common scam patterns.
+    pub fn analyze(&self, details: &TradeDetails) -> TradeAnalysis {
+        let mut flags = Vec::new();
+
+        let yours = self.side_value(
+            TradeSide::Yours,
+            &details.your_items,
+            details.your_robux,
+            &mut flags,
+        );
+        let partner = self.side_value(
+            TradeSide::Partner,
+            &details.partner_items,
+            details.partner_robux,
+            &mut flags,
+        );
+
+        for your_item in &details.your_items {
+            let your_serial = match your_item.serial_number {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let downgrade = details.partner_items.iter().find(|x| {
+                x.item_id == your_item.item_id
+                    && x.serial_number.is_none_or(|serial| serial > your_serial)
+            });
+
+            if let Some(partner_item) = downgrade {
+                flags.push(TradeFlag::SerialDowngrade {
+                    item_id: your_item.item_id,
+                    your_serial,
+                    partner_serial: partner_item.serial_number,
+                });
+            }
+        }
+
+        let net_gain = partner.received() as i64 - yours.cost() as i64;
+        let partner_net_gain = yours.received() as i64 - partner.cost() as i64;
+
+        let margin = yours.cost() as f64 * self.fair_margin;
+
+        let verdict = if flags.iter().any(TradeFlag::is_red_flag) {
+            TradeVerdict::Suspicious
+        } else if net_gain as f64 > margin {
+            TradeVerdict::Favorable
+        } else if (net_gain as f64) < -margin {
+            TradeVerdict::Unfavorable
+        } else {
+            TradeVerdict::Even
+        };
+
+        TradeAnalysis {
+            yours,
+            partner,
+            net_gain,
+            partner_net_gain,
+            flags,
+            verdict,
+        }
+    }
+
+    fn side_value(
+        &self,
+        side: TradeSide,
+        items: &[TradeItem],
+        robux: u64,
+        flags: &mut Vec<TradeFlag>,
+    ) -> SideValue {
+        let mut value = SideValue {
+            robux,
+            robux_after_tax: self.metadata.robux_after_tax(robux),
+            ..Default::default()
+        };
+
+        if items.is_empty() && robux == 0 {
+            flags.push(TradeFlag::EmptySide(side));
+        }
+
+        for item in items {
+            value.rap += item.rap;
+
+            value.items_value += match self.values.value(item) {
+                Some(x) => x,
+                None => {
+                    flags.push(TradeFlag::UnknownValue {
+                        side,
+                        uaid: item.uaid,
+                    });
+
+                    item.rap
+                }
+            };
+
+            if self.values.is_projected(item) {
+                flags.push(TradeFlag::ProjectedItem {
+                    side,
+                    uaid: item.uaid,
+                });
+            }
+        }
+
+        value
+    }
+}
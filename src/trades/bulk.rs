@@ -0,0 +1,240 @@
+//! Declining (or cancelling) trades in bulk with [`Client::decline_trades_where`].
+//!
+//! A [`TradeFilter`] selects the trades to decline, and the result is returned as a [`DeclineReport`].
+
+use super::{Trade, TradeDetails, TradeStatus, TradeType};
+use crate::{timestamps, Client, Limit, RoboatError};
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+type TradePredicate = Arc<dyn Fn(&Trade) -> bool + Send + Sync>;
+type DetailsPredicate = Arc<dyn Fn(&TradeDetails) -> bool + Send + Sync>;
+
+/// The criteria a trade must match to be declined by [`Client::decline_trades_where`].
+///
+/// A trade must match every criterion set. A filter with no criteria matches every trade.
+///
+/// # Notes
+/// * Every criterion except [`TradeFilter::details`] is checked using the trade list, without
+///   any extra request. [`TradeFilter::details`] fetches the details of every trade that matches
+///   the other criteria, so it should be combined with them when possible.
+/// * Trades whose creation time cannot be parsed never match [`TradeFilter::older_than`].
+///
+/// # Example
+/// ```
+/// use roboat::trades::TradeFilter;
+/// use std::time::Duration;
+///
+/// // Trades older than a day that ask for more than 2 of our items.
+/// let filter = TradeFilter::new()
+///     .older_than(Duration::from_secs(60 * 60 * 24))
+///     .details(|details| details.your_items.len() > 2);
+/// ```
+#[derive(Clone, Default)]
+pub struct TradeFilter {
+    partners: HashSet<u64>,
+    statuses: HashSet<TradeStatus>,
+    older_than: Option<Duration>,
+    trade_predicate: Option<TradePredicate>,
+    details_predicate: Option<DetailsPredicate>,
+}
+
+impl std::fmt::Debug for TradeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TradeFilter")
+            .field("partners", &self.partners)
+            .field("statuses", &self.statuses)
+            .field("older_than", &self.older_than)
+            .field("trade_predicate", &self.trade_predicate.is_some())
+            .field("details_predicate", &self.details_predicate.is_some())
+            .finish()
+    }
+}
+
+impl TradeFilter {
+    /// Creates a filter that matches every trade.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only matches trades with one of the partners given. Can be called multiple times.
+    pub fn partner(mut self, user_id: u64) -> Self {
+        self.partners.insert(user_id);
+        self
+    }
+
+    /// Only matches trades with one of the statuses given. Can be called multiple times.
+    pub fn status(mut self, status: TradeStatus) -> Self {
+        self.statuses.insert(status);
+        self
+    }
+
+    /// Only matches trades created longer than `age` ago.
+    pub fn older_than(mut self, age: Duration) -> Self {
+        self.older_than = Some(age);
+        self
+    }
+
+    /// Only matches trades for which `predicate` returns true.
+    pub fn trade(mut self, predicate: impl Fn(&Trade) -> bool + Send + Sync + 'static) -> Self {
+        self.trade_predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Only matches trades whose details `predicate` returns true for. The details are fetched
+    /// using [`Client::trade_details`].
+    pub fn details(
+        mut self,
+        predicate: impl Fn(&TradeDetails) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.details_predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Returns whether a trade matches every criterion that does not need its details.
+    fn matches(&self, trade: &Trade, now: u64) -> bool {
+        if !self.partners.is_empty() && !self.partners.contains(&trade.partner.user_id) {
+            return false;
+        }
+
+        if !self.statuses.is_empty() && !self.statuses.contains(&trade.status) {
+            return false;
+        }
+
+        if let Some(age) = self.older_than {
+            match timestamps::parse_millis(&trade.created) {
+                Some(created) if now.saturating_sub(created) > age.as_millis() as u64 => {}
+                _ => return false,
+            }
+        }
+
+        self.trade_predicate.as_ref().is_none_or(|x| x(trade))
+    }
+}
+
+/// The result of [`Client::decline_trades_where`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct DeclineReport {
+    /// The amount of trades listed.
+    pub checked: u64,
+    /// The ids of the trades declined.
+    pub declined: Vec<u64>,
+    /// The ids of the trades that matched the filter but were not declined, as the client
+    /// is in [dry run](crate::ClientBuilder::dry_run) mode.
+    pub dry_run: Vec<u64>,
+    /// The ids of the trades that matched the filter but could not be declined (or whose details
+    /// could not be fetched), and the error returned.
+    pub failed: BTreeMap<u64, String>,
+}
+
+impl Client {
+    /// Declines every trade of `trade_type` that matches `filter`, using <https://trades.roblox.com/v1/trades/{trade_id}/decline>.
+    ///
+    /// Declining an outbound trade cancels it.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * Every page of trades is listed before any trade is declined.
+    /// * Trades are checked and declined at most `max_concurrency` at a time. Requests are paced
+    ///   by the [`RateLimitPolicy`](crate::config::RateLimitPolicy) of the client, if it has one.
+    /// * A trade that cannot be declined does not stop the others from being declined.
+    /// * In [dry run](crate::ClientBuilder::dry_run) mode, the trades that would be declined are listed in
+    ///   [`DeclineReport::dry_run`].
+    ///
+    /// # Argument Notes
+    /// * A `max_concurrency` of 0 is treated as 1.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    ///
+    /// Errors returned while declining a trade are part of the [`DeclineReport`] instead.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::trades::{TradeFilter, TradeType};
+    /// use roboat::ClientBuilder;
+    /// use std::time::Duration;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// // Cancels every outbound trade older than 3 days.
+    /// let filter = TradeFilter::new().older_than(Duration::from_secs(60 * 60 * 24 * 3));
+    /// let report = client.decline_trades_where(TradeType::Outbound, filter, 4).await?;
+    ///
+    /// println!("Declined {} trades, {} failed", report.declined.len(), report.failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn decline_trades_where(
+        &self,
+        trade_type: TradeType,
+        filter: TradeFilter,
+        max_concurrency: usize,
+    ) -> Result<DeclineReport, RoboatError> {
+        let mut report = DeclineReport::default();
+        let mut matches = Vec::new();
+        let mut cursor = None;
+        let now = timestamps::now_millis();
+
+        loop {
+            let (trades, next_cursor) = self.trades(trade_type, Limit::Hundred, cursor).await?;
+
+            report.checked += trades.len() as u64;
+            matches.extend(
+                trades
+                    .iter()
+                    .filter(|x| filter.matches(x, now))
+                    .map(|x| x.trade_id),
+            );
+
+            match next_cursor {
+                Some(x) => cursor = Some(x),
+                None => break,
+            }
+        }
+
+        let results: Vec<(u64, Option<Result<(), RoboatError>>)> = stream::iter(matches)
+            .map(|trade_id| {
+                let filter = &filter;
+
+                async move {
+                    if let Some(predicate) = &filter.details_predicate {
+                        match self.trade_details(trade_id).await {
+                            Ok(details) if !predicate(&details) => return (trade_id, None),
+                            Ok(_) => {}
+                            Err(e) => return (trade_id, Some(Err(e))),
+                        }
+                    }
+
+                    (trade_id, Some(self.decline_trade(trade_id).await))
+                }
+            })
+            .buffer_unordered(max_concurrency.max(1))
+            .collect()
+            .await;
+
+        for (trade_id, result) in results {
+            match result {
+                Some(Ok(())) => report.declined.push(trade_id),
+                Some(Err(RoboatError::DryRun(_))) => report.dry_run.push(trade_id),
+                Some(Err(e)) => {
+                    report.failed.insert(trade_id, e.to_string());
+                }
+                None => {}
+            }
+        }
+
+        report.declined.sort_unstable();
+        report.dry_run.sort_unstable();
+
+        Ok(report)
+    }
+}
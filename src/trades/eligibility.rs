@@ -0,0 +1,468 @@
+use super::request_types;
+use crate::enums::string_enum;
+use crate::{Client, RoboatError};
+use reqwest::header;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const CAN_TRADE_WITH_API: &str = "https://trades.roblox.com/v1/users/{user_id}/can-trade-with";
+const TRADE_METADATA_API: &str = "https://trades.roblox.com/v1/trades/metadata";
+const TRADE_PRIVACY_API: &str = "https://accountsettings.roblox.com/v1/trade-privacy";
+
+/// Whether the account can trade with a user, returned by [`Client::can_trade_with`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct CanTradeWith {
+    /// Whether a trade can be sent to the user.
+    pub can_trade: bool,
+    /// The reason the trade can or cannot be sent.
+    pub status: CanTradeWithStatus,
+}
+
+/// The reason the account can or cannot trade with a user.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum CanTradeWithStatus {
+    #[default]
+    CanTrade,
+    CannotTradeWithSelf,
+    /// The account cannot trade at all (for example, because it does not have Premium).
+    SenderCannotTrade,
+    /// The user cannot trade at all.
+    ReceiverCannotTrade,
+    /// The trade privacy of the user does not allow trades from the account.
+    ReceiverPrivacySettingsTooStrict,
+    UnknownUser,
+    /// A value not known by this crate.
+    Unknown(String),
+}
+
+string_enum!(CanTradeWithStatus {
+    CanTrade => "CanTrade",
+    CannotTradeWithSelf => "CannotTradeWithSelf",
+    SenderCannotTrade => "SenderCannotTrade",
+    ReceiverCannotTrade => "ReceiverCannotTrade",
+    ReceiverPrivacySettingsTooStrict => "ReceiverPrivacySettingsTooStrict",
+    UnknownUser => "UnknownUser",
+});
+
+/// Who can send trades to the account, returned by [`Client::trade_privacy`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TradePrivacy {
+    /// Trading is turned off for the account.
+    Disabled,
+    NoOne,
+    Friends,
+    TopFriends,
+    Following,
+    Followers,
+    #[default]
+    All,
+    /// A value not known by this crate.
+    Unknown(String),
+}
+
+string_enum!(TradePrivacy {
+    Disabled => "Disabled",
+    NoOne => "NoOne",
+    Friends => "Friends",
+    TopFriends => "TopFriends",
+    Following => "Following",
+    Followers => "Followers",
+    All => "All",
+});
+
+/// The limits every trade must follow, returned by [`Client::trade_metadata`].
+///
+/// The [`Default`] value holds the limits Roblox used at the time of writing.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct TradeMetadata {
+    /// The maximum amount of items each side of a trade can offer.
+    pub max_items_per_side: u64,
+    /// The minimum ratio between the values of the two sides of a trade.
+    pub min_value_ratio: f64,
+    /// The maximum robux a side can add, as a fraction of the value of the items it offers.
+    pub max_robux_share: f64,
+    /// The fraction of robux taken as tax when it is received in a trade.
+    pub robux_tax_rate: f64,
+}
+
+impl Default for TradeMetadata {
+    fn default() -> Self {
+        Self {
+            max_items_per_side: 4,
+            min_value_ratio: 0.5,
+            max_robux_share: 0.5,
+            robux_tax_rate: 0.3,
+        }
+    }
+}
+
+/// A side of a trade.
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
+)]
+pub enum TradeSide {
+    /// The side of the account.
+    #[default]
+    Yours,
+    /// The side of the trade partner.
+    Partner,
+}
+
+/// A reason a trade offer was rejected by [`Client::send_trade`] or [`Client::counter_trade`]
+/// before being sent.
+#[non_exhaustive]
+#[derive(
+    thiserror::Error, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum TradeOfferViolation {
+    /// The partner is the account itself.
+    #[error("Cannot Trade With Self")]
+    TradeWithSelf,
+    /// A side offers more items than [`TradeMetadata::max_items_per_side`].
+    #[error("Too Many Items On {side:?} Side ({count} Above Max {max})")]
+    TooManyItems {
+        /// The side offering too many items.
+        side: TradeSide,
+        /// The amount of items offered.
+        count: u64,
+        /// The maximum amount of items per side.
+        max: u64,
+    },
+    /// The same uaid is offered more than once.
+    #[error("Duplicate Item {0}")]
+    DuplicateItem(u64),
+    /// A side offers robux without offering any items. Robux can only be added up to
+    /// [`TradeMetadata::max_robux_share`] of the value of the items of a side.
+    #[error("Robux Without Items On {0:?} Side")]
+    RobuxWithoutItems(TradeSide),
+    /// A side offers more robux than [`TradeMetadata::max_robux_share`] of the value of its items.
+    #[error("Robux Above Allowed Share On {side:?} Side ({robux} Above Max {max})")]
+    RobuxAboveMaxShare {
+        /// The side offering too much robux.
+        side: TradeSide,
+        /// The robux offered.
+        robux: u64,
+        /// The most robux the side can offer.
+        max: u64,
+    },
+    /// The value of the items of one side is less than [`TradeMetadata::min_value_ratio`]
+    /// of the value of the items of the other side.
+    #[error("Value Ratio Too Low On {side:?} Side ({value} Compared To {other_value})")]
+    ValueRatioTooLow {
+        /// The side offering less value.
+        side: TradeSide,
+        /// The value of the items of the side.
+        value: u64,
+        /// The value of the items of the other side.
+        other_value: u64,
+    },
+}
+
+impl TradeMetadata {
+    /// Returns the most robux a side offering items worth `items_value` (usually their rap) can add.
+    pub fn max_robux(&self, items_value: u64) -> u64 {
+        (items_value as f64 * self.max_robux_share) as u64
+    }
+
+    /// Returns the robux received by the other side when `robux` is offered, after tax.
+    pub fn robux_after_tax(&self, robux: u64) -> u64 {
+        (robux as f64 * (1.0 - self.robux_tax_rate)) as u64
+    }
+
+    /// Checks an offer against the limits that can be verified without knowing the value of the items.
+    ///
+    /// The limits that depend on the value of the items are checked by [`TradeMetadata::check_offer_values`].
+    ///
+    /// # Errors
+    /// * [`TradeOfferViolation`] - The first limit the offer breaks.
+    pub fn check_offer(
+        &self,
+        your_item_uaids: &[u64],
+        your_robux: u64,
+        partner_item_uaids: &[u64],
+        partner_robux: u64,
+    ) -> Result<(), TradeOfferViolation> {
+        let sides = [
+            (TradeSide::Yours, your_item_uaids, your_robux),
+            (TradeSide::Partner, partner_item_uaids, partner_robux),
+        ];
+
+        for (side, uaids, robux) in sides {
+            if uaids.len() as u64 > self.max_items_per_side {
+                return Err(TradeOfferViolation::TooManyItems {
+                    side,
+                    count: uaids.len() as u64,
+                    max: self.max_items_per_side,
+                });
+            }
+
+            if uaids.is_empty() && robux > 0 {
+                return Err(TradeOfferViolation::RobuxWithoutItems(side));
+            }
+        }
+
+        let mut seen = HashSet::new();
+
+        for uaid in your_item_uaids.iter().chain(partner_item_uaids) {
+            if !seen.insert(uaid) {
+                return Err(TradeOfferViolation::DuplicateItem(*uaid));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks an offer against [`TradeMetadata::max_robux_share`] and [`TradeMetadata::min_value_ratio`],
+    /// given the value of the items of each side (usually their rap).
+    ///
+    /// # Errors
+    /// * [`TradeOfferViolation`] - The first limit the offer breaks.
+    pub fn check_offer_values(
+        &self,
+        your_items_value: u64,
+        your_robux: u64,
+        partner_items_value: u64,
+        partner_robux: u64,
+    ) -> Result<(), TradeOfferViolation> {
+        let sides = [
+            (TradeSide::Yours, your_items_value, your_robux),
+            (TradeSide::Partner, partner_items_value, partner_robux),
+        ];
+
+        for (side, items_value, robux) in sides {
+            let max = self.max_robux(items_value);
+
+            if robux > max {
+                return Err(TradeOfferViolation::RobuxAboveMaxShare { side, robux, max });
+            }
+        }
+
+        let (side, value, other_value) = match your_items_value < partner_items_value {
+            true => (TradeSide::Yours, your_items_value, partner_items_value),
+            false => (TradeSide::Partner, partner_items_value, your_items_value),
+        };
+
+        if other_value > 0 && (value as f64) < other_value as f64 * self.min_value_ratio {
+            return Err(TradeOfferViolation::ValueRatioTooLow {
+                side,
+                value,
+                other_value,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Client {
+    /// Checks whether a trade can be sent to a user using <https://trades.roblox.com/v1/users/{user_id}/can-trade-with>.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * [`CanTradeWithStatus::SenderCannotTrade`] means the account cannot trade with anyone.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    /// const USER_ID: u64 = 1;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let can_trade_with = client.can_trade_with(USER_ID).await?;
+    ///
+    /// if !can_trade_with.can_trade {
+    ///     println!("Cannot trade with {}: {}", USER_ID, can_trade_with.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn can_trade_with(&self, user_id: u64) -> Result<CanTradeWith, RoboatError> {
+        let formatted_url = CAN_TRADE_WITH_API.replace("{user_id}", &user_id.to_string());
+        let cookie_string = self.cookie_string()?;
+
+        let request_builder = self
+            .reqwest_client
+            .get(&formatted_url)
+            .header(header::COOKIE, cookie_string);
+
+        let response = self
+            .send_request(request_builder, "trades.can_trade_with")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::CanTradeWithResponse>(response)
+            .await?;
+
+        Ok(CanTradeWith {
+            can_trade: raw.can_trade,
+            status: raw.status,
+        })
+    }
+
+    /// Fetches the limits every trade must follow using <https://trades.roblox.com/v1/trades/metadata>.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * The result is cached by the client, and used by [`Client::send_trade`] and [`Client::counter_trade`]
+    ///   to check offers before sending them. Until this is called, [`TradeMetadata::default`] is used instead.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let metadata = client.trade_metadata().await?;
+    ///
+    /// println!("Max Items Per Side: {}", metadata.max_items_per_side);
+    /// println!("Robux Tax Rate: {}", metadata.robux_tax_rate);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn trade_metadata(&self) -> Result<TradeMetadata, RoboatError> {
+        let cookie_string = self.cookie_string()?;
+
+        let request_builder = self
+            .reqwest_client
+            .get(TRADE_METADATA_API)
+            .header(header::COOKIE, cookie_string);
+
+        let response = self
+            .send_request(request_builder, "trades.trade_metadata")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::TradeMetadataResponse>(response)
+            .await?;
+
+        let metadata = TradeMetadata {
+            max_items_per_side: raw.max_items_per_side,
+            min_value_ratio: raw.min_value_ratio,
+            max_robux_share: raw.trade_system_max_robux_percent,
+            robux_tax_rate: raw.trade_robux_tax_percent,
+        };
+
+        *self.trade_metadata.write().await = Some(metadata.clone());
+
+        Ok(metadata)
+    }
+
+    /// Fetches who can send trades to the account using <https://accountsettings.roblox.com/v1/trade-privacy>.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::trades::TradePrivacy;
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// if client.trade_privacy().await? == TradePrivacy::Disabled {
+    ///     println!("Trading is turned off.");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn trade_privacy(&self) -> Result<TradePrivacy, RoboatError> {
+        let cookie_string = self.cookie_string()?;
+
+        let request_builder = self
+            .reqwest_client
+            .get(TRADE_PRIVACY_API)
+            .header(header::COOKIE, cookie_string);
+
+        let response = self
+            .send_request(request_builder, "trades.trade_privacy")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::TradePrivacyResponse>(response)
+            .await?;
+
+        Ok(raw.trade_privacy)
+    }
+
+    /// Checks an offer against the cached [`TradeMetadata`] (or the default one), and makes
+    /// sure the partner is not the account itself if the user id of the account is cached.
+    ///
+    /// A rejected offer is written to the [`AuditSink`](crate::audit::AuditSink) of the client as `endpoint`.
+    pub(crate) async fn check_trade_offer(
+        &self,
+        endpoint: &'static str,
+        partner_id: u64,
+        your_item_uaids: &[u64],
+        your_robux: u64,
+        partner_item_uaids: &[u64],
+        partner_robux: u64,
+    ) -> Result<(), RoboatError> {
+        // The user id is not fetched here, so that checking an offer never sends a request.
+        let user_id = self
+            .user_information
+            .read()
+            .await
+            .as_ref()
+            .map(|x| x.user_id);
+
+        let violation = if user_id == Some(partner_id) {
+            Err(TradeOfferViolation::TradeWithSelf)
+        } else {
+            self.trade_metadata
+                .read()
+                .await
+                .clone()
+                .unwrap_or_default()
+                .check_offer(
+                    your_item_uaids,
+                    your_robux,
+                    partner_item_uaids,
+                    partner_robux,
+                )
+        };
+
+        if let Err(violation) = violation {
+            let error = RoboatError::InvalidTradeOffer(violation);
+
+            self.record_rejected_call(
+                endpoint,
+                serde_json::json!({
+                    "partner_id": partner_id,
+                    "your_item_uaids": your_item_uaids,
+                    "your_robux": your_robux,
+                    "partner_item_uaids": partner_item_uaids,
+                    "partner_robux": partner_robux,
+                }),
+                &error,
+            )
+            .await;
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,457 @@
+//! A resumable exporter of trade history, with the items of both sides of every trade.
+//!
+//! A [`TradeExporter`] pages through the trades of a [`Client`], fetches the details of every trade,
+//! and appends them to a file as [JSON Lines](ExportFormat::JsonLines) or [CSV](ExportFormat::Csv).
+//! After every page, its progress is saved to a checkpoint file, so that an interrupted export
+//! continues where it stopped when it is run again.
+
+use super::{TradeDetails, TradeItem, TradeType};
+use crate::{files, Client, Limit, RoboatError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// The default value of [`TradeExporter::max_concurrency`].
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+/// The default value of [`TradeExporter::rate_limit_backoff`].
+const DEFAULT_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(10);
+/// The amount of times the details of a trade are fetched again after status code 429.
+const RATE_LIMIT_RETRIES: u32 = 5;
+/// The extension appended to the output path to get the default checkpoint path.
+const CHECKPOINT_EXTENSION: &str = "checkpoint.json";
+/// The columns written by [`ExportFormat::Csv`].
+const CSV_HEADER: &str = "trade_id,status,created,expiration,partner_id,partner_username,\
+your_robux,partner_robux,your_rap,partner_rap,your_items,partner_items";
+
+/// The format of the file written by a [`TradeExporter`].
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum ExportFormat {
+    /// One [`TradeDetails`] serialized as json per line.
+    #[default]
+    JsonLines,
+    /// One trade per row, with a header row. The items of each side are written in a single
+    /// column, as a json array of [`TradeItem`]s.
+    Csv,
+}
+
+/// The progress of an export, saved after every page.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct ExportCheckpoint {
+    /// The format of the output file.
+    pub format: ExportFormat,
+    /// The type of the trades exported.
+    pub trade_type: TradeType,
+    /// The cursor of the next page to export, or `None` if the first page is next.
+    pub cursor: Option<String>,
+    /// The amount of trades exported so far.
+    pub exported: u64,
+    /// The length of the output file once the last page was written. Anything written past it,
+    /// by an export interrupted in the middle of a page, is removed when the export resumes.
+    pub output_len: u64,
+    /// Whether every page has been exported.
+    pub complete: bool,
+    /// The ids of the trades whose details could not be fetched, and the error returned.
+    /// These trades are skipped.
+    #[serde(default)]
+    pub failed: BTreeMap<u64, String>,
+}
+
+/// The result of [`TradeExporter::export`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct ExportReport {
+    /// The amount of trades exported in total, including by previous runs.
+    pub exported: u64,
+    /// The amount of trades exported by this run.
+    pub exported_this_run: u64,
+    /// Whether the export continued from a checkpoint.
+    pub resumed: bool,
+    /// The ids of the trades that were skipped as their details could not be fetched, including by
+    /// previous runs, and the error returned.
+    pub failed: BTreeMap<u64, String>,
+}
+
+/// Exports trade history with the items of both sides of every trade.
+///
+/// # Notes
+/// * Requires a valid roblosecurity.
+/// * Trades are exported newest to oldest, 100 trades per page. The details of the trades of a page are
+///   fetched at most [`TradeExporter::max_concurrency`] at a time.
+/// * The [`RateLimitPolicy`](crate::config::RateLimitPolicy) and [`RetryPolicy`](crate::config::RetryPolicy)
+///   of the client apply. If Roblox still returns status code 429, the exporter waits
+///   [`TradeExporter::rate_limit_backoff`] (doubling every time) and tries again, up to 5 times.
+/// * A trade whose details cannot be fetched for a reason specific to it (such as status code 400 or 403)
+///   is skipped and listed in [`ExportReport::failed`]. Errors that would affect every trade (such as
+///   status code 429, 5xx, or an invalid roblosecurity) stop the export instead, so it can be resumed later.
+/// * Unless [`TradeExporter::checkpoint`] is used, the checkpoint is saved next to the output file, with
+///   `.checkpoint.json` appended to its name. Once an export is complete, running it again does nothing
+///   until the checkpoint is deleted.
+///
+/// # Example
+/// ```no_run
+/// use roboat::trades::export::{ExportFormat, TradeExporter};
+/// use roboat::ClientBuilder;
+/// use std::sync::Arc;
+///
+/// const ROBLOSECURITY: &str = "roblosecurity";
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build());
+///
+/// let report = TradeExporter::new(client)
+///     .format(ExportFormat::Csv)
+///     .export("trades.csv")
+///     .await?;
+///
+/// println!("Exported {} trades", report.exported);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TradeExporter {
+    client: Arc<Client>,
+    trade_type: TradeType,
+    format: ExportFormat,
+    max_concurrency: usize,
+    rate_limit_backoff: Duration,
+    checkpoint_path: Option<PathBuf>,
+}
+
+impl TradeExporter {
+    /// Creates an exporter for the completed trades of `client`, written as JSON Lines.
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            trade_type: TradeType::Completed,
+            format: ExportFormat::default(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            rate_limit_backoff: DEFAULT_RATE_LIMIT_BACKOFF,
+            checkpoint_path: None,
+        }
+    }
+
+    /// Sets the type of the trades exported. Defaults to [`TradeType::Completed`].
+    pub fn trade_type(mut self, trade_type: TradeType) -> Self {
+        self.trade_type = trade_type;
+        self
+    }
+
+    /// Sets the format of the output file. Defaults to [`ExportFormat::JsonLines`].
+    pub fn format(mut self, format: ExportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the maximum amount of trade details fetched at the same time. Defaults to 4.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Sets the first delay before fetching trade details again after status code 429. Defaults to 10 seconds.
+    pub fn rate_limit_backoff(mut self, rate_limit_backoff: Duration) -> Self {
+        self.rate_limit_backoff = rate_limit_backoff;
+        self
+    }
+
+    /// Sets the path of the checkpoint file.
+    pub fn checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint_path = Some(path.into());
+        self
+    }
+
+    /// Exports every trade to the file at `output`, continuing from the checkpoint if there is one.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](Client#standard-errors).
+    /// * All errors under [Auth Required Errors](Client#auth-required-errors).
+    /// * [`RoboatError::IoError`] - If the output or checkpoint file cannot be read or written.
+    /// * [`RoboatError::InvalidConfig`] - If the checkpoint file is not a valid checkpoint, or does not match the output file,
+    ///   the format, or the trade type of the exporter.
+    ///
+    /// The progress of every page fully written before an error is kept in the checkpoint.
+    pub async fn export(&self, output: impl AsRef<Path>) -> Result<ExportReport, RoboatError> {
+        let output = output.as_ref();
+        let checkpoint_path = match &self.checkpoint_path {
+            Some(x) => x.clone(),
+            None => {
+                let mut path = output.as_os_str().to_owned();
+                path.push(".");
+                path.push(CHECKPOINT_EXTENSION);
+                PathBuf::from(path)
+            }
+        };
+
+        let (mut checkpoint, resumed) = match fs::read_to_string(&checkpoint_path) {
+            Ok(x) => (
+                serde_json::from_str::<ExportCheckpoint>(&x).map_err(|e| {
+                    RoboatError::InvalidConfig(format!("invalid export checkpoint: {}", e))
+                })?,
+                true,
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let checkpoint = ExportCheckpoint {
+                    format: self.format,
+                    trade_type: self.trade_type,
+                    ..Default::default()
+                };
+
+                (checkpoint, false)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // Appending to an export of a different format or trade type would mix them in one file.
+        if checkpoint.format != self.format || checkpoint.trade_type != self.trade_type {
+            return Err(RoboatError::InvalidConfig(format!(
+                "the export checkpoint is for {:?} trades as {:?}, not {:?} trades as {:?}",
+                checkpoint.trade_type, checkpoint.format, self.trade_type, self.format
+            )));
+        }
+
+        let mut report = ExportReport {
+            exported: checkpoint.exported,
+            exported_this_run: 0,
+            resumed,
+            failed: checkpoint.failed.clone(),
+        };
+
+        if checkpoint.complete {
+            return Ok(report);
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(output)?;
+
+        if file.metadata()?.len() < checkpoint.output_len {
+            return Err(RoboatError::InvalidConfig(
+                "the output file is shorter than recorded in the export checkpoint".to_string(),
+            ));
+        }
+
+        // Removes anything written after the last checkpoint, then appends from there.
+        file.set_len(checkpoint.output_len)?;
+        let mut file = OpenOptions::new().append(true).open(output)?;
+
+        if checkpoint.output_len == 0 && self.format == ExportFormat::Csv {
+            writeln!(file, "{}", CSV_HEADER)?;
+        }
+
+        loop {
+            let (trades, next_cursor) = self
+                .client
+                .trades(self.trade_type, Limit::Hundred, checkpoint.cursor.clone())
+                .await?;
+
+            let trade_ids: Vec<u64> = trades.iter().map(|x| x.trade_id).collect();
+            let (details, failed) = self.fetch_details(&trade_ids).await?;
+
+            let mut page = String::new();
+
+            for trade_details in &details {
+                match self.format {
+                    ExportFormat::JsonLines => page.push_str(&files::json_line(trade_details)?),
+                    ExportFormat::Csv => {
+                        page.push_str(&csv_row(trade_details));
+                        page.push('\n');
+                    }
+                }
+            }
+
+            file.write_all(page.as_bytes())?;
+            file.flush()?;
+
+            checkpoint.exported += details.len() as u64;
+            checkpoint.failed.extend(failed);
+            checkpoint.output_len = file.metadata()?.len();
+            checkpoint.complete = next_cursor.is_none();
+            checkpoint.cursor = next_cursor;
+
+            report.exported = checkpoint.exported;
+            report.exported_this_run += details.len() as u64;
+            report.failed = checkpoint.failed.clone();
+
+            files::write_json_atomic(&checkpoint_path, &checkpoint)?;
+
+            if checkpoint.complete {
+                return Ok(report);
+            }
+        }
+    }
+
+    /// Fetches the details of every trade, returned in the same order as the ids, along with the
+    /// trades skipped as their details could not be fetched.
+    async fn fetch_details(
+        &self,
+        trade_ids: &[u64],
+    ) -> Result<(Vec<TradeDetails>, BTreeMap<u64, String>), RoboatError> {
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+        let mut tasks = JoinSet::new();
+
+        for (index, trade_id) in trade_ids.iter().copied().enumerate() {
+            let client = self.client.clone();
+            let semaphore = semaphore.clone();
+            let mut backoff = self.rate_limit_backoff;
+
+            tasks.spawn(async move {
+                // The semaphore is never closed.
+                let _permit = semaphore.acquire_owned().await.unwrap();
+                let mut retries = 0;
+
+                loop {
+                    match client.trade_details(trade_id).await {
+                        Err(RoboatError::TooManyRequests) if retries < RATE_LIMIT_RETRIES => {
+                            tokio::time::sleep(backoff).await;
+                            backoff *= 2;
+                            retries += 1;
+                        }
+                        result => return (index, result),
+                    }
+                }
+            });
+        }
+
+        let mut details = Vec::with_capacity(trade_ids.len());
+        let mut failed = BTreeMap::new();
+
+        while let Some(result) = tasks.join_next().await {
+            // The tasks never panic.
+            let (index, result) = result.unwrap();
+
+            match result {
+                Ok(x) => details.push((index, x)),
+                Err(e) if is_trade_specific(&e) => {
+                    failed.insert(trade_ids[index], e.to_string());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        details.sort_by_key(|(index, _)| *index);
+
+        Ok((details.into_iter().map(|(_, x)| x).collect(), failed))
+    }
+}
+
+/// Returns whether an error returned while fetching the details of a trade is specific to that
+/// trade, rather than one that would be returned for every trade (such as a rate limit or an outage).
+fn is_trade_specific(error: &RoboatError) -> bool {
+    match error {
+        RoboatError::TooManyRequests
+        | RoboatError::InternalServerError
+        | RoboatError::InvalidRoblosecurity
+        | RoboatError::RoblosecurityNotSet
+        | RoboatError::ReqwestError(_)
+        | RoboatError::IoError(_)
+        | RoboatError::CassetteMismatch(_)
+        | RoboatError::InvalidConfig(_) => false,
+        RoboatError::UnidentifiedStatusCode(status) => *status < 500,
+        _ => true,
+    }
+}
+
+/// Formats a trade as a csv row (without a line break), in the order of [`CSV_HEADER`].
+fn csv_row(details: &TradeDetails) -> String {
+    let rap = |items: &[TradeItem]| items.iter().map(|x| x.rap).sum::<u64>();
+
+    // An item only contains strings, integers, and options, so serializing it cannot fail.
+    let items = |items: &[TradeItem]| serde_json::to_string(items).unwrap();
+
+    [
+        details.trade_id.to_string(),
+        details.status.to_string(),
+        details.created.clone(),
+        details.expiration.clone().unwrap_or_default(),
+        details.partner.user_id.to_string(),
+        details.partner.username.clone(),
+        details.your_robux.to_string(),
+        details.partner_robux.to_string(),
+        rap(&details.your_items).to_string(),
+        rap(&details.partner_items).to_string(),
+        items(&details.your_items),
+        items(&details.partner_items),
+    ]
+    .iter()
+    .map(|x| csv_field(x))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Quotes a field if it contains a comma, a quote, or a line break.
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TradeExporter;
+    use crate::testing::{FakeCollectible, FakeRoblox, FakeUser, Fault};
+    use crate::ClientBuilder;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn skips_trades_whose_details_cannot_be_fetched() {
+        let fake = FakeRoblox::new();
+        fake.add_user(FakeUser::new(1, "alice").roblosecurity("alice-cookie"));
+        fake.add_user(FakeUser::new(2, "bob").roblosecurity("bob-cookie"));
+        fake.add_collectible(1, FakeCollectible::new(100, 10, "Hat").rap(100));
+        fake.add_collectible(1, FakeCollectible::new(101, 11, "Hat").rap(100));
+        fake.add_collectible(2, FakeCollectible::new(200, 20, "Fedora").rap(100));
+        fake.add_collectible(2, FakeCollectible::new(201, 21, "Fedora").rap(100));
+
+        let client = |roblosecurity: &str| {
+            ClientBuilder::new()
+                .roblosecurity(roblosecurity.to_string())
+                .fake_roblox(fake.clone())
+                .build()
+        };
+
+        let alice = Arc::new(client("alice-cookie"));
+        let bob = client("bob-cookie");
+
+        for (yours, theirs) in [(100, 200), (101, 201)] {
+            let trade_id = alice
+                .send_trade(2, vec![yours], 0, vec![theirs], 0)
+                .await
+                .unwrap();
+            bob.accept_trade(trade_id).await.unwrap();
+        }
+
+        // The trades are listed, then the details of the first trade fetched are rejected.
+        fake.inject_fault(Fault::RotateRoblosecurity("alice-cookie".to_string()));
+        fake.inject_fault(Fault::Status(400));
+
+        let directory =
+            std::env::temp_dir().join(format!("roboat-export-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir(&directory).unwrap();
+        let output = directory.join("trades.jsonl");
+
+        let report = TradeExporter::new(alice)
+            .max_concurrency(1)
+            .export(&output)
+            .await
+            .unwrap();
+
+        let lines = std::fs::read_to_string(&output).unwrap().lines().count();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(report.exported, 1);
+        assert_eq!(lines, 1);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed.values().next().unwrap(), "Bad Request");
+    }
+}
@@ -1,15 +1,34 @@
+use crate::enums::string_enum;
 use crate::{Client, Limit, RoboatError, User};
 use reqwest::header;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 
+pub use bulk::{DeclineReport, TradeFilter};
+pub use eligibility::{
+    CanTradeWith, CanTradeWithStatus, TradeMetadata, TradeOfferViolation, TradePrivacy, TradeSide,
+};
+
+/// A module related to valuing trades and detecting common scam patterns.
+pub mod analysis;
+mod bulk;
+mod eligibility;
+/// A module related to exporting trade history to a file.
+pub mod export;
+mod polling;
+/// A module related to proposing trades from two inventories.
+pub mod proposals;
 mod request_types;
+/// A module related to accepting, declining, and countering inbound trades automatically.
+pub mod responder;
+/// A module related to watching trades for changes.
+pub mod watcher;
 
 const TRADES_API: &str = "https://trades.roblox.com/v1/trades/";
 const TRADE_DETAILS_API: &str = "https://trades.roblox.com/v1/trades/{trade_id}";
 const DECLINE_TRADE_API: &str = "https://trades.roblox.com/v1/trades/{trade_id}/decline";
 const SEND_TRADE_API: &str = "https://trades.roblox.com/v1/trades/send";
 const ACCEPT_TRADE_API: &str = "https://trades.roblox.com/v1/trades/{trade_id}/accept";
+const COUNTER_TRADE_API: &str = "https://trades.roblox.com/v1/trades/{trade_id}/counter";
 const TRADE_COUNT_API: &str = "https://trades.roblox.com/v1/trades/inbound/count";
 
 /// For requests related to trades, we use Descending as the sort order.
@@ -36,6 +55,10 @@ pub struct Trade {
     pub trade_id: u64,
     /// The details of the person you're trading with.
     pub partner: User,
+    /// The creation time of the trade in ISO 8601 format.
+    pub created: String,
+    /// The expiration time of the trade in ISO 8601 format.
+    pub expiration: String,
     /// Whether one of the parties can still act on the trade.
     pub is_active: bool,
     /// The status of the trade.
@@ -44,9 +67,7 @@ pub struct Trade {
 
 /// The status of a Roblox trade. [`Self::Open`] is the status for both
 /// inbound and outbound trades.
-#[derive(
-    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, Copy,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[allow(missing_docs)]
 pub enum TradeStatus {
     Open,
@@ -55,26 +76,34 @@ pub enum TradeStatus {
     #[default]
     Expired,
     RejectedDueToError,
+    /// The trade was countered with a new trade.
+    Countered,
+    /// A value not known by this crate.
+    Unknown(String),
 }
 
+string_enum!(TradeStatus {
+    Open => "Open",
+    Completed => "Completed",
+    Declined => "Declined",
+    Expired => "Expired",
+    RejectedDueToError => "RejectedDueToError",
+    Countered => "Countered",
+});
+
 impl std::str::FromStr for TradeStatus {
-    type Err = RoboatError;
+    type Err = std::convert::Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Open" => Ok(Self::Open),
-            "Completed" => Ok(Self::Completed),
-            "Declined" => Ok(Self::Declined),
-            "Expired" => Ok(Self::Expired),
-            "RejectedDueToError" => Ok(Self::RejectedDueToError),
-            _ => Err(RoboatError::MalformedResponse),
-        }
+        Ok(Self::from(s))
     }
 }
 
 /// The details of a trade.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 pub struct TradeDetails {
+    /// The id of the trade.
+    pub trade_id: u64,
     /// Your partner in the trade deal.
     pub partner: User,
     /// The items you're offering.
@@ -95,6 +124,22 @@ pub struct TradeDetails {
     pub status: TradeStatus,
 }
 
+impl TradeDetails {
+    /// Returns the uaids of the items you're offering.
+    ///
+    /// Useful for building a counter offer with [`Client::counter_trade`].
+    pub fn your_uaids(&self) -> Vec<u64> {
+        self.your_items.iter().map(|x| x.uaid).collect()
+    }
+
+    /// Returns the uaids of the items your partner is offering.
+    ///
+    /// Useful for building a counter offer with [`Client::counter_trade`].
+    pub fn partner_uaids(&self) -> Vec<u64> {
+        self.partner_items.iter().map(|x| x.uaid).collect()
+    }
+}
+
 /// The details of an item in a trade. This is separate from other item structs
 #[allow(missing_docs)]
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
@@ -165,15 +210,15 @@ impl Client {
             TRADES_API, trade_type_str, SORT_ORDER, cursor, limit
         );
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(&formatted_url)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::InboundTradesResponse>(response).await?;
+        let response = self.send_request(request_builder, "trades.trades").await?;
+        let raw = self
+            .parse_to_raw::<request_types::InboundTradesResponse>(response)
+            .await?;
 
         let next_cursor = raw.next_page_cursor;
 
@@ -189,6 +234,8 @@ impl Client {
             let trade = Trade {
                 trade_id: trade.id as u64,
                 partner,
+                created: trade.created,
+                expiration: trade.expiration,
                 is_active: trade.is_active,
                 status: trade.status,
             };
@@ -229,15 +276,17 @@ impl Client {
         let formatted_url = TRADE_DETAILS_API.replace("{trade_id}", &trade_id.to_string());
         let cookie_string = self.cookie_string()?;
 
-        let response_result = self
+        let request_builder = self
             .reqwest_client
             .get(&formatted_url)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(response_result).await?;
-        let raw = Self::parse_to_raw::<request_types::TradeDetailsResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "trades.trade_details")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::TradeDetailsResponse>(response)
+            .await?;
 
         let partner = User {
             user_id: raw.offers[1].user.id as u64,
@@ -280,9 +329,8 @@ impl Client {
         let expiration = raw.expiration;
         let is_active = raw.is_active;
 
-        let trade_status = TradeStatus::from_str(&raw.status)?;
-
         let trade_details = TradeDetails {
+            trade_id: raw.id as u64,
             partner,
             your_items,
             partner_items,
@@ -291,7 +339,7 @@ impl Client {
             created,
             expiration,
             is_active,
-            status: trade_status,
+            status: raw.status,
         };
 
         Ok(trade_details)
@@ -332,7 +380,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.decline_trade_internal(trade_id).await
+                    Self::retry(self.decline_trade_internal(trade_id)).await
                 }
                 _ => Err(e),
             },
@@ -346,6 +394,11 @@ impl Client {
     /// # Notes
     /// * Requires a valid roblosecurity.
     /// * Will repeat once if the x-csrf-token is invalid.
+    /// * The offer is checked against the limits cached by [`Client::trade_metadata`] (or
+    ///   [`TradeMetadata::default`]) before it is sent. Use [`Client::can_trade_with`] to check the partner.
+    ///   The limits that depend on the value of the items cannot be checked here, use
+    ///   [`TradeMetadata::check_offer_values`] for them.
+    /// * Trading with the account itself is only rejected early if the user id of the account is cached.
     ///
     /// # Argument Notes
     /// * `your_robux` and `partner` is before 30% tax.
@@ -358,6 +411,7 @@ impl Client {
     /// * All errors under [Standard Errors](#standard-errors).
     /// * All errors under [Auth Required Errors](#auth-required-errors).
     /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    /// * [`RoboatError::InvalidTradeOffer`] - If the offer breaks a limit of [`TradeMetadata`].
     ///
     /// # Example
     /// ```no_run
@@ -397,6 +451,16 @@ impl Client {
         partner_item_uaids: Vec<u64>,
         partner_robux: u64,
     ) -> Result<u64, RoboatError> {
+        self.check_trade_offer(
+            "trades.send_trade",
+            partner_id,
+            &your_item_uaids,
+            your_robux,
+            &partner_item_uaids,
+            partner_robux,
+        )
+        .await?;
+
         match self
             .send_trade_internal(
                 partner_id,
@@ -412,13 +476,197 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.send_trade_internal(
+                    Self::retry(self.send_trade_internal(
                         partner_id,
                         your_item_uaids,
                         your_robux,
                         partner_item_uaids,
                         partner_robux,
-                    )
+                    ))
+                    .await
+                }
+                _ => Err(e),
+            },
+        }
+    }
+
+    /// Counters a trade using <https://trades.roblox.com/v1/trades/{trade_id}/counter>.
+    ///
+    /// The countered trade is replaced by a new trade with the given offer, sent to the same partner.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * Will repeat once if the x-csrf-token is invalid.
+    /// * Fetches the trade using [`Client::trade_details`] first to find the partner. If the partner is
+    ///   already known, use [`Client::counter_trade_with_partner`] instead to skip this request.
+    /// * The offer is checked against the limits cached by [`Client::trade_metadata`] (or
+    ///   [`TradeMetadata::default`]) before it is sent. The limits that depend on the value of the items
+    ///   cannot be checked here, use [`TradeMetadata::check_offer_values`] for them.
+    /// * Trading with the account itself is only rejected early if the user id of the account is cached.
+    ///
+    /// # Argument Notes
+    /// * `your_robux` and `partner_robux` are before 30% tax.
+    /// * Uaids are NOT item/asset ids. They are unique ids for each item.
+    /// * To change an offer instead of writing a new one, start from [`TradeDetails::your_uaids`]
+    ///   and [`TradeDetails::partner_uaids`].
+    ///
+    /// # Return Notes
+    /// * The value returned on success is the id of the new trade.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    /// * All errors under [2-Factor Authentication / Captcha Required Errors](#2-factor-authentication--captcha-required-errors).
+    /// * [`RoboatError::InvalidTradeOffer`] - If the offer breaks a limit of [`TradeMetadata`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    /// const TRADE_ID: u64 = 123456789;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// // Ask for the same items, but offer 100 robux less.
+    /// let details = client.trade_details(TRADE_ID).await?;
+    ///
+    /// let new_trade_id = client
+    ///     .counter_trade(
+    ///         details.trade_id,
+    ///         details.your_uaids(),
+    ///         details.your_robux.saturating_sub(100),
+    ///         details.partner_uaids(),
+    ///         details.partner_robux,
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("Countered Trade! New Trade ID: {}", new_trade_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn counter_trade(
+        &self,
+        trade_id: u64,
+        your_item_uaids: Vec<u64>,
+        your_robux: u64,
+        partner_item_uaids: Vec<u64>,
+        partner_robux: u64,
+    ) -> Result<u64, RoboatError> {
+        let partner_id = self.trade_details(trade_id).await?.partner.user_id;
+
+        self.counter_trade_with_partner(
+            trade_id,
+            partner_id,
+            your_item_uaids,
+            your_robux,
+            partner_item_uaids,
+            partner_robux,
+        )
+        .await
+    }
+
+    /// Counters a trade with a known partner using <https://trades.roblox.com/v1/trades/{trade_id}/counter>.
+    ///
+    /// This is the same as [`Client::counter_trade`], except that the partner is not fetched first.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * Will repeat once if the x-csrf-token is invalid.
+    /// * The offer is checked against the limits cached by [`Client::trade_metadata`] (or
+    ///   [`TradeMetadata::default`]) before it is sent. The limits that depend on the value of the items
+    ///   cannot be checked here, use [`TradeMetadata::check_offer_values`] for them.
+    /// * Trading with the account itself is only rejected early if the user id of the account is cached.
+    ///
+    /// # Argument Notes
+    /// * `partner_id` must be the user id of the partner of the trade, such as [`TradeDetails::partner`].
+    /// * See [`Client::counter_trade`] for the other arguments.
+    ///
+    /// # Return Notes
+    /// * The value returned on success is the id of the new trade.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](#standard-errors).
+    /// * All errors under [Auth Required Errors](#auth-required-errors).
+    /// * All errors under [X-CSRF-TOKEN Required Errors](#x-csrf-token-required-errors).
+    /// * All errors under [2-Factor Authentication / Captcha Required Errors](#2-factor-authentication--captcha-required-errors).
+    /// * [`RoboatError::InvalidTradeOffer`] - If the offer breaks a limit of [`TradeMetadata`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use roboat::ClientBuilder;
+    ///
+    /// const ROBLOSECURITY: &str = "roblosecurity";
+    /// const TRADE_ID: u64 = 123456789;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+    ///
+    /// let details = client.trade_details(TRADE_ID).await?;
+    ///
+    /// // The details already contain the partner, so they are not fetched again.
+    /// let new_trade_id = client
+    ///     .counter_trade_with_partner(
+    ///         details.trade_id,
+    ///         details.partner.user_id,
+    ///         details.your_uaids(),
+    ///         details.your_robux.saturating_sub(100),
+    ///         details.partner_uaids(),
+    ///         details.partner_robux,
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("Countered Trade! New Trade ID: {}", new_trade_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn counter_trade_with_partner(
+        &self,
+        trade_id: u64,
+        partner_id: u64,
+        your_item_uaids: Vec<u64>,
+        your_robux: u64,
+        partner_item_uaids: Vec<u64>,
+        partner_robux: u64,
+    ) -> Result<u64, RoboatError> {
+        self.check_trade_offer(
+            "trades.counter_trade",
+            partner_id,
+            &your_item_uaids,
+            your_robux,
+            &partner_item_uaids,
+            partner_robux,
+        )
+        .await?;
+
+        match self
+            .counter_trade_internal(
+                trade_id,
+                partner_id,
+                your_item_uaids.clone(),
+                your_robux,
+                partner_item_uaids.clone(),
+                partner_robux,
+            )
+            .await
+        {
+            Ok(x) => Ok(x),
+            Err(e) => match e {
+                RoboatError::InvalidXcsrf(new_xcsrf) => {
+                    self.set_xcsrf(new_xcsrf).await;
+
+                    Self::retry(self.counter_trade_internal(
+                        trade_id,
+                        partner_id,
+                        your_item_uaids,
+                        your_robux,
+                        partner_item_uaids,
+                        partner_robux,
+                    ))
                     .await
                 }
                 _ => Err(e),
@@ -473,7 +721,7 @@ impl Client {
                 RoboatError::InvalidXcsrf(new_xcsrf) => {
                     self.set_xcsrf(new_xcsrf).await;
 
-                    self.accept_trade_internal(trade_id).await
+                    Self::retry(self.accept_trade_internal(trade_id)).await
                 }
                 _ => Err(e),
             },
@@ -510,22 +758,26 @@ impl Client {
     pub async fn trade_count(&self) -> Result<u64, RoboatError> {
         let cookie_string = self.cookie_string()?;
 
-        let response_result = self
+        let request_builder = self
             .reqwest_client
             .get(TRADE_COUNT_API)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(response_result).await?;
-        let raw = Self::parse_to_raw::<request_types::TradeCountResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "trades.trade_count")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::TradeCountResponse>(response)
+            .await?;
 
         Ok(raw.count)
     }
 }
 
 mod internal {
-    use super::{request_types, ACCEPT_TRADE_API, DECLINE_TRADE_API, SEND_TRADE_API};
+    use super::{
+        request_types, ACCEPT_TRADE_API, COUNTER_TRADE_API, DECLINE_TRADE_API, SEND_TRADE_API,
+    };
     use crate::{Client, RoboatError, XCSRF_HEADER};
     use reqwest::header;
 
@@ -538,15 +790,14 @@ mod internal {
             let cookie_string = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
-            let response_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(&formatted_url)
                 .header(header::COOKIE, cookie_string)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            Self::validate_request_result(response_result).await?;
+            self.send_mutating_request(request_builder, "trades.decline_trade")
+                .await?;
 
             Ok(())
         }
@@ -562,6 +813,82 @@ mod internal {
             let cookie_string = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
+            let body = self
+                .send_trade_body(
+                    partner_id,
+                    your_item_uaids,
+                    your_robux,
+                    partner_item_uaids,
+                    partner_robux,
+                )
+                .await?;
+
+            let request_builder = self
+                .reqwest_client
+                .post(SEND_TRADE_API)
+                .header(header::COOKIE, cookie_string)
+                .header(XCSRF_HEADER, xcsrf)
+                .json(&body);
+
+            let response = self
+                .send_mutating_request(request_builder, "trades.send_trade")
+                .await?;
+            let raw = self
+                .parse_to_raw::<request_types::SendTradeResponse>(response)
+                .await?;
+
+            Ok(raw.id)
+        }
+
+        pub(super) async fn counter_trade_internal(
+            &self,
+            trade_id: u64,
+            partner_id: u64,
+            your_item_uaids: Vec<u64>,
+            your_robux: u64,
+            partner_item_uaids: Vec<u64>,
+            partner_robux: u64,
+        ) -> Result<u64, RoboatError> {
+            let formatted_url = COUNTER_TRADE_API.replace("{trade_id}", &trade_id.to_string());
+            let cookie_string = self.cookie_string()?;
+            let xcsrf = self.xcsrf().await;
+
+            let body = self
+                .send_trade_body(
+                    partner_id,
+                    your_item_uaids,
+                    your_robux,
+                    partner_item_uaids,
+                    partner_robux,
+                )
+                .await?;
+
+            let request_builder = self
+                .reqwest_client
+                .post(&formatted_url)
+                .header(header::COOKIE, cookie_string)
+                .header(XCSRF_HEADER, xcsrf)
+                .json(&body);
+
+            let response = self
+                .send_mutating_request(request_builder, "trades.counter_trade")
+                .await?;
+            let raw = self
+                .parse_to_raw::<request_types::SendTradeResponse>(response)
+                .await?;
+
+            Ok(raw.id)
+        }
+
+        /// Builds the body used to send or counter a trade.
+        async fn send_trade_body(
+            &self,
+            partner_id: u64,
+            your_item_uaids: Vec<u64>,
+            your_robux: u64,
+            partner_item_uaids: Vec<u64>,
+            partner_robux: u64,
+        ) -> Result<request_types::SendTradeBody, RoboatError> {
             let user_id = self.user_id().await?;
             let user_trade_offer = request_types::SendTradeOffer {
                 user_id,
@@ -575,24 +902,10 @@ mod internal {
                 robux: partner_robux,
             };
 
-            let body = request_types::SendTradeBody {
+            Ok(request_types::SendTradeBody {
                 // The partner trade offer always comes first.
                 offers: vec![partner_trade_offer, user_trade_offer],
-            };
-
-            let response_result = self
-                .reqwest_client
-                .post(SEND_TRADE_API)
-                .header(header::COOKIE, cookie_string)
-                .header(XCSRF_HEADER, xcsrf)
-                .json(&body)
-                .send()
-                .await;
-
-            let response = Self::validate_request_result(response_result).await?;
-            let raw = Self::parse_to_raw::<request_types::SendTradeResponse>(response).await?;
-
-            Ok(raw.id)
+            })
         }
 
         pub(super) async fn accept_trade_internal(&self, trade_id: u64) -> Result<(), RoboatError> {
@@ -600,15 +913,14 @@ mod internal {
             let cookie_string = self.cookie_string()?;
             let xcsrf = self.xcsrf().await;
 
-            let response_result = self
+            let request_builder = self
                 .reqwest_client
                 .post(&formatted_url)
                 .header(header::COOKIE, cookie_string)
-                .header(XCSRF_HEADER, xcsrf)
-                .send()
-                .await;
+                .header(XCSRF_HEADER, xcsrf);
 
-            Self::validate_request_result(response_result).await?;
+            self.send_mutating_request(request_builder, "trades.accept_trade")
+                .await?;
 
             // The response is empty, so we just return Ok(()).
             Ok(())
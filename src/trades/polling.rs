@@ -0,0 +1,59 @@
+//! The background loop shared by [`TradeWatcher::spawn`](super::watcher::TradeWatcher::spawn)
+//! and [`TradeResponder::spawn`](super::responder::TradeResponder::spawn).
+
+use crate::RoboatError;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// The capacity of the channel returned by [`spawn`].
+const EVENT_CHANNEL_CAPACITY: usize = 100;
+
+/// Something that can be polled for events at a fixed interval.
+pub(crate) trait Poller: Send + Sync + 'static {
+    /// The type of the events returned by a poll.
+    type Event: Send + 'static;
+
+    /// Polls once, returning the events found.
+    fn poll_events(&self) -> impl Future<Output = Result<Vec<Self::Event>, RoboatError>> + Send;
+
+    /// Returns the time waited between polls.
+    fn interval(&self) -> Duration;
+}
+
+/// Polls `poller` every [`Poller::interval`] in a background task, and sends every event
+/// (or error) to the returned receiver.
+///
+/// The task stops once the receiver is dropped.
+pub(crate) fn spawn<P: Poller>(
+    poller: P,
+) -> (
+    JoinHandle<()>,
+    mpsc::Receiver<Result<P::Event, RoboatError>>,
+) {
+    let (sender, receiver) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+
+    let handle = tokio::spawn(async move {
+        loop {
+            match poller.poll_events().await {
+                Ok(events) => {
+                    for event in events {
+                        if sender.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(e) => {
+                    if sender.send(Err(e)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+
+            tokio::time::sleep(poller.interval()).await;
+        }
+    });
+
+    (handle, receiver)
+}
@@ -0,0 +1,803 @@
+//! Generation of trade proposals from two inventories.
+//!
+//! A [`TradeProposer`] searches the collectibles of both sides for item-for-item trades that
+//! satisfy a set of [`ProposalConstraints`], and ranks them by the value you gain. The search
+//! does not make any request, and always returns the same proposals for the same inventories,
+//! so it can be run offline with synthetic inventories.
+//!
+//! # Example
+//! ```
+//! use roboat::inventory::Collectible;
+//! use roboat::trades::proposals::{ProposalConstraints, TradeProposer};
+//! use std::collections::HashMap;
+//!
+//! let ours = vec![
+//!     Collectible { uaid: 1, asset_id: 10, rap: 1000, ..Default::default() },
+//!     Collectible { uaid: 2, asset_id: 20, rap: 400, ..Default::default() },
+//! ];
+//!
+//! let theirs = vec![
+//!     Collectible { uaid: 3, asset_id: 30, rap: 1200, ..Default::default() },
+//!     Collectible { uaid: 4, asset_id: 40, rap: 500, ..Default::default() },
+//! ];
+//!
+//! // Item 30 is only worth 900, and we never give away item 10.
+//! let proposer = TradeProposer::new()
+//!     .values(HashMap::from([(10, 1000), (20, 400), (30, 900), (40, 500)]))
+//!     .constraints(ProposalConstraints::new().min_gain(50).keep_asset(10));
+//!
+//! let proposals = proposer.propose(&ours, &theirs);
+//!
+//! assert_eq!(proposals.len(), 1);
+//! assert_eq!(proposals[0].your_uaids(), vec![2]);
+//! assert_eq!(proposals[0].partner_uaids(), vec![4]);
+//! assert_eq!(proposals[0].net_gain, 100);
+//! ```
+
+use super::analysis::{RapValues, SideValue, ValueProvider};
+use super::{TradeItem, TradeMetadata};
+use crate::inventory::Collectible;
+use crate::{Client, RoboatError};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The default value of [`ProposalConstraints::min_gain`].
+const DEFAULT_MIN_GAIN: i64 = 1;
+/// The default value of [`ProposalConstraints::max_proposals`].
+const DEFAULT_MAX_PROPOSALS: usize = 10;
+/// The default value of [`ProposalConstraints::pool_size`].
+const DEFAULT_POOL_SIZE: usize = 10;
+/// The maximum value of [`ProposalConstraints::pool_size`].
+const MAX_POOL_SIZE: usize = 20;
+/// The default value of [`TradeProposer::send_interval`].
+const DEFAULT_SEND_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The rules every proposal made by a [`TradeProposer`] must follow.
+///
+/// # Example
+/// ```
+/// use roboat::trades::proposals::ProposalConstraints;
+///
+/// // Gain at least 100, give at most 2 items, and never give item 1365767.
+/// let constraints = ProposalConstraints::new()
+///     .min_gain(100)
+///     .max_items_per_side(2)
+///     .keep_asset(1365767);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalConstraints {
+    min_gain: i64,
+    max_gain: Option<i64>,
+    max_items_per_side: Option<u64>,
+    kept_uaids: HashSet<u64>,
+    kept_assets: HashSet<u64>,
+    allow_projected: bool,
+    max_proposals: usize,
+    pool_size: usize,
+}
+
+impl Default for ProposalConstraints {
+    fn default() -> Self {
+        Self {
+            min_gain: DEFAULT_MIN_GAIN,
+            max_gain: None,
+            max_items_per_side: None,
+            kept_uaids: HashSet::new(),
+            kept_assets: HashSet::new(),
+            allow_projected: false,
+            max_proposals: DEFAULT_MAX_PROPOSALS,
+            pool_size: DEFAULT_POOL_SIZE,
+        }
+    }
+}
+
+impl ProposalConstraints {
+    /// Creates constraints that only require a proposal to gain value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum value you must gain from a proposal. Defaults to 1.
+    pub fn min_gain(mut self, min_gain: i64) -> Self {
+        self.min_gain = min_gain;
+        self
+    }
+
+    /// Sets the maximum value you can gain from a proposal. Proposals that take too much value
+    /// from the partner are rarely accepted. Not set by default.
+    pub fn max_gain(mut self, max_gain: i64) -> Self {
+        self.max_gain = Some(max_gain);
+        self
+    }
+
+    /// Sets the maximum amount of items each side can offer. Always capped at
+    /// [`TradeMetadata::max_items_per_side`].
+    pub fn max_items_per_side(mut self, max_items_per_side: u64) -> Self {
+        self.max_items_per_side = Some(max_items_per_side);
+        self
+    }
+
+    /// Never offers the copy with this uaid. Can be called multiple times.
+    pub fn keep_uaid(mut self, uaid: u64) -> Self {
+        self.kept_uaids.insert(uaid);
+        self
+    }
+
+    /// Never offers any copy of this asset. Can be called multiple times.
+    pub fn keep_asset(mut self, asset_id: u64) -> Self {
+        self.kept_assets.insert(asset_id);
+        self
+    }
+
+    /// Sets whether items of the partner that are [projected](ValueProvider::is_projected)
+    /// can be asked for. Defaults to false.
+    pub fn allow_projected(mut self, allow_projected: bool) -> Self {
+        self.allow_projected = allow_projected;
+        self
+    }
+
+    /// Sets the maximum amount of proposals returned. Defaults to 10.
+    pub fn max_proposals(mut self, max_proposals: usize) -> Self {
+        self.max_proposals = max_proposals;
+        self
+    }
+
+    /// Sets how many of the most valuable items of each side are searched. Defaults to 10,
+    /// and is capped at 20.
+    ///
+    /// Every combination of up to [`ProposalConstraints::max_items_per_side`] items is tried,
+    /// so the search gets much slower as this grows.
+    pub fn pool_size(mut self, pool_size: usize) -> Self {
+        self.pool_size = pool_size;
+        self
+    }
+}
+
+/// A trade proposed by a [`TradeProposer`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct TradeProposal {
+    /// The items you offer.
+    pub your_items: Vec<Collectible>,
+    /// The items you ask the partner for.
+    pub partner_items: Vec<Collectible>,
+    /// The value of your side.
+    pub yours: SideValue,
+    /// The value of the partner's side.
+    pub partner: SideValue,
+    /// The value of the partner's items minus the value of yours.
+    pub net_gain: i64,
+}
+
+impl TradeProposal {
+    /// Returns the uaids of the items you offer.
+    pub fn your_uaids(&self) -> Vec<u64> {
+        self.your_items.iter().map(|x| x.uaid).collect()
+    }
+
+    /// Returns the uaids of the items you ask the partner for.
+    pub fn partner_uaids(&self) -> Vec<u64> {
+        self.partner_items.iter().map(|x| x.uaid).collect()
+    }
+}
+
+/// An item considered by the search, with its value.
+struct Candidate<'a> {
+    collectible: &'a Collectible,
+    value: u64,
+}
+
+/// A combination of items from one side.
+struct Combination {
+    indices: Vec<usize>,
+    uaids: Vec<u64>,
+    value: u64,
+    rap: u64,
+}
+
+/// A pair of combinations that follows the constraints. A proposal ranked before another
+/// compares as less than it.
+struct Match<'a> {
+    yours: &'a Combination,
+    partner: &'a Combination,
+    net_gain: i64,
+}
+
+impl Match<'_> {
+    fn item_count(&self) -> usize {
+        self.yours.indices.len() + self.partner.indices.len()
+    }
+}
+
+impl Ord for Match<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .net_gain
+            .cmp(&self.net_gain)
+            .then_with(|| self.item_count().cmp(&other.item_count()))
+            .then_with(|| self.yours.uaids.cmp(&other.yours.uaids))
+            .then_with(|| self.partner.uaids.cmp(&other.partner.uaids))
+    }
+}
+
+impl PartialOrd for Match<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Match<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Match<'_> {}
+
+/// Proposes item-for-item trades between two inventories.
+///
+/// # Notes
+/// * Items are valued using [`RapValues`] unless [`TradeProposer::values`] is used. Items whose
+///   value is unknown are valued by their rap.
+/// * Trade limits are taken from [`TradeMetadata::default`] unless [`TradeProposer::metadata`] is used.
+/// * Items on hold are never proposed, and the same asset is never on both sides of a proposal.
+/// * Proposals never include robux.
+/// * Proposals are ranked by net gain, then by the amount of items, then by uaid, so the same
+///   inventories always give the same proposals.
+///
+/// # Example
+/// ```no_run
+/// use roboat::trades::proposals::{ProposalConstraints, TradeProposer};
+/// use roboat::{ClientBuilder, Limit};
+///
+/// const ROBLOSECURITY: &str = "roblosecurity";
+/// const PARTNER_ID: u64 = 1;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build();
+///
+/// let user_id = client.user_id().await?;
+/// let (ours, _) = client.collectibles(user_id, Limit::Hundred, None).await?;
+/// let (theirs, _) = client.collectibles(PARTNER_ID, Limit::Hundred, None).await?;
+///
+/// let proposer = TradeProposer::new()
+///     .metadata(client.trade_metadata().await?)
+///     .constraints(ProposalConstraints::new().min_gain(100).max_gain(1000));
+///
+/// let proposals = proposer.propose(&ours, &theirs);
+///
+/// // Sends the 3 best proposals, one every 10 seconds.
+/// for result in proposer.send(&client, PARTNER_ID, &proposals, 3).await {
+///     println!("{:?}", result);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TradeProposer {
+    values: Arc<dyn ValueProvider>,
+    metadata: TradeMetadata,
+    constraints: ProposalConstraints,
+    send_interval: Duration,
+}
+
+impl Default for TradeProposer {
+    fn default() -> Self {
+        Self {
+            values: Arc::new(RapValues),
+            metadata: TradeMetadata::default(),
+            constraints: ProposalConstraints::default(),
+            send_interval: DEFAULT_SEND_INTERVAL,
+        }
+    }
+}
+
+impl TradeProposer {
+    /// Creates a proposer that values items by their rap, using the default [`ProposalConstraints`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the source of item values.
+    pub fn values<T: ValueProvider + 'static>(mut self, values: T) -> Self {
+        self.values = Arc::new(values);
+        self
+    }
+
+    /// Sets the trade limits used. Usually fetched with
+    /// [`Client::trade_metadata`](crate::Client::trade_metadata).
+    pub fn metadata(mut self, metadata: TradeMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets the rules every proposal must follow.
+    pub fn constraints(mut self, constraints: ProposalConstraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Sets the time waited between trades sent by [`TradeProposer::send`]. Defaults to 10 seconds.
+    pub fn send_interval(mut self, send_interval: Duration) -> Self {
+        self.send_interval = send_interval;
+        self
+    }
+
+    /// Returns the best proposals between your collectibles and the partner's, best first.
+    pub fn propose(&self, ours: &[Collectible], theirs: &[Collectible]) -> Vec<TradeProposal> {
+        let constraints = &self.constraints;
+
+        let your_pool = self.pool(ours, |x| {
+            !constraints.kept_uaids.contains(&x.uaid)
+                && !constraints.kept_assets.contains(&x.asset_id)
+        });
+        let partner_pool = self.pool(theirs, |x| {
+            constraints.allow_projected || !self.values.is_projected(&trade_item(x))
+        });
+
+        let max_items = constraints
+            .max_items_per_side
+            .unwrap_or(u64::MAX)
+            .min(self.metadata.max_items_per_side) as usize;
+
+        let max_proposals = constraints.max_proposals;
+
+        if max_proposals == 0 {
+            return Vec::new();
+        }
+
+        let your_combinations = combinations(&your_pool, max_items);
+        let mut partner_combinations = combinations(&partner_pool, max_items);
+        partner_combinations.sort_by_key(|x| x.value);
+
+        // A max-heap of the best matches found so far, with the worst of them on top.
+        let mut best = BinaryHeap::with_capacity(max_proposals + 1);
+
+        for yours in &your_combinations {
+            let gain = |partner: &Combination| partner.value as i64 - yours.value as i64;
+
+            let start = partner_combinations.partition_point(|x| gain(x) < constraints.min_gain);
+            let end = match constraints.max_gain {
+                Some(max_gain) => partner_combinations.partition_point(|x| gain(x) <= max_gain),
+                None => partner_combinations.len(),
+            };
+
+            // Partners are visited from the most valuable, so the search stops as soon as the
+            // gain is too low to replace any of the best matches.
+            for partner in partner_combinations[start..end.max(start)].iter().rev() {
+                let net_gain = gain(partner);
+
+                if best.len() == max_proposals
+                    && best
+                        .peek()
+                        .is_some_and(|worst: &Match| net_gain < worst.net_gain)
+                {
+                    break;
+                }
+
+                if self
+                    .metadata
+                    .check_offer_values(yours.rap, 0, partner.rap, 0)
+                    .is_err()
+                    || shares_asset(&your_pool, yours, &partner_pool, partner)
+                {
+                    continue;
+                }
+
+                best.push(Match {
+                    yours,
+                    partner,
+                    net_gain,
+                });
+
+                if best.len() > max_proposals {
+                    best.pop();
+                }
+            }
+        }
+
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|x| TradeProposal {
+                your_items: collectibles(&your_pool, x.yours),
+                partner_items: collectibles(&partner_pool, x.partner),
+                yours: side_value(x.yours),
+                partner: side_value(x.partner),
+                net_gain: x.net_gain,
+            })
+            .collect()
+    }
+
+    /// Sends the first `count` proposals to a partner using [`Client::send_trade`], waiting
+    /// [`TradeProposer::send_interval`] between each trade.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * Stops early if [`RoboatError::TooManyRequests`] is returned.
+    ///
+    /// # Return Value Notes
+    /// * One result is returned per proposal sent, in order. A successful result is the trade id.
+    pub async fn send(
+        &self,
+        client: &Client,
+        partner_id: u64,
+        proposals: &[TradeProposal],
+        count: usize,
+    ) -> Vec<Result<u64, RoboatError>> {
+        let mut results = Vec::new();
+
+        for (i, proposal) in proposals.iter().take(count).enumerate() {
+            if i > 0 {
+                tokio::time::sleep(self.send_interval).await;
+            }
+
+            let result = client
+                .send_trade(
+                    partner_id,
+                    proposal.your_uaids(),
+                    0,
+                    proposal.partner_uaids(),
+                    0,
+                )
+                .await;
+
+            let rate_limited = matches!(result, Err(RoboatError::TooManyRequests));
+            results.push(result);
+
+            if rate_limited {
+                break;
+            }
+        }
+
+        results
+    }
+
+    /// Returns the most valuable tradable items that pass `filter`, most valuable first.
+    fn pool<'a>(
+        &self,
+        collectibles: &'a [Collectible],
+        filter: impl Fn(&Collectible) -> bool,
+    ) -> Vec<Candidate<'a>> {
+        let mut pool: Vec<Candidate> = collectibles
+            .iter()
+            .filter(|x| x.is_tradable() && filter(x))
+            .map(|x| Candidate {
+                collectible: x,
+                value: self.values.value(&trade_item(x)).unwrap_or(x.rap),
+            })
+            .collect();
+
+        pool.sort_by(|a, b| match b.value.cmp(&a.value) {
+            Ordering::Equal => a.collectible.uaid.cmp(&b.collectible.uaid),
+            x => x,
+        });
+
+        pool.truncate(self.constraints.pool_size.min(MAX_POOL_SIZE));
+        pool
+    }
+}
+
+fn trade_item(collectible: &Collectible) -> TradeItem {
+    TradeItem {
+        item_id: collectible.asset_id,
+        serial_number: collectible.serial_number,
+        uaid: collectible.uaid,
+        name: collectible.name.clone(),
+        rap: collectible.rap,
+    }
+}
+
+/// Returns every combination of 1 to `max_items` items of the pool, in lexicographic order.
+fn combinations(pool: &[Candidate], max_items: usize) -> Vec<Combination> {
+    let mut output = Vec::new();
+    let mut indices = Vec::new();
+
+    fn recurse(
+        pool: &[Candidate],
+        max_items: usize,
+        start: usize,
+        indices: &mut Vec<usize>,
+        output: &mut Vec<Combination>,
+    ) {
+        for i in start..pool.len() {
+            indices.push(i);
+
+            output.push(Combination {
+                indices: indices.clone(),
+                uaids: indices.iter().map(|&x| pool[x].collectible.uaid).collect(),
+                value: indices.iter().map(|&x| pool[x].value).sum(),
+                rap: indices.iter().map(|&x| pool[x].collectible.rap).sum(),
+            });
+
+            if indices.len() < max_items {
+                recurse(pool, max_items, i + 1, indices, output);
+            }
+
+            indices.pop();
+        }
+    }
+
+    recurse(pool, max_items, 0, &mut indices, &mut output);
+    output
+}
+
+fn shares_asset(
+    your_pool: &[Candidate],
+    yours: &Combination,
+    partner_pool: &[Candidate],
+    partner: &Combination,
+) -> bool {
+    yours.indices.iter().any(|&x| {
+        partner
+            .indices
+            .iter()
+            .any(|&y| your_pool[x].collectible.asset_id == partner_pool[y].collectible.asset_id)
+    })
+}
+
+fn collectibles(pool: &[Candidate], combination: &Combination) -> Vec<Collectible> {
+    combination
+        .indices
+        .iter()
+        .map(|&x| pool[x].collectible.clone())
+        .collect()
+}
+
+fn side_value(combination: &Combination) -> SideValue {
+    SideValue {
+        items_value: combination.value,
+        rap: combination.rap,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProposalConstraints, TradeProposer};
+    use crate::inventory::Collectible;
+    use crate::trades::TradeMetadata;
+    use std::collections::HashMap;
+
+    fn item(uaid: u64, asset_id: u64, rap: u64) -> Collectible {
+        Collectible {
+            uaid,
+            asset_id,
+            rap,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the uaids of each side and the net gain of every proposal, in order.
+    fn propose(
+        proposer: &TradeProposer,
+        ours: &[Collectible],
+        theirs: &[Collectible],
+    ) -> Vec<(Vec<u64>, Vec<u64>, i64)> {
+        proposer
+            .propose(ours, theirs)
+            .into_iter()
+            .map(|x| (x.your_uaids(), x.partner_uaids(), x.net_gain))
+            .collect()
+    }
+
+    #[test]
+    fn ranks_by_gain_then_item_count() {
+        let ours = [item(1, 10, 100), item(2, 20, 60), item(5, 50, 40)];
+        let theirs = [item(3, 30, 200)];
+
+        // {2} and {5} alone are worth less than half of {3}, and {1, 2, 5} gains nothing.
+        assert_eq!(
+            propose(&TradeProposer::new(), &ours, &theirs),
+            vec![
+                (vec![1], vec![3], 100),
+                (vec![2, 5], vec![3], 100),
+                (vec![1, 5], vec![3], 60),
+                (vec![1, 2], vec![3], 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn breaks_ties_by_uaid() {
+        let ours = [item(2, 20, 100), item(1, 10, 100)];
+        let theirs = [item(4, 40, 150), item(3, 30, 150)];
+        let proposer =
+            TradeProposer::new().constraints(ProposalConstraints::new().max_items_per_side(1));
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![
+                (vec![1], vec![3], 50),
+                (vec![1], vec![4], 50),
+                (vec![2], vec![3], 50),
+                (vec![2], vec![4], 50),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_only_the_best_proposals() {
+        let ours = [item(2, 20, 100), item(1, 10, 100)];
+        let theirs = [item(4, 40, 150), item(3, 30, 150), item(6, 60, 120)];
+        let proposer = TradeProposer::new().constraints(
+            ProposalConstraints::new()
+                .max_items_per_side(1)
+                .max_proposals(3),
+        );
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![
+                (vec![1], vec![3], 50),
+                (vec![1], vec![4], 50),
+                (vec![2], vec![3], 50),
+            ]
+        );
+    }
+
+    #[test]
+    fn follows_min_and_max_gain() {
+        let ours = [item(1, 10, 100)];
+        let theirs = [item(2, 20, 110), item(3, 30, 150), item(4, 40, 190)];
+        let proposer = TradeProposer::new().constraints(
+            ProposalConstraints::new()
+                .max_items_per_side(1)
+                .min_gain(20)
+                .max_gain(60),
+        );
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![1], vec![3], 50)]
+        );
+    }
+
+    #[test]
+    fn never_offers_kept_uaids() {
+        let ours = [item(1, 10, 100), item(2, 20, 100)];
+        let theirs = [item(3, 30, 150)];
+        let proposer = TradeProposer::new().constraints(
+            ProposalConstraints::new()
+                .max_items_per_side(1)
+                .keep_uaid(1),
+        );
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![2], vec![3], 50)]
+        );
+    }
+
+    #[test]
+    fn never_offers_kept_assets() {
+        let ours = [item(1, 10, 100), item(2, 10, 100), item(5, 50, 100)];
+        let theirs = [item(3, 30, 150)];
+        let proposer = TradeProposer::new().constraints(
+            ProposalConstraints::new()
+                .max_items_per_side(1)
+                .keep_asset(10),
+        );
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![5], vec![3], 50)]
+        );
+    }
+
+    #[test]
+    fn excludes_projected_items_unless_allowed() {
+        let ours = [item(1, 10, 100)];
+        // Item 30 has a rap of 180 but is only worth 140, so it is projected.
+        let theirs = [item(3, 30, 180), item(4, 40, 120)];
+        let values = HashMap::from([(10, 100), (30, 140), (40, 120)]);
+        let constraints = ProposalConstraints::new().max_items_per_side(1);
+
+        let proposer = TradeProposer::new()
+            .values(values.clone())
+            .constraints(constraints.clone());
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![1], vec![4], 20)]
+        );
+
+        let proposer = TradeProposer::new()
+            .values(values)
+            .constraints(constraints.allow_projected(true));
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![1], vec![3], 40), (vec![1], vec![4], 20)]
+        );
+    }
+
+    #[test]
+    fn follows_max_items_per_side() {
+        let ours = [item(1, 10, 100), item(2, 20, 100)];
+        let theirs = [item(3, 30, 250)];
+
+        // A single item is worth less than half of item 3.
+        let proposer =
+            TradeProposer::new().constraints(ProposalConstraints::new().max_items_per_side(1));
+        assert_eq!(propose(&proposer, &ours, &theirs), vec![]);
+
+        let proposer =
+            TradeProposer::new().constraints(ProposalConstraints::new().max_items_per_side(2));
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![1, 2], vec![3], 50)]
+        );
+
+        // The limit of the trade metadata applies too.
+        let proposer = TradeProposer::new()
+            .metadata(TradeMetadata {
+                max_items_per_side: 1,
+                ..Default::default()
+            })
+            .constraints(ProposalConstraints::new().max_items_per_side(2));
+        assert_eq!(propose(&proposer, &ours, &theirs), vec![]);
+    }
+
+    #[test]
+    fn follows_min_value_ratio() {
+        let ours = [item(1, 10, 100)];
+        let theirs = [item(2, 20, 200), item(3, 30, 201)];
+
+        // 100 is exactly half of 200, but less than half of 201.
+        assert_eq!(
+            propose(&TradeProposer::new(), &ours, &theirs),
+            vec![(vec![1], vec![2], 100)]
+        );
+
+        let proposer = TradeProposer::new().metadata(TradeMetadata {
+            min_value_ratio: 0.6,
+            ..Default::default()
+        });
+        assert_eq!(propose(&proposer, &ours, &theirs), vec![]);
+    }
+
+    #[test]
+    fn skips_items_on_hold() {
+        let on_hold = |uaid, asset_id, rap| Collectible {
+            is_on_hold: true,
+            ..item(uaid, asset_id, rap)
+        };
+
+        let ours = [on_hold(1, 10, 100), item(2, 20, 100)];
+        let theirs = [on_hold(3, 30, 150), item(4, 40, 140)];
+        let proposer =
+            TradeProposer::new().constraints(ProposalConstraints::new().max_items_per_side(1));
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![2], vec![4], 40)]
+        );
+    }
+
+    #[test]
+    fn never_trades_an_asset_for_itself() {
+        let ours = [item(1, 10, 100)];
+        let theirs = [item(2, 10, 150)];
+
+        assert_eq!(propose(&TradeProposer::new(), &ours, &theirs), vec![]);
+    }
+
+    #[test]
+    fn only_searches_the_pool() {
+        let ours = [item(1, 10, 100), item(2, 20, 90)];
+        let theirs = [item(3, 30, 150)];
+        let proposer = TradeProposer::new().constraints(
+            ProposalConstraints::new()
+                .max_items_per_side(1)
+                .pool_size(1),
+        );
+
+        assert_eq!(
+            propose(&proposer, &ours, &theirs),
+            vec![(vec![1], vec![3], 50)]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::TradeStatus;
+use super::{CanTradeWithStatus, TradePrivacy, TradeStatus};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -39,7 +39,7 @@ pub(super) struct TradeDetailsResponse {
     pub created: String,
     pub expiration: Option<String>,
     pub is_active: bool,
-    pub status: String,
+    pub status: TradeStatus,
 }
 
 /// Each offer is a side of a trade
@@ -72,6 +72,7 @@ pub(super) struct UserAsset {
     pub membership_type: Option<String>,
 }
 
+/// Also used to counter a trade.
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct SendTradeBody {
@@ -96,3 +97,25 @@ pub(super) struct SendTradeResponse {
 pub(super) struct TradeCountResponse {
     pub count: u64,
 }
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct CanTradeWithResponse {
+    pub can_trade: bool,
+    pub status: CanTradeWithStatus,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TradeMetadataResponse {
+    pub max_items_per_side: u64,
+    pub min_value_ratio: f64,
+    pub trade_system_max_robux_percent: f64,
+    pub trade_robux_tax_percent: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TradePrivacyResponse {
+    pub trade_privacy: TradePrivacy,
+}
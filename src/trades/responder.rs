@@ -0,0 +1,592 @@
+//! A rule-based responder for inbound trades.
+//!
+//! A [`TradeResponder`] polls the inbound trades of a [`Client`], fetches the details of every trade
+//! it has not seen yet, and checks them against its [`ResponderRules`]. Trades that pass every rule
+//! are accepted, the rest are declined, or countered if a [`CounterStrategy`] offers a counter.
+//! Every decision, and every trade that could not be decided, is reported as a [`ResponderEvent`].
+
+use super::analysis::{TradeAnalysis, TradeAnalyzer, TradeFlag};
+use super::polling::{self, Poller};
+use super::{TradeDetails, TradeType};
+use crate::{timestamps, Client, Limit, RoboatError, User};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// The default value of [`TradeResponder::max_concurrency`].
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+/// The default value of [`TradeResponder::poll_interval`].
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A new offer sent in place of a trade, returned by a [`CounterStrategy`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct CounterOffer {
+    /// The uaids of the items you're offering.
+    pub your_item_uaids: Vec<u64>,
+    /// The robux you're offering, before tax.
+    pub your_robux: u64,
+    /// The uaids of the items you're asking for.
+    pub partner_item_uaids: Vec<u64>,
+    /// The robux you're asking for, before tax.
+    pub partner_robux: u64,
+}
+
+/// Builds counter offers for trades that do not give enough value, used by [`TradeResponder::counter_strategy`].
+///
+/// # Example
+/// ```
+/// use roboat::trades::analysis::TradeAnalysis;
+/// use roboat::trades::responder::{CounterOffer, CounterStrategy};
+/// use roboat::trades::TradeDetails;
+///
+/// /// Asks for the same trade without the robux we're offering.
+/// #[derive(Debug)]
+/// struct DropRobux;
+///
+/// impl CounterStrategy for DropRobux {
+///     fn counter(&self, details: &TradeDetails, _: &TradeAnalysis) -> Option<CounterOffer> {
+///         if details.your_robux == 0 {
+///             return None;
+///         }
+///
+///         Some(CounterOffer {
+///             your_item_uaids: details.your_uaids(),
+///             your_robux: 0,
+///             partner_item_uaids: details.partner_uaids(),
+///             partner_robux: details.partner_robux,
+///         })
+///     }
+/// }
+/// ```
+pub trait CounterStrategy: std::fmt::Debug + Send + Sync {
+    /// Returns the offer to counter a trade with, or `None` to decline it instead.
+    fn counter(&self, details: &TradeDetails, analysis: &TradeAnalysis) -> Option<CounterOffer>;
+}
+
+/// A rule broken by a trade, as checked by a [`TradeResponder`].
+#[non_exhaustive]
+#[derive(
+    thiserror::Error, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum ResponderReason {
+    /// The allow list of partners is not empty, and the partner is not on it.
+    #[error("Partner {0} Not Allowed")]
+    PartnerNotAllowed(u64),
+    /// The trade is older than the maximum age.
+    #[error("Trade Age {age_seconds}s Above Max Age {max_age_seconds}s")]
+    TooOld {
+        /// The age of the trade, in seconds.
+        age_seconds: u64,
+        /// The maximum age, in seconds.
+        max_age_seconds: u64,
+    },
+    /// A maximum age is set, and the creation time of the trade could not be parsed.
+    #[error("Trade Created At {0:?} Has An Unknown Age")]
+    UnknownAge(String),
+    /// The trade asks for an item you never give.
+    #[error("Item {0} Is Protected")]
+    ProtectedItem(u64),
+    /// The trade offers an item you never receive.
+    #[error("Item {0} Is Denied")]
+    DeniedItem(u64),
+    /// The analysis of the trade raised a [red flag](TradeFlag::is_red_flag).
+    #[error("Red Flag: {0:?}")]
+    RedFlag(TradeFlag),
+    /// The trade does not give enough value.
+    #[error("Net Gain {net_gain} Below Min Net Gain {min_net_gain}")]
+    GainBelowMin {
+        /// The net gain of the trade.
+        net_gain: i64,
+        /// The minimum net gain.
+        min_net_gain: i64,
+    },
+}
+
+/// What a [`TradeResponder`] decided to do with a trade.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ResponderDecision {
+    /// The trade passed every rule.
+    Accept,
+    /// The trade broke a rule.
+    Decline(ResponderReason),
+    /// The trade did not give enough value, and the [`CounterStrategy`] offered a counter.
+    Counter(ResponderReason, CounterOffer),
+}
+
+/// The result of acting on a [`ResponderDecision`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ResponderOutcome {
+    /// The trade was accepted or declined.
+    Done,
+    /// The trade was countered with a new trade, which has the id given.
+    Countered(u64),
+    /// Nothing was sent, as the responder or the client is in dry run mode.
+    DryRun,
+    /// The request failed. The trade will be tried again on the next poll.
+    Failed(String),
+}
+
+/// A trade decided by a [`TradeResponder`], and the outcome of the decision.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct HandledTrade {
+    /// The id of the trade.
+    pub trade_id: u64,
+    /// The partner of the trade.
+    pub partner: User,
+    /// The analysis of the trade the decision was based on.
+    pub analysis: TradeAnalysis,
+    /// What the responder decided to do.
+    pub decision: ResponderDecision,
+    /// The result of acting on the decision.
+    pub outcome: ResponderOutcome,
+}
+
+/// A trade handled by a [`TradeResponder`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ResponderEvent {
+    /// The trade was decided and acted on.
+    Handled(Box<HandledTrade>),
+    /// The trade was not decided, as its details could not be fetched. It will be tried again on the next poll.
+    Skipped {
+        /// The id of the trade.
+        trade_id: u64,
+        /// The error returned while fetching the details.
+        error: String,
+    },
+}
+
+impl ResponderEvent {
+    /// Returns the id of the trade the event is about.
+    pub fn trade_id(&self) -> u64 {
+        match self {
+            Self::Handled(x) => x.trade_id,
+            Self::Skipped { trade_id, .. } => *trade_id,
+        }
+    }
+}
+
+/// The rules every inbound trade must pass to be accepted by a [`TradeResponder`].
+///
+/// # Notes
+/// * Rules are checked in the order: partner allow list, max age, protected items, denied items,
+///   red flags, then min net gain. The first rule broken is the reason a trade is declined.
+/// * Trades with a [red flag](TradeFlag::is_red_flag) are always declined.
+/// * The min net gain defaults to 0, so trades that lose value are declined.
+///
+/// # Example
+/// ```
+/// use roboat::trades::responder::ResponderRules;
+/// use std::time::Duration;
+///
+/// let rules = ResponderRules::new()
+///     .min_net_gain(500)
+///     .max_age(Duration::from_secs(60 * 60 * 24))
+///     .protect_item(1365767)
+///     .deny_item(1029025);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ResponderRules {
+    min_net_gain: i64,
+    max_age: Option<Duration>,
+    protected_items: HashSet<u64>,
+    denied_items: HashSet<u64>,
+    allowed_partners: HashSet<u64>,
+}
+
+impl ResponderRules {
+    /// Creates rules that accept every trade that does not lose value and has no red flags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum [net gain](TradeAnalysis::net_gain) of an accepted trade.
+    pub fn min_net_gain(mut self, min_net_gain: i64) -> Self {
+        self.min_net_gain = min_net_gain;
+        self
+    }
+
+    /// Declines trades created longer than `max_age` ago, and trades whose creation
+    /// time cannot be parsed.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Declines trades that ask for an item with the given item id.
+    pub fn protect_item(mut self, item_id: u64) -> Self {
+        self.protected_items.insert(item_id);
+        self
+    }
+
+    /// Declines trades that offer an item with the given item id.
+    pub fn deny_item(mut self, item_id: u64) -> Self {
+        self.denied_items.insert(item_id);
+        self
+    }
+
+    /// Adds a user to the allow list. Once the allow list is not empty, trades from
+    /// every other user are declined.
+    pub fn allow_partner(mut self, user_id: u64) -> Self {
+        self.allowed_partners.insert(user_id);
+        self
+    }
+
+    /// Returns the first rule broken by a trade, if any.
+    pub fn check(
+        &self,
+        details: &TradeDetails,
+        analysis: &TradeAnalysis,
+    ) -> Result<(), ResponderReason> {
+        let partner_id = details.partner.user_id;
+
+        if !self.allowed_partners.is_empty() && !self.allowed_partners.contains(&partner_id) {
+            return Err(ResponderReason::PartnerNotAllowed(partner_id));
+        }
+
+        if let Some(max_age) = self.max_age {
+            let created = timestamps::parse_millis(&details.created)
+                .ok_or_else(|| ResponderReason::UnknownAge(details.created.clone()))?;
+            let age = Duration::from_millis(timestamps::now_millis().saturating_sub(created));
+
+            if age > max_age {
+                return Err(ResponderReason::TooOld {
+                    age_seconds: age.as_secs(),
+                    max_age_seconds: max_age.as_secs(),
+                });
+            }
+        }
+
+        if let Some(item) = details
+            .your_items
+            .iter()
+            .find(|x| self.protected_items.contains(&x.item_id))
+        {
+            return Err(ResponderReason::ProtectedItem(item.item_id));
+        }
+
+        if let Some(item) = details
+            .partner_items
+            .iter()
+            .find(|x| self.denied_items.contains(&x.item_id))
+        {
+            return Err(ResponderReason::DeniedItem(item.item_id));
+        }
+
+        if let Some(flag) = analysis.flags.iter().find(|x| x.is_red_flag()) {
+            return Err(ResponderReason::RedFlag(flag.clone()));
+        }
+
+        if analysis.net_gain < self.min_net_gain {
+            return Err(ResponderReason::GainBelowMin {
+                net_gain: analysis.net_gain,
+                min_net_gain: self.min_net_gain,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Accepts, declines, or counters inbound trades according to [`ResponderRules`].
+///
+/// # Notes
+/// * Each trade is only decided once. Trades whose action failed, or whose details could not be fetched,
+///   are tried again on the next poll.
+/// * Inbound trades are listed 100 at a time, and every page is fetched on each poll.
+/// * The details of trades are fetched, and trades are acted on, at most [`TradeResponder::max_concurrency`] at a time.
+/// * With [`TradeResponder::dry_run`] (or [`ClientBuilder::dry_run`](crate::ClientBuilder::dry_run)),
+///   trades are decided and reported, but never accepted, declined, or countered.
+/// * Only trades that give up enough value are countered, trades that break any other rule are declined.
+///
+/// # Example
+/// ```no_run
+/// use roboat::trades::responder::{ResponderEvent, ResponderRules, TradeResponder};
+/// use roboat::ClientBuilder;
+/// use std::sync::Arc;
+///
+/// const ROBLOSECURITY: &str = "roblosecurity";
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build());
+///
+/// let rules = ResponderRules::new().min_net_gain(1000);
+/// let (_handle, mut events) = TradeResponder::new(client, rules).dry_run(true).spawn();
+///
+/// while let Some(event) = events.recv().await {
+///     match event {
+///         Ok(ResponderEvent::Handled(x)) => println!("Trade {}: {:?}", x.trade_id, x.decision),
+///         Ok(ResponderEvent::Skipped { trade_id, error }) => eprintln!("Trade {}: {}", trade_id, error),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TradeResponder {
+    client: Arc<Client>,
+    rules: Arc<ResponderRules>,
+    analyzer: TradeAnalyzer,
+    counter_strategy: Option<Arc<dyn CounterStrategy>>,
+    max_concurrency: usize,
+    poll_interval: Duration,
+    dry_run: bool,
+    handled: Arc<Mutex<HashSet<u64>>>,
+}
+
+impl TradeResponder {
+    /// Creates a responder for the inbound trades of `client`.
+    pub fn new(client: Arc<Client>, rules: ResponderRules) -> Self {
+        Self {
+            client,
+            rules: Arc::new(rules),
+            analyzer: TradeAnalyzer::default(),
+            counter_strategy: None,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            dry_run: false,
+            handled: Arc::default(),
+        }
+    }
+
+    /// Sets the analyzer used to value trades. Defaults to [`TradeAnalyzer::default`].
+    pub fn analyzer(mut self, analyzer: TradeAnalyzer) -> Self {
+        self.analyzer = analyzer;
+        self
+    }
+
+    /// Sets the strategy used to counter trades that do not give enough value.
+    /// Without one, these trades are declined.
+    pub fn counter_strategy<T: CounterStrategy + 'static>(mut self, counter_strategy: T) -> Self {
+        self.counter_strategy = Some(Arc::new(counter_strategy));
+        self
+    }
+
+    /// Sets the maximum amount of trades handled at the same time. Defaults to 4.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Sets the time waited between polls by [`TradeResponder::spawn`]. Defaults to 60 seconds.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets whether trades are only decided, without being accepted, declined, or countered.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Handles every inbound trade not decided yet, and returns the events of the trades handled.
+    ///
+    /// # Notes
+    /// * Requires a valid roblosecurity.
+    /// * A trade whose details cannot be fetched is reported as [`ResponderEvent::Skipped`], and tried again
+    ///   on the next call.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](Client#standard-errors).
+    /// * All errors under [Auth Required Errors](Client#auth-required-errors).
+    pub async fn poll(&self) -> Result<Vec<ResponderEvent>, RoboatError> {
+        let mut trade_ids = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (trades, next_cursor) = self
+                .client
+                .trades(TradeType::Inbound, Limit::Hundred, cursor)
+                .await?;
+
+            trade_ids.extend(trades.into_iter().map(|x| x.trade_id));
+
+            match next_cursor {
+                Some(x) => cursor = Some(x),
+                None => break,
+            }
+        }
+
+        // Trades no longer inbound were finished, so they are forgotten to keep the set bounded.
+        let inbound: HashSet<u64> = trade_ids.iter().copied().collect();
+        self.handled.lock().unwrap().retain(|x| inbound.contains(x));
+
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+        let mut tasks = JoinSet::new();
+        let mut task_trade_ids = HashMap::new();
+
+        for trade_id in trade_ids {
+            // Marked before the task starts, so that overlapping polls do not handle a trade twice.
+            if !self.handled.lock().unwrap().insert(trade_id) {
+                continue;
+            }
+
+            let responder = self.clone();
+            let semaphore = semaphore.clone();
+
+            let task = tasks.spawn(async move {
+                // The semaphore is never closed.
+                let _permit = semaphore.acquire_owned().await.unwrap();
+                responder.handle(trade_id).await
+            });
+
+            task_trade_ids.insert(task.id(), trade_id);
+        }
+
+        let mut events = Vec::new();
+
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok(event) => events.push(event),
+                // A task that panicked never finished its trade, so it is tried again on the next poll.
+                Err(e) => {
+                    if let Some(trade_id) = task_trade_ids.get(&e.id()) {
+                        self.handled.lock().unwrap().remove(trade_id);
+
+                        events.push(ResponderEvent::Skipped {
+                            trade_id: *trade_id,
+                            error: e.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        events.sort_by_key(|x| x.trade_id());
+
+        Ok(events)
+    }
+
+    /// Runs [`TradeResponder::poll`] every [`TradeResponder::poll_interval`] in a background task,
+    /// and sends every event (or error) to the returned receiver.
+    ///
+    /// The task stops once the receiver is dropped, or can be stopped with [`JoinHandle::abort`].
+    pub fn spawn(
+        self,
+    ) -> (
+        JoinHandle<()>,
+        mpsc::Receiver<Result<ResponderEvent, RoboatError>>,
+    ) {
+        polling::spawn(self)
+    }
+
+    /// Decides what to do with a trade and acts on it.
+    async fn handle(&self, trade_id: u64) -> ResponderEvent {
+        let details = match self.client.trade_details(trade_id).await {
+            Ok(x) => x,
+            Err(e) => {
+                self.handled.lock().unwrap().remove(&trade_id);
+
+                return ResponderEvent::Skipped {
+                    trade_id,
+                    error: e.to_string(),
+                };
+            }
+        };
+
+        let analysis = self.analyzer.analyze(&details);
+
+        let decision = match self.rules.check(&details, &analysis) {
+            Ok(()) => ResponderDecision::Accept,
+            Err(reason @ ResponderReason::GainBelowMin { .. }) => {
+                match self
+                    .counter_strategy
+                    .as_ref()
+                    .and_then(|x| x.counter(&details, &analysis))
+                {
+                    Some(offer) => ResponderDecision::Counter(reason, offer),
+                    None => ResponderDecision::Decline(reason),
+                }
+            }
+            Err(reason) => ResponderDecision::Decline(reason),
+        };
+
+        let outcome = match self.dry_run {
+            true => ResponderOutcome::DryRun,
+            false => self.act(&details, &decision).await,
+        };
+
+        if matches!(outcome, ResponderOutcome::Failed(_)) {
+            self.handled.lock().unwrap().remove(&trade_id);
+        }
+
+        ResponderEvent::Handled(Box::new(HandledTrade {
+            trade_id,
+            partner: details.partner,
+            analysis,
+            decision,
+            outcome,
+        }))
+    }
+
+    async fn act(&self, details: &TradeDetails, decision: &ResponderDecision) -> ResponderOutcome {
+        let trade_id = details.trade_id;
+
+        let result = match decision {
+            ResponderDecision::Accept => self.client.accept_trade(trade_id).await.map(|_| None),
+            ResponderDecision::Decline(_) => {
+                self.client.decline_trade(trade_id).await.map(|_| None)
+            }
+            ResponderDecision::Counter(_, offer) => self
+                .client
+                .counter_trade_with_partner(
+                    trade_id,
+                    details.partner.user_id,
+                    offer.your_item_uaids.clone(),
+                    offer.your_robux,
+                    offer.partner_item_uaids.clone(),
+                    offer.partner_robux,
+                )
+                .await
+                .map(Some),
+        };
+
+        match result {
+            Ok(None) => ResponderOutcome::Done,
+            Ok(Some(new_trade_id)) => ResponderOutcome::Countered(new_trade_id),
+            Err(RoboatError::DryRun(_)) => ResponderOutcome::DryRun,
+            Err(e) => ResponderOutcome::Failed(e.to_string()),
+        }
+    }
+}
+
+impl Poller for TradeResponder {
+    type Event = ResponderEvent;
+
+    fn poll_events(&self) -> impl Future<Output = Result<Vec<ResponderEvent>, RoboatError>> + Send {
+        self.poll()
+    }
+
+    fn interval(&self) -> Duration {
+        self.poll_interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResponderReason, ResponderRules};
+    use crate::trades::analysis::TradeAnalysis;
+    use crate::trades::TradeDetails;
+    use std::time::Duration;
+
+    #[test]
+    fn max_age_declines_trades_with_an_unparseable_creation_time() {
+        let rules = ResponderRules::new().max_age(Duration::from_secs(60));
+
+        let details = TradeDetails {
+            created: "not a timestamp".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            rules.check(&details, &TradeAnalysis::default()),
+            Err(ResponderReason::UnknownAge("not a timestamp".to_string()))
+        );
+    }
+}
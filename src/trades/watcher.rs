@@ -0,0 +1,392 @@
+//! A watcher that turns changes to the trades of an account into events.
+//!
+//! A [`TradeWatcher`] polls the open (inbound and outbound) trades of a [`Client`], and compares
+//! them to the trades it saw on the previous poll, kept in a [`WatcherState`]. New inbound trades,
+//! and open trades that were accepted, declined, expired, countered, or rejected, are reported
+//! as [`TradeEvent`]s.
+//!
+//! The state can be saved to a file with [`TradeWatcher::state_file`], so that a restarted
+//! watcher does not report the same changes twice.
+
+use super::polling::{self, Poller};
+use super::{Trade, TradeStatus, TradeType};
+use crate::{files, Client, Limit, RoboatError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+/// The default value of [`TradeWatcher::poll_interval`].
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An open trade followed by a [`TradeWatcher`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct WatchedTrade {
+    /// The trade, as last seen. Its status is updated when the trade closes.
+    pub trade: Trade,
+    /// Whether the trade is [`TradeType::Inbound`] or [`TradeType::Outbound`].
+    pub trade_type: TradeType,
+}
+
+/// A change to the trades of an account, reported by a [`TradeWatcher`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TradeEvent {
+    /// A new inbound trade was received.
+    NewInbound(WatchedTrade),
+    /// An open trade was completed.
+    Accepted(WatchedTrade),
+    /// An open trade was declined, by either side.
+    Declined(WatchedTrade),
+    /// An open trade expired.
+    Expired(WatchedTrade),
+    /// An open trade was countered with a new trade.
+    Countered(WatchedTrade),
+    /// An open trade was accepted but could not be completed, usually because an item was no
+    /// longer owned.
+    RejectedDueToError(WatchedTrade),
+    /// An open trade closed with a status not known by this crate, which is kept in
+    /// the [`Trade::status`] of the trade.
+    Closed(WatchedTrade),
+}
+
+impl TradeEvent {
+    /// Returns the trade the event is about.
+    pub fn trade(&self) -> &WatchedTrade {
+        match self {
+            Self::NewInbound(x)
+            | Self::Accepted(x)
+            | Self::Declined(x)
+            | Self::Expired(x)
+            | Self::Countered(x)
+            | Self::RejectedDueToError(x)
+            | Self::Closed(x) => x,
+        }
+    }
+
+    /// Returns the event for a trade that closed with `status`, or `None` if the trade is still open.
+    fn closed(trade: WatchedTrade) -> Option<Self> {
+        match trade.trade.status {
+            TradeStatus::Completed => Some(Self::Accepted(trade)),
+            TradeStatus::Declined => Some(Self::Declined(trade)),
+            TradeStatus::Expired => Some(Self::Expired(trade)),
+            TradeStatus::Countered => Some(Self::Countered(trade)),
+            TradeStatus::RejectedDueToError => Some(Self::RejectedDueToError(trade)),
+            TradeStatus::Unknown(_) => Some(Self::Closed(trade)),
+            TradeStatus::Open => None,
+        }
+    }
+}
+
+/// The trades seen by a [`TradeWatcher`], used to find what changed since the last poll.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct WatcherState {
+    /// The open trades seen on the last poll, by trade id.
+    pub open: BTreeMap<u64, WatchedTrade>,
+    /// The inbound trade count returned by [`Client::trade_count`] on the last poll.
+    pub inbound_count: Option<u64>,
+}
+
+impl WatcherState {
+    /// Reads a state from a JSON file. Returns an empty state if the file does not exist.
+    ///
+    /// # Errors
+    /// * [`RoboatError::IoError`] - If the file exists but cannot be read.
+    /// * [`RoboatError::InvalidConfig`] - If the file is not a valid state.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RoboatError> {
+        match fs::read_to_string(path) {
+            Ok(x) => serde_json::from_str(&x).map_err(|e| {
+                RoboatError::InvalidConfig(format!("invalid trade watcher state: {}", e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the state to a JSON file. The file is replaced atomically, so an interrupted
+    /// write never leaves a partial state behind.
+    ///
+    /// # Errors
+    /// * [`RoboatError::IoError`] - If the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RoboatError> {
+        files::write_json_atomic(path.as_ref(), self)
+    }
+}
+
+/// Polls the trades of an account and reports what changed as [`TradeEvent`]s.
+///
+/// # Notes
+/// * Requires a valid roblosecurity.
+/// * Every poll lists the inbound trades (unless [`Client::trade_count`] is 0 and no inbound trade
+///   is open) and the outbound trades. The completed trades are listed, and the details of closed
+///   trades are fetched, only when an open trade closes.
+/// * Without a saved state, every inbound trade open on the first poll is reported as new.
+/// * Only trades that were seen open are reported when they close, so old trades are never reported.
+/// * A closed trade whose status cannot be fetched is kept open, and checked again on the next poll.
+/// * A trade that closes with a status not known by this crate is reported as [`TradeEvent::Closed`].
+///
+/// # Example
+/// ```no_run
+/// use roboat::trades::watcher::{TradeEvent, TradeWatcher};
+/// use roboat::ClientBuilder;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// const ROBLOSECURITY: &str = "roblosecurity";
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(ClientBuilder::new().roblosecurity(ROBLOSECURITY.to_string()).build());
+///
+/// let (_handle, mut events) = TradeWatcher::new(client)
+///     .poll_interval(Duration::from_secs(30))
+///     .state_file("trade_watcher.json")
+///     .spawn();
+///
+/// while let Some(event) = events.recv().await {
+///     match event {
+///         Ok(TradeEvent::NewInbound(x)) => println!("New trade from {}", x.trade.partner.username),
+///         Ok(event) => println!("Trade {}: {:?}", event.trade().trade.trade_id, event),
+///         Err(e) => eprintln!("Error: {}", e),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TradeWatcher {
+    client: Arc<Client>,
+    poll_interval: Duration,
+    state_path: Option<PathBuf>,
+    /// `None` until the state is first needed, so that the state file is only read once.
+    state: Arc<Mutex<Option<WatcherState>>>,
+}
+
+impl TradeWatcher {
+    /// Creates a watcher for the trades of `client`, with an empty state.
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            state_path: None,
+            state: Arc::default(),
+        }
+    }
+
+    /// Sets the time waited between polls by [`TradeWatcher::spawn`]. Defaults to 60 seconds.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the state the watcher starts from, usually one returned by
+    /// [`TradeWatcher::current_state`].
+    pub fn state(mut self, state: WatcherState) -> Self {
+        self.state = Arc::new(Mutex::new(Some(state)));
+        self
+    }
+
+    /// Sets a file the state is read from on the first poll (unless [`TradeWatcher::state`]
+    /// is used), and saved to after every poll.
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    /// Returns a copy of the current state.
+    pub async fn current_state(&self) -> WatcherState {
+        self.state.lock().await.clone().unwrap_or_default()
+    }
+
+    /// Compares the trades of the account to the last poll, and returns what changed.
+    ///
+    /// # Notes
+    /// * Polls are run one at a time, even if called at the same time.
+    /// * If an error is returned, the state is left unchanged.
+    ///
+    /// # Errors
+    /// * All errors under [Standard Errors](Client#standard-errors).
+    /// * All errors under [Auth Required Errors](Client#auth-required-errors).
+    /// * [`RoboatError::IoError`] - If the state file cannot be read or written.
+    /// * [`RoboatError::InvalidConfig`] - If the state file is not a valid state.
+    pub async fn poll(&self) -> Result<Vec<TradeEvent>, RoboatError> {
+        let mut guard = self.state.lock().await;
+
+        let mut state = match guard.take() {
+            Some(x) => x,
+            None => match &self.state_path {
+                Some(path) => WatcherState::load(path)?,
+                None => WatcherState::default(),
+            },
+        };
+
+        // Put back right away, so that the state is kept if the poll fails.
+        *guard = Some(state.clone());
+
+        let inbound_count = self.client.trade_count().await?;
+
+        let has_open_inbound = state
+            .open
+            .values()
+            .any(|x| x.trade_type == TradeType::Inbound);
+
+        let inbound = match inbound_count == 0 && !has_open_inbound {
+            true => Vec::new(),
+            false => self.list(TradeType::Inbound).await?,
+        };
+        let outbound = self.list(TradeType::Outbound).await?;
+
+        let mut events = Vec::new();
+        let mut open = BTreeMap::new();
+
+        for (trade_type, trades) in [
+            (TradeType::Inbound, inbound),
+            (TradeType::Outbound, outbound),
+        ] {
+            for trade in trades {
+                let watched = WatchedTrade { trade, trade_type };
+
+                if trade_type == TradeType::Inbound
+                    && !state.open.contains_key(&watched.trade.trade_id)
+                {
+                    events.push(TradeEvent::NewInbound(watched.clone()));
+                }
+
+                open.insert(watched.trade.trade_id, watched);
+            }
+        }
+
+        let mut closed: Vec<WatchedTrade> = state
+            .open
+            .values()
+            .filter(|x| !open.contains_key(&x.trade.trade_id))
+            .cloned()
+            .collect();
+
+        if !closed.is_empty() {
+            // Completed trades are listed newest first, so recently closed ones are on the first page.
+            let (completed, _) = self
+                .client
+                .trades(TradeType::Completed, Limit::Hundred, None)
+                .await?;
+
+            let completed: HashSet<u64> = completed.into_iter().map(|x| x.trade_id).collect();
+
+            for watched in &mut closed {
+                if completed.contains(&watched.trade.trade_id) {
+                    watched.trade.status = TradeStatus::Completed;
+                    watched.trade.is_active = false;
+                } else if let Ok(details) = self.client.trade_details(watched.trade.trade_id).await
+                {
+                    watched.trade.status = details.status;
+                    watched.trade.is_active = details.is_active;
+                }
+            }
+        }
+
+        for watched in closed {
+            match watched.trade.status {
+                // Still open (the lists can lag behind), or the status could not be fetched.
+                TradeStatus::Open => {
+                    open.insert(watched.trade.trade_id, watched);
+                }
+                _ => events.extend(TradeEvent::closed(watched)),
+            }
+        }
+
+        state.open = open;
+        state.inbound_count = Some(inbound_count);
+
+        if let Some(path) = &self.state_path {
+            state.save(path)?;
+        }
+
+        *guard = Some(state);
+
+        Ok(events)
+    }
+
+    /// Runs [`TradeWatcher::poll`] every [`TradeWatcher::poll_interval`] in a background task,
+    /// and sends every event (or error) to the returned receiver.
+    ///
+    /// The task stops once the receiver is dropped, or can be stopped with [`JoinHandle::abort`].
+    pub fn spawn(
+        self,
+    ) -> (
+        JoinHandle<()>,
+        mpsc::Receiver<Result<TradeEvent, RoboatError>>,
+    ) {
+        polling::spawn(self)
+    }
+
+    /// Lists every page of trades of a type.
+    async fn list(&self, trade_type: TradeType) -> Result<Vec<Trade>, RoboatError> {
+        let mut trades = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (page, next_cursor) = self
+                .client
+                .trades(trade_type, Limit::Hundred, cursor)
+                .await?;
+
+            trades.extend(page);
+
+            match next_cursor {
+                Some(x) => cursor = Some(x),
+                None => break,
+            }
+        }
+
+        Ok(trades)
+    }
+}
+
+impl Poller for TradeWatcher {
+    type Event = TradeEvent;
+
+    fn poll_events(&self) -> impl Future<Output = Result<Vec<TradeEvent>, RoboatError>> + Send {
+        self.poll()
+    }
+
+    fn interval(&self) -> Duration {
+        self.poll_interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TradeEvent, WatchedTrade};
+    use crate::trades::{Trade, TradeStatus, TradeType};
+
+    fn watched(status: TradeStatus) -> WatchedTrade {
+        WatchedTrade {
+            trade: Trade {
+                status,
+                ..Default::default()
+            },
+            trade_type: TradeType::Inbound,
+        }
+    }
+
+    #[test]
+    fn reports_every_closed_status() {
+        assert_eq!(TradeEvent::closed(watched(TradeStatus::Open)), None);
+        assert_eq!(
+            TradeEvent::closed(watched(TradeStatus::Completed)),
+            Some(TradeEvent::Accepted(watched(TradeStatus::Completed)))
+        );
+
+        let status = TradeStatus::Unknown("Moderated".to_string());
+
+        assert_eq!(
+            TradeEvent::closed(watched(status.clone())),
+            Some(TradeEvent::Closed(watched(status)))
+        );
+    }
+}
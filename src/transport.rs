@@ -0,0 +1,599 @@
+use crate::audit::{AuditEntry, AuditOutcome};
+use crate::client::ROBLOSECURITY_COOKIE_PREFIX;
+use crate::config::RateLimitPolicy;
+use crate::meta::record_response_meta;
+use crate::metrics::{
+    MetricLabel, REQUESTS_TOTAL, REQUEST_DURATION_SECONDS, REQUEST_ERRORS_TOTAL,
+    REQUEST_RETRIES_TOTAL,
+};
+use crate::testing::{Interceptor, RecordedPart, RecordedRequest};
+use crate::users::{ClientUserInformation, AUTHENTICATED_USER_DETAILS_API};
+use crate::{timestamps, Client, RoboatError, XCSRF_HEADER};
+use reqwest::header::{self, HeaderMap};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Request, RequestBuilder, Response, StatusCode};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// The value used in place of sensitive header values.
+const REDACTED: &str = "[redacted]";
+
+/// Marks a response to a request that changed state on Roblox, so that it is never rejected
+/// by [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema) after the change was made.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MutatingResponse;
+
+/// Paces the requests of a [`Client`] so that they stay under a [`RateLimitPolicy`].
+///
+/// Every request reserves the next free slot, and waits until it is reached.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next_slot: tokio::sync::Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / policy.requests_per_second,
+            next_slot: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until a request can be sent.
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+
+        tokio::time::sleep_until(slot.into()).await;
+    }
+}
+
+tokio::task_local! {
+    /// The amount of times the current request has been retried. Only set inside [`Client::retry`].
+    static RETRY_COUNT: u32;
+}
+
+impl Client {
+    /// Sends a request built from [`Client::reqwest_client`] and validates the response.
+    ///
+    /// Every endpoint method goes through here so that instrumentation only has to happen
+    /// in one place. `endpoint` is a low cardinality name for the endpoint (e.g. `trades.send_trade`)
+    /// and is used as a label for metrics and spans.
+    ///
+    /// If this returns successfully, the response is guaranteed to have a status code of 200.
+    ///
+    /// Requests sent through here must not change state on Roblox. Use [`Client::send_mutating_request`]
+    /// for those instead.
+    pub(crate) async fn send_request(
+        &self,
+        request_builder: RequestBuilder,
+        endpoint: &'static str,
+    ) -> Result<Response, RoboatError> {
+        self.send_request_inner(request_builder, endpoint, false, Vec::new())
+            .await
+    }
+
+    /// Sends a request that changes state on Roblox, the same way as [`Client::send_request`].
+    ///
+    /// The request is not sent in dry run mode, is never repeated by the
+    /// [`RetryPolicy`](crate::config::RetryPolicy), and is written to the
+    /// [`AuditSink`](crate::audit::AuditSink) if one is set.
+    pub(crate) async fn send_mutating_request(
+        &self,
+        request_builder: RequestBuilder,
+        endpoint: &'static str,
+    ) -> Result<Response, RoboatError> {
+        self.send_request_inner(request_builder, endpoint, true, Vec::new())
+            .await
+    }
+
+    /// Sends a request with a multipart body that changes state on Roblox, the same way as
+    /// [`Client::send_mutating_request`]. `parts` describes the body, as it cannot be read back
+    /// from the request.
+    pub(crate) async fn send_multipart_request(
+        &self,
+        request_builder: RequestBuilder,
+        endpoint: &'static str,
+        parts: Vec<RecordedPart>,
+    ) -> Result<Response, RoboatError> {
+        self.send_request_inner(request_builder, endpoint, true, parts)
+            .await
+    }
+
+    async fn send_request_inner(
+        &self,
+        request_builder: RequestBuilder,
+        endpoint: &'static str,
+        mutating: bool,
+        parts: Vec<RecordedPart>,
+    ) -> Result<Response, RoboatError> {
+        let mut request = request_builder.build().map_err(RoboatError::ReqwestError)?;
+
+        self.apply_base_url(&mut request);
+
+        if self.dry_run && mutating {
+            let recorded_request = RecordedRequest {
+                parts,
+                ..RecordedRequest::from_request(&request)
+            };
+
+            self.record_audit_entry(
+                endpoint,
+                recorded_request.clone(),
+                AuditOutcome::DryRun,
+                None,
+                None,
+            )
+            .await;
+
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                endpoint,
+                method = %recorded_request.method,
+                url = %recorded_request.url,
+                headers = ?recorded_request.headers,
+                body = ?recorded_request.body,
+                "dry run, request not sent"
+            );
+
+            return Err(RoboatError::DryRun(Box::new(recorded_request)));
+        }
+
+        let method = request.method().to_string();
+        let retry_count = RETRY_COUNT.try_with(|x| *x).unwrap_or(0);
+
+        // The request is moved when it is sent, so the audited copy is made beforehand.
+        let audit_request = match (&self.audit_sink, mutating) {
+            (Some(_), true) => Some(RecordedRequest {
+                parts,
+                ..RecordedRequest::from_request(&request)
+            }),
+            _ => None,
+        };
+
+        #[cfg(feature = "tracing")]
+        let span = {
+            let user_id = self
+                .user_information
+                .read()
+                .await
+                .as_ref()
+                .map(|x| x.user_id);
+
+            let span = tracing::info_span!(
+                "roboat_request",
+                endpoint,
+                method = %method,
+                url = %request.url(),
+                retry_count,
+                user_id,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            );
+
+            tracing::trace!(
+                parent: &span,
+                headers = ?redact_headers(request.headers()),
+                "sending request"
+            );
+
+            span
+        };
+
+        let start = Instant::now();
+
+        #[cfg(feature = "tracing")]
+        let request_result = {
+            use tracing::Instrument;
+
+            self.dispatch_with_retries(request, mutating, endpoint)
+                .instrument(span.clone())
+                .await
+        };
+
+        #[cfg(not(feature = "tracing"))]
+        let request_result = self
+            .dispatch_with_retries(request, mutating, endpoint)
+            .await;
+
+        let latency = start.elapsed();
+
+        if let Ok(response) = &request_result {
+            record_response_meta(response, latency);
+            self.update_rotated_roblosecurity(response);
+        }
+
+        let status_code = request_result.as_ref().ok().map(|x| x.status().as_u16());
+
+        let status = match status_code {
+            Some(x) => x.to_string(),
+            None => "none".to_string(),
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            span.record("status", status.as_str());
+            span.record("latency_ms", latency.as_millis() as u64);
+        }
+
+        let mut result = Self::validate_request_result(request_result).await;
+
+        if let (Ok(response), true) = (&mut result, mutating) {
+            response.extensions_mut().insert(MutatingResponse);
+        }
+
+        #[cfg(feature = "tracing")]
+        if let Err(e) = &result {
+            tracing::debug!(parent: &span, error = %e, "request failed");
+        }
+
+        if let Some(audit_request) = audit_request {
+            // Attempts with an invalid x-csrf-token are repeated by the method, so only the repeat is recorded.
+            let repeated = matches!(result, Err(RoboatError::InvalidXcsrf(_))) && retry_count == 0;
+
+            if !repeated {
+                let (outcome, error) = match &result {
+                    Ok(_) => (AuditOutcome::Success, None),
+                    Err(e) => (AuditOutcome::Failure, Some(e.to_string())),
+                };
+
+                self.record_audit_entry(endpoint, audit_request, outcome, status_code, error)
+                    .await;
+            }
+        }
+
+        if let Some(metrics_sink) = &self.metrics_sink {
+            let labels: [MetricLabel; 3] = [
+                ("endpoint", endpoint.to_string()),
+                ("method", method),
+                ("status", status),
+            ];
+
+            metrics_sink.increment_counter(REQUESTS_TOTAL, &labels);
+            metrics_sink.record_histogram(REQUEST_DURATION_SECONDS, latency.as_secs_f64(), &labels);
+
+            if result.is_err() {
+                metrics_sink.increment_counter(REQUEST_ERRORS_TOTAL, &labels);
+            }
+
+            if retry_count > 0 {
+                metrics_sink.increment_counter(REQUEST_RETRIES_TOTAL, &labels[..2]);
+            }
+        }
+
+        result
+    }
+
+    /// Sends a request without validating the response, returning it along with the time taken to receive it.
+    ///
+    /// Used where any response counts as a success, such as when warming up connections. The base url,
+    /// rate limit, and interceptor still apply, but the request is not instrumented.
+    pub(crate) async fn send_request_unvalidated(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<(Response, Duration), RoboatError> {
+        let mut request = request_builder.build().map_err(RoboatError::ReqwestError)?;
+
+        self.apply_base_url(&mut request);
+        self.wait_for_rate_limit().await;
+
+        let start = Instant::now();
+        let response = self.dispatch(request).await?;
+
+        Ok((response, start.elapsed()))
+    }
+
+    /// Replaces the roblosecurity of the client if Roblox rotated it through a `set-cookie` header.
+    fn update_rotated_roblosecurity(&self, response: &Response) {
+        let rotated = response
+            .headers()
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .filter_map(|x| x.to_str().ok())
+            .filter_map(|x| x.strip_prefix(ROBLOSECURITY_COOKIE_PREFIX))
+            .map(|x| x.split(';').next().unwrap_or_default())
+            // Roblox clears the cookie with an empty value when logging out.
+            .find(|x| !x.is_empty());
+
+        let rotated = match rotated {
+            Some(x) if self.roblosecurity().as_deref() != Some(x) => x,
+            _ => return,
+        };
+
+        // A roblosecurity Roblox sent can never contain invalid header characters.
+        let _ = self.set_roblosecurity(rotated);
+
+        #[cfg(feature = "tracing")]
+        tracing::info!("roblosecurity rotated by roblox");
+    }
+
+    /// Writes an entry to the [`AuditSink`](crate::audit::AuditSink), if one is set.
+    async fn record_audit_entry(
+        &self,
+        endpoint: &'static str,
+        request: RecordedRequest,
+        outcome: AuditOutcome,
+        status: Option<u16>,
+        error: Option<String>,
+    ) {
+        let audit_sink = match &self.audit_sink {
+            Some(x) => x,
+            None => return,
+        };
+
+        audit_sink.record(&AuditEntry {
+            timestamp: timestamps::now_millis(),
+            account_id: self.audit_account_id().await,
+            action: endpoint.to_string(),
+            method: request.method,
+            url: request.url,
+            body: request.body,
+            outcome,
+            status,
+            error,
+        });
+    }
+
+    /// Writes an entry to the [`AuditSink`](crate::audit::AuditSink) (if one is set) for a call of a mutating
+    /// method that was rejected before a request was built. `parameters` are the arguments of the call.
+    pub(crate) async fn record_rejected_call(
+        &self,
+        endpoint: &'static str,
+        parameters: serde_json::Value,
+        error: &RoboatError,
+    ) {
+        let audit_sink = match &self.audit_sink {
+            Some(x) => x,
+            None => return,
+        };
+
+        audit_sink.record(&AuditEntry {
+            timestamp: timestamps::now_millis(),
+            account_id: self.audit_account_id().await,
+            action: endpoint.to_string(),
+            method: String::new(),
+            url: String::new(),
+            body: Some(parameters.to_string()),
+            outcome: AuditOutcome::Rejected,
+            status: None,
+            error: Some(error.to_string()),
+        });
+    }
+
+    /// Returns the user id of the account, fetching it if it is not cached.
+    async fn audit_account_id(&self) -> Option<u64> {
+        let cached = self
+            .user_information
+            .read()
+            .await
+            .as_ref()
+            .map(|x| x.user_id);
+
+        if cached.is_some() || self.cookie_string().is_err() {
+            return cached;
+        }
+
+        // The request is sent without validation so that it is not audited itself.
+        let cookie = self.cookie_string().ok()?;
+        let request_builder = self
+            .reqwest_client
+            .get(AUTHENTICATED_USER_DETAILS_API)
+            .header(header::COOKIE, cookie);
+
+        let (response, _) = self.send_request_unvalidated(request_builder).await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+
+        let user_information = response.json::<ClientUserInformation>().await.ok()?;
+        let user_id = user_information.user_id;
+        self.set_user_information(user_information).await;
+
+        Some(user_id)
+    }
+
+    /// Replaces the scheme, host, and port of the request if a base url is set for its host,
+    /// and prepends the path of the base url.
+    fn apply_base_url(&self, request: &mut Request) {
+        let base_url = match request
+            .url()
+            .host_str()
+            .and_then(|host| self.base_urls.get(host))
+        {
+            Some(x) => x,
+            None => return,
+        };
+
+        let mut url = base_url.clone();
+        let path = format!(
+            "{}{}",
+            base_url.path().trim_end_matches('/'),
+            request.url().path()
+        );
+
+        url.set_path(&path);
+        url.set_query(request.url().query());
+
+        *request.url_mut() = url;
+    }
+
+    /// Dispatches the request, pacing it with the [`RateLimiter`] and repeating it according to
+    /// the [`RetryPolicy`](crate::config::RetryPolicy) if it is read-only and failed temporarily.
+    async fn dispatch_with_retries(
+        &self,
+        request: Request,
+        mutating: bool,
+        endpoint: &'static str,
+    ) -> Result<Response, RoboatError> {
+        let retry_policy = match &self.retry_policy {
+            Some(x) if !mutating => x,
+            _ => {
+                self.wait_for_rate_limit().await;
+                return self.dispatch(request).await;
+            }
+        };
+
+        let mut attempt = 0;
+        let mut request = request;
+
+        loop {
+            // Requests with a streaming body cannot be repeated.
+            let next_request = match attempt < retry_policy.max_retries {
+                true => request.try_clone(),
+                false => None,
+            };
+
+            self.wait_for_rate_limit().await;
+            let result = self.dispatch(request).await;
+
+            let next_request = match (next_request, &result) {
+                (Some(x), Err(RoboatError::ReqwestError(_))) => x,
+                (Some(x), Ok(response)) if is_temporary_failure(response.status()) => x,
+                _ => return result,
+            };
+
+            let delay = match &result {
+                Ok(response) => retry_after(response),
+                Err(_) => None,
+            }
+            .unwrap_or_else(|| retry_policy.backoff(attempt))
+            .min(retry_policy.max_backoff);
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                endpoint,
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                "request failed temporarily, repeating"
+            );
+
+            if let Some(metrics_sink) = &self.metrics_sink {
+                let labels: [MetricLabel; 2] = [
+                    ("endpoint", endpoint.to_string()),
+                    ("method", next_request.method().to_string()),
+                ];
+
+                metrics_sink.increment_counter(REQUEST_RETRIES_TOTAL, &labels);
+            }
+
+            tokio::time::sleep(delay).await;
+
+            attempt += 1;
+            request = next_request;
+        }
+    }
+
+    async fn wait_for_rate_limit(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+    }
+
+    /// Sends the request to Roblox, or hands it to the [`Interceptor`] if one is set.
+    async fn dispatch(&self, request: Request) -> Result<Response, RoboatError> {
+        match &self.interceptor {
+            None => self
+                .reqwest_client
+                .execute(request)
+                .await
+                .map_err(RoboatError::ReqwestError),
+            Some(Interceptor::Recorder(recorder)) => {
+                recorder.record(&self.reqwest_client, request).await
+            }
+            Some(Interceptor::Replayer(replayer)) => replayer.replay(request),
+            Some(Interceptor::FakeRoblox(fake_roblox)) => fake_roblox.handle(request),
+        }
+    }
+
+    /// Runs a retried attempt of a request (for example, after the x-csrf-token was rotated)
+    /// so that it is reported as a retry in metrics and spans.
+    pub(crate) async fn retry<F: Future>(future: F) -> F::Output {
+        let retry_count = RETRY_COUNT.try_with(|x| *x).unwrap_or(0);
+        RETRY_COUNT.scope(retry_count + 1, future).await
+    }
+}
+
+/// Whether a status code means the request may succeed if it is repeated.
+fn is_temporary_failure(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Returns the value of the `retry-after` header (in seconds), if it exists and is a number.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Returns the headers of a request with the values of the cookie and x-csrf-token headers redacted.
+pub(crate) fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if name == header::COOKIE || name == XCSRF_HEADER || value.is_sensitive() {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or(REDACTED).to_string()
+            };
+
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// A multipart form that keeps a description of its parts, so that they can be recorded
+/// when the request is not sent (in dry run mode) or audited.
+#[derive(Debug, Default)]
+pub(crate) struct DescribedForm {
+    form: Form,
+    parts: Vec<RecordedPart>,
+}
+
+impl DescribedForm {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a text field.
+    pub(crate) fn text(mut self, name: &str, value: String) -> Self {
+        self.parts.push(RecordedPart {
+            name: name.to_string(),
+            file_name: None,
+            len: value.len() as u64,
+            text: Some(value.clone()),
+        });
+
+        self.form = self.form.text(name.to_string(), value);
+        self
+    }
+
+    /// Adds a file field. The contents of the file are not recorded.
+    pub(crate) fn file(mut self, name: &str, contents: Vec<u8>, file_name: String) -> Self {
+        self.parts.push(RecordedPart {
+            name: name.to_string(),
+            file_name: Some(file_name.clone()),
+            len: contents.len() as u64,
+            text: None,
+        });
+
+        self.form = self
+            .form
+            .part(name.to_string(), Part::bytes(contents).file_name(file_name));
+        self
+    }
+
+    /// Returns the form, and the description of its parts.
+    pub(crate) fn into_parts(self) -> (Form, Vec<RecordedPart>) {
+        (self.form, self.parts)
+    }
+}
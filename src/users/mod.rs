@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 
 mod request_types;
 
-const AUTHENTICATED_USER_DETAILS_API: &str = "https://users.roblox.com/v1/users/authenticated";
+pub(crate) const AUTHENTICATED_USER_DETAILS_API: &str =
+    "https://users.roblox.com/v1/users/authenticated";
 const USERS_SEARCH_API: &str = "https://users.roblox.com/v1/users/search";
 const USER_DETAILS_API: &str = "https://users.roblox.com/v1/users/{user_id}";
 const USER_FROM_USERNAME_API: &str = "https://users.roblox.com/v1/usernames/users";
@@ -71,15 +72,15 @@ impl Client {
     ) -> Result<ClientUserInformation, RoboatError> {
         let cookie = self.cookie_string()?;
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(AUTHENTICATED_USER_DETAILS_API)
-            .header(header::COOKIE, cookie)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let user_information = Self::parse_to_raw::<ClientUserInformation>(response).await?;
+        let response = self
+            .send_request(request_builder, "users.user_information")
+            .await?;
+        let user_information = self.parse_to_raw::<ClientUserInformation>(response).await?;
 
         // Cache results.
         self.set_user_information(user_information.clone()).await;
@@ -128,15 +129,17 @@ impl Client {
 
         let cookie_string = self.cookie_string().unwrap_or(HeaderValue::from_static(""));
 
-        let request_result = self
+        let request_builder = self
             .reqwest_client
             .get(formatted_url)
-            .header(header::COOKIE, cookie_string)
-            .send()
-            .await;
+            .header(header::COOKIE, cookie_string);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw = Self::parse_to_raw::<request_types::UserSearchResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "users.user_search")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::UserSearchResponse>(response)
+            .await?;
 
         let mut users = Vec::new();
 
@@ -189,10 +192,12 @@ impl Client {
     pub async fn user_details(&self, user_id: u64) -> Result<UserDetails, RoboatError> {
         let formatted_url = USER_DETAILS_API.replace("{user_id}", &user_id.to_string());
 
-        let request_result = self.reqwest_client.get(formatted_url).send().await;
+        let request_builder = self.reqwest_client.get(formatted_url);
 
-        let response = Self::validate_request_result(request_result).await?;
-        let user_details = Self::parse_to_raw::<UserDetails>(response).await?;
+        let response = self
+            .send_request(request_builder, "users.user_details")
+            .await?;
+        let user_details = self.parse_to_raw::<UserDetails>(response).await?;
 
         Ok(user_details)
     }
@@ -208,7 +213,7 @@ impl Client {
     /// # Notes
     /// * Does not require a valid roblosecurity.
     /// * This is virtually the same as [`Client::user_details`] except that it can
-    /// fetch multiple users at once, and it searches using usernames instead of user IDs.
+    ///   fetch multiple users at once, and it searches using usernames instead of user IDs.
     /// * The usernames are not case sensitive.
     ///
     /// # Errors
@@ -241,19 +246,19 @@ impl Client {
         usernames: Vec<String>,
         exclude_banned_users: bool,
     ) -> Result<Vec<UsernameUserDetails>, RoboatError> {
-        let request_result = self
-            .reqwest_client
-            .post(USER_FROM_USERNAME_API)
-            .json(&request_types::UsernameUserDetailsRequest {
+        let request_builder = self.reqwest_client.post(USER_FROM_USERNAME_API).json(
+            &request_types::UsernameUserDetailsRequest {
                 usernames,
                 exclude_banned_users,
-            })
-            .send()
-            .await;
+            },
+        );
 
-        let response = Self::validate_request_result(request_result).await?;
-        let raw =
-            Self::parse_to_raw::<request_types::UsernameUserDetailsResponse>(response).await?;
+        let response = self
+            .send_request(request_builder, "users.username_user_details")
+            .await?;
+        let raw = self
+            .parse_to_raw::<request_types::UsernameUserDetailsResponse>(response)
+            .await?;
 
         let users = raw
             .data
@@ -1,3 +1,4 @@
+use crate::transport::MutatingResponse;
 use crate::{Client, RoboatError, XCSRF_HEADER};
 use reqwest::Response;
 use serde::de::DeserializeOwned;
@@ -153,23 +154,38 @@ impl Client {
         }
     }
 
-    /// Takes the result of a `reqwest` request and catches any possible errors, whether it be
-    /// a non-200 status code or a `reqwest` error.
+    /// Takes the result of sending a request and catches any possible errors, whether it be
+    /// a non-200 status code or an error that occurred while sending.
     ///
     /// If this returns successfully, the response is guaranteed to have a status code of 200.
     pub(crate) async fn validate_request_result(
-        request_result: Result<Response, reqwest::Error>,
+        request_result: Result<Response, RoboatError>,
     ) -> Result<Response, RoboatError> {
         match request_result {
             Ok(response) => Self::handle_non_200_status_codes(response).await,
-            Err(e) => Err(RoboatError::ReqwestError(e)),
+            Err(e) => Err(e),
         }
     }
 
     /// Parses a json from a [`reqwest::Response`] into a response struct, returning an error if the response is malformed.
+    ///
+    /// If schema drift detection is enabled, the response is compared to the struct and any differences are reported.
+    /// Responses to mutating requests are never rejected by [`ClientBuilder::strict_schema`](crate::ClientBuilder::strict_schema).
     pub(crate) async fn parse_to_raw<T: DeserializeOwned>(
+        &self,
         response: Response,
     ) -> Result<T, RoboatError> {
+        if self.detects_schema_drift() {
+            let url = response.url().to_string();
+            let mutating = response.extensions().get::<MutatingResponse>().is_some();
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(|_| RoboatError::MalformedResponse)?;
+
+            return self.parse_tracked(&bytes, Some(url), mutating, |_, _| {});
+        }
+
         let response_struct = match response.json::<T>().await {
             Ok(x) => x,
             Err(_) => {